@@ -1,4 +1,5 @@
 use clap::Parser;
+use pkcore::analysis::nut_rank::NutRank;
 use pkcore::play::board::Board;
 use pkcore::play::game::Game;
 use pkcore::play::hole_cards::HoleCards;
@@ -17,6 +18,9 @@ struct Args {
 
     #[clap(short = 'n', long)]
     nuts: bool,
+
+    #[clap(short = 'r', long)]
+    runouts: bool,
 }
 
 /// The goal of calc isn't to run a full simulation of play at a holdem poker table. It's
@@ -128,11 +132,25 @@ fn main() -> Result<(), PKError> {
     println!();
     let flop_eval = FlopEval::try_from(game.clone()).unwrap();
     println!("{}", flop_eval);
+    println!("{}", flop_eval.outs_to_string());
 
     if args.nuts {
         println!();
         println!("The Nuts @ Flop:");
         println!("{}", game.board.flop.evals());
+
+        println!();
+        println!("How close to the nuts @ Flop:");
+        for (i, two) in game.hands.iter().enumerate() {
+            if let Ok(nut_rank) = NutRank::new(two, &game.board.flop) {
+                println!("  Player #{} [{}] {}", i + 1, two, nut_rank);
+            }
+        }
+    }
+
+    if args.runouts {
+        println!();
+        println!("{}", flop_eval.runouts());
     }
 
     game.turn_display_odds()?;
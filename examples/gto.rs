@@ -1,8 +1,8 @@
 use clap::Parser;
-use pkcore::analysis::gto::combos::Combos;
 use pkcore::analysis::gto::odds::WinLoseDraw;
 use pkcore::analysis::gto::vs::Versus;
-use pkcore::analysis::store::db::hup::HUPResult;
+use pkcore::analysis::gto::weighted_range::WeightedRange;
+use pkcore::analysis::nut_rank::NutRank;
 use pkcore::arrays::two::Two;
 use pkcore::play::board::Board;
 use pkcore::play::stages::flop_eval::FlopEval;
@@ -27,6 +27,7 @@ struct Args {
 }
 
 /// `cargo run --example gto -- -p "K♠ K♥" -v "66+,AJs+,KQs,AJo+,KQo"`
+/// `cargo run --example gto -- -p "K♠ K♥" -v "QQ+,AKs:0.5,AKo:0.25"`
 fn main() -> Result<(), PKError> {
     let now = std::time::Instant::now();
     env_logger::init();
@@ -36,9 +37,13 @@ fn main() -> Result<(), PKError> {
     let solver: Versus;
 
     if let Some(board) = args.board {
-        solver = Versus::new_with_board(Two::from_str(&*args.player)?, Combos::from_str(&*args.villain)?, board);
+        solver = Versus::new_with_board(
+            Two::from_str(&*args.player)?,
+            WeightedRange::from_str(&*args.villain)?,
+            board,
+        );
     } else {
-        solver = Versus::new(Two::from_str(&*args.player)?, Combos::from_str(&*args.villain)?);
+        solver = Versus::new(Two::from_str(&*args.player)?, WeightedRange::from_str(&*args.villain)?);
     }
 
     println!("{}", solver);
@@ -61,7 +66,7 @@ fn main() -> Result<(), PKError> {
         println!("{}", hups.get(key).unwrap());
     }
 
-    let results = Versus::combined_odds_at_deal(&hups.values().collect::<Vec<&HUPResult>>());
+    let results = solver.weighted_odds_at_deal(&hups);
     println!();
     println!("Consolidated odds:");
     println!("{}", results);
@@ -73,6 +78,11 @@ fn main() -> Result<(), PKError> {
             println!("{fe}");
             println!("{}", WinLoseDraw::from(fe));
         }
+        if args.nuts {
+            if let Ok(nut_rank) = NutRank::new(&solver.hero, &solver.board.flop) {
+                println!("How close to the nuts @ Flop: {nut_rank}");
+            }
+        }
         println!("FLOP: {}", solver.combined_odds_at_flop());
         println!("TURN: {}", solver.combined_odds_at_turn());
 
@@ -0,0 +1,367 @@
+use crate::analysis::gto::twos::Twos;
+use crate::analysis::gto::weighted_range::WeightedRange;
+use crate::analysis::holding::{Holding, Kicker, MadeHand};
+use crate::arrays::two::Two;
+use crate::cards::Cards;
+use crate::{PKError, Pile};
+use std::collections::BTreeMap;
+use std::fmt::{Display, Formatter};
+
+/// What hero's cards do to villain's range.
+///
+/// When you pick a hand to bluff with, you want one that takes value hands out of villain's
+/// range, and leaves the hands that are going to fold. Holding the A♠ on a three spade board
+/// means villain can't have the nut flush. Holding a king on a king high board means there are
+/// fewer sets of kings and fewer top pairs out there.
+///
+/// `Blockers` sorts every combo in villain's range into the `MadeHand` it makes on the board,
+/// the same categories that `Holding` uses, and counts them three ways:
+///
+/// * `board` - only taking out the cards on the board.
+/// * `neutral` - what an average holding for hero would leave. Any two cards hero might have
+///   takes a villain combo out with the same odds, so this is `board` times the chance that a
+///   combo survives two random cards.
+/// * `actual` - what's left once you take out hero's two cards.
+///
+/// Comparing `actual` to `neutral`, rather than to `board`, tells us what hero's specific cards
+/// do that any other two cards wouldn't.
+///
+/// For the value/bluff ratio, value is anything `value_threshold` or better, by default top
+/// pair with a weak kicker, and bluffs are hands that have nothing: high card or the board.
+/// Everything in between is a bluff catcher, and left out.
+///
+/// ```
+/// use pkcore::analysis::blockers::Blockers;
+/// use pkcore::analysis::gto::weighted_range::WeightedRange;
+/// use pkcore::analysis::holding::MadeHand;
+/// use pkcore::arrays::two::Two;
+/// use pkcore::cards::Cards;
+/// use std::str::FromStr;
+///
+/// let board = Cards::from_str("K♠ 9♠ 5♠ 2♦ 3♥").unwrap();
+/// let villain = WeightedRange::from_str("A2s+, KQs, KJs, 99, 55, QJs, JTs, T9s, 87s").unwrap();
+///
+/// let blockers = Blockers::new(&Two::from_str("A♠ 4♦").unwrap(), &board, &villain).unwrap();
+///
+/// // Without the A♠ villain can't have the nut flush.
+/// assert!(blockers.neutral(MadeHand::NutFlush) > 0.0);
+/// assert_eq!(0.0, blockers.actual(MadeHand::NutFlush));
+/// assert!(blockers.value_bluff_ratio() < blockers.neutral_value_bluff_ratio());
+/// ```
+#[derive(Clone, Debug)]
+pub struct Blockers {
+    pub hero: Two,
+    pub board: Cards,
+    /// Villain's weighted combos by category, with only the board taken out.
+    pub board_combos: BTreeMap<MadeHand, f64>,
+    /// Villain's weighted combos by category, with hero's cards taken out as well.
+    pub actual_combos: BTreeMap<MadeHand, f64>,
+    /// The villain combos that hero's cards take out.
+    pub blocked: Twos,
+    /// The worst hand that counts as value.
+    pub value_threshold: MadeHand,
+    survival: f64,
+}
+
+impl Blockers {
+    /// # Errors
+    ///
+    /// * `PKError::InvalidCardCount` unless the board is three, four or five cards.
+    /// * `PKError::BlankCard` if hero isn't dealt.
+    /// * `PKError::DuplicateCard` if hero shares a card with the board.
+    pub fn new<P: Pile>(hero: &Two, board: &P, villain: &WeightedRange) -> Result<Blockers, PKError> {
+        let board = board.cards();
+        Holding::validate(hero, &board)?;
+
+        let mut twos = villain.twos();
+        for card in board.iter() {
+            twos = twos.filter_on_not_card(*card);
+        }
+        let remaining = twos.filter_on_not_card(hero.first()).filter_on_not_card(hero.second());
+        let blocked = twos
+            .filter_on_card(hero.first())
+            .extend(&twos.filter_on_card(hero.second()));
+
+        let mut board_combos = BTreeMap::new();
+        let mut actual_combos = BTreeMap::new();
+        for two in &twos.to_vec() {
+            let made = Holding::new(two, &board)?.made;
+            let weight = villain.weight(two);
+            *board_combos.entry(made).or_default() += weight;
+            if remaining.contains(two) {
+                *actual_combos.entry(made).or_default() += weight;
+            }
+        }
+
+        let survival = Blockers::survival(52 - board.len());
+        Ok(Blockers {
+            hero: *hero,
+            board,
+            board_combos,
+            actual_combos,
+            blocked,
+            value_threshold: MadeHand::TopPair(Kicker::Weak),
+            survival,
+        })
+    }
+
+    /// Changes what counts as value for the value/bluff ratios.
+    #[must_use]
+    pub fn with_value_threshold(mut self, value_threshold: MadeHand) -> Self {
+        self.value_threshold = value_threshold;
+        self
+    }
+
+    /// Villain's combos in the category with only the board taken out.
+    #[must_use]
+    pub fn board(&self, made: MadeHand) -> f64 {
+        self.board_combos.get(&made).copied().unwrap_or_default()
+    }
+
+    /// Villain's combos in the category that an average holding would leave.
+    #[must_use]
+    pub fn neutral(&self, made: MadeHand) -> f64 {
+        self.board(made) * self.survival
+    }
+
+    /// Villain's combos in the category that hero's actual holding leaves.
+    #[must_use]
+    pub fn actual(&self, made: MadeHand) -> f64 {
+        self.actual_combos.get(&made).copied().unwrap_or_default()
+    }
+
+    /// How many more combos of the category hero's cards take out than an average holding.
+    /// Positive means hero blocks it, negative means hero unblocks it.
+    #[must_use]
+    pub fn removed(&self, made: MadeHand) -> f64 {
+        self.neutral(made) - self.actual(made)
+    }
+
+    /// Every category villain can have, best first.
+    #[must_use]
+    pub fn categories(&self) -> Vec<MadeHand> {
+        self.board_combos.keys().rev().copied().collect()
+    }
+
+    #[must_use]
+    pub fn value(&self) -> f64 {
+        Blockers::sum(&self.actual_combos, |made| made >= self.value_threshold)
+    }
+
+    #[must_use]
+    pub fn bluffs(&self) -> f64 {
+        Blockers::sum(&self.actual_combos, Blockers::is_bluff)
+    }
+
+    /// Villain's value combos for every bluff, with hero's cards taken out. Infinite if there
+    /// are no bluffs left, and zero if there's no value left, even if there aren't any bluffs
+    /// either, so a range of nothing but bluff catchers comes out as zero.
+    #[must_use]
+    pub fn value_bluff_ratio(&self) -> f64 {
+        Blockers::ratio(self.value(), self.bluffs())
+    }
+
+    /// Villain's value combos for every bluff, for an average hero holding. The survival odds
+    /// are the same for every combo, so this is the same as the ratio with only the board
+    /// taken out.
+    #[must_use]
+    pub fn neutral_value_bluff_ratio(&self) -> f64 {
+        Blockers::ratio(
+            Blockers::sum(&self.board_combos, |made| made >= self.value_threshold),
+            Blockers::sum(&self.board_combos, Blockers::is_bluff),
+        )
+    }
+
+    /// Above zero means hero's cards take more of villain's value out than an average holding
+    /// would, which is what you want from a bluff. Below zero means they take out villain's
+    /// bluffs, which is bad news for a bluff catcher.
+    #[must_use]
+    #[allow(clippy::float_cmp)]
+    pub fn ratio_change(&self) -> f64 {
+        let neutral = self.neutral_value_bluff_ratio();
+        let actual = self.value_bluff_ratio();
+        // Infinity minus infinity is NaN, and no change is no change.
+        if neutral == actual { 0.0 } else { neutral - actual }
+    }
+
+    fn ratio(value: f64, bluffs: f64) -> f64 {
+        if value <= 0.0 { 0.0 } else { value / bluffs }
+    }
+
+    fn is_bluff(made: MadeHand) -> bool {
+        matches!(made, MadeHand::HighCard | MadeHand::BoardPlays)
+    }
+
+    fn sum(combos: &BTreeMap<MadeHand, f64>, predicate: impl Fn(MadeHand) -> bool) -> f64 {
+        combos
+            .iter()
+            .filter(|(made, _)| predicate(**made))
+            .map(|(_, weight)| weight)
+            .sum()
+    }
+
+    /// The chance that a villain combo is still there after two random cards come out of
+    /// what's left of the deck.
+    #[allow(clippy::cast_precision_loss)]
+    fn survival(remaining: usize) -> f64 {
+        let n = remaining as f64;
+        ((n - 2.0) * (n - 3.0)) / (n * (n - 1.0))
+    }
+}
+
+/// ```txt
+/// A♠ 4♦ on K♠ 9♠ 5♠ 2♦ 3♥
+///   nut flush: 0.00 (neutral 8.24, -8.24)
+///   flush: 1.00 (neutral 0.92, +0.08)
+///   ...
+///   high card: 21.00 (neutral 19.23, +1.77)
+///   value/bluff: 0.29 (neutral 0.76)
+/// ```
+impl Display for Blockers {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{} on {}", self.hero, self.board)?;
+        for made in self.categories() {
+            write!(
+                f,
+                "\n  {made}: {:.2} (neutral {:.2}, {:+.2})",
+                self.actual(made),
+                self.neutral(made),
+                -self.removed(made)
+            )?;
+        }
+        write!(
+            f,
+            "\n  value/bluff: {:.2} (neutral {:.2})",
+            self.value_bluff_ratio(),
+            self.neutral_value_bluff_ratio()
+        )
+    }
+}
+
+#[cfg(test)]
+#[allow(non_snake_case)]
+mod analysis__blockers_tests {
+    use super::*;
+    use std::str::FromStr;
+
+    fn blockers(hero: &str, board: &str, villain: &str) -> Blockers {
+        Blockers::new(
+            &Two::from_str(hero).unwrap(),
+            &Cards::from_str(board).unwrap(),
+            &WeightedRange::from_str(villain).unwrap(),
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn new__nut_flush_blocker() {
+        let blockers = blockers("A♠ 4♦", "K♠ 9♠ 5♠ 2♦ 3♥", "A2s+, KQs, QJs, JTs");
+
+        assert_eq!(9.0, blockers.board(MadeHand::NutFlush));
+        assert_eq!(0.0, blockers.actual(MadeHand::NutFlush));
+        assert!(blockers.removed(MadeHand::NutFlush) > 0.0);
+        // Nine suited aces of spades, and the A♦ 4♦.
+        assert_eq!(10, blockers.blocked.len());
+    }
+
+    #[test]
+    fn new__set_blocker() {
+        let blockers = blockers("K♥ Q♦", "K♠ 9♠ 5♥", "KK, 99, 55, AK");
+
+        // One king left for villain, so only one combo of top set.
+        assert_eq!(9.0, blockers.board(MadeHand::Set));
+        assert_eq!(7.0, blockers.actual(MadeHand::Set));
+        assert!(blockers.removed(MadeHand::Set) > 0.0);
+        assert!(blockers.removed(MadeHand::TopPair(Kicker::Top)) > 0.0);
+    }
+
+    #[test]
+    fn neutral() {
+        let blockers = blockers("A♠ 4♦", "K♠ 9♠ 5♠ 2♦ 3♥", "QQ");
+
+        // 47 cards left: 45 × 44 / (47 × 46) of the six queens.
+        assert!((blockers.neutral(MadeHand::PocketPairBelowTop) - 6.0 * 45.0 * 44.0 / (47.0 * 46.0)).abs() < 1e-9);
+        assert!(blockers.removed(MadeHand::PocketPairBelowTop) < 0.0);
+    }
+
+    /// Queens on this board are all bluff catchers: no value and no bluffs.
+    #[test]
+    fn value_bluff_ratio__no_value_or_bluffs() {
+        let blockers = blockers("A♥ 4♦", "K♠ 9♠ 5♥ 2♦ 3♣", "QQ");
+
+        assert_eq!(0.0, blockers.value_bluff_ratio());
+        assert_eq!(0.0, blockers.neutral_value_bluff_ratio());
+        assert_eq!(0.0, blockers.ratio_change());
+    }
+
+    #[test]
+    fn value_bluff_ratio__no_bluffs() {
+        let blockers = blockers("A♥ 4♦", "K♠ 9♠ 5♥ 2♦ 3♣", "KK");
+
+        assert!(blockers.value_bluff_ratio().is_infinite());
+        assert_eq!(0.0, blockers.ratio_change());
+    }
+
+    /// Quads on the board are the board's, not villain's. Ace king only plays as a kicker, so
+    /// it's a bluff.
+    #[test]
+    fn bluffs__board_quads() {
+        let blockers = blockers("Q♥ J♦", "7♥ 7♠ 7♦ 7♣ 2♥", "AKo");
+
+        assert_eq!(0.0, blockers.actual(MadeHand::Quads));
+        assert_eq!(12.0, blockers.actual(MadeHand::HighCard));
+        assert_eq!(12.0, blockers.bluffs());
+        assert_eq!(0.0, blockers.value());
+    }
+
+    #[test]
+    fn value_bluff_ratio() {
+        let board = "K♠ 9♠ 5♥ 2♦ 3♣";
+        let villain = "KK, AK, 87s, 76s";
+
+        let king = blockers("K♥ 4♦", board, villain);
+        let eight = blockers("8♥ 4♦", board, villain);
+
+        // Holding a king takes out value, holding an eight takes out bluffs.
+        assert!(king.value_bluff_ratio() < king.neutral_value_bluff_ratio());
+        assert!(king.ratio_change() > 0.0);
+        assert!(eight.value_bluff_ratio() > eight.neutral_value_bluff_ratio());
+        assert!(eight.ratio_change() < 0.0);
+    }
+
+    #[test]
+    fn with_value_threshold() {
+        let blockers = blockers("A♠ 4♦", "K♠ 9♠ 5♥", "KK, 99, AK, QJs").with_value_threshold(MadeHand::Set);
+
+        assert_eq!(6.0, blockers.value());
+    }
+
+    #[test]
+    fn new__errors() {
+        let villain = WeightedRange::from_str("AK").unwrap();
+
+        assert_eq!(
+            PKError::InvalidCardCount,
+            Blockers::new(&Two::HAND_AS_KS, &Cards::from_str("K♥ 7♦").unwrap(), &villain).unwrap_err()
+        );
+        assert_eq!(
+            PKError::DuplicateCard,
+            Blockers::new(&Two::HAND_AS_KS, &Cards::from_str("K♠ 7♦ 2♣").unwrap(), &villain).unwrap_err()
+        );
+    }
+
+    #[test]
+    fn display() {
+        let expected = "A♠ 4♦ on K♠ 9♠ 5♠ 2♦ 3♥
+  nut flush: 0.00 (neutral 8.24, -8.24)
+  flush: 1.00 (neutral 0.92, +0.08)
+  straight: 2.00 (neutral 2.75, -0.75)
+  top pair, top kicker: 3.00 (neutral 2.75, +0.25)
+  second pair: 3.00 (neutral 2.75, +0.25)
+  weak pair: 7.00 (neutral 6.41, +0.59)
+  high card: 21.00 (neutral 19.23, +1.77)
+  value/bluff: 0.29 (neutral 0.76)";
+
+        assert_eq!(expected, blockers("A♠ 4♦", "K♠ 9♠ 5♠ 2♦ 3♥", "A2s+, QJs").to_string());
+    }
+}
@@ -0,0 +1,537 @@
+use crate::arrays::three::Three;
+use crate::card::Card;
+use crate::cards::Cards;
+use crate::play::board::Board;
+use crate::rank::Rank;
+use crate::suit::Suit;
+use crate::{PKError, Pile};
+use std::collections::HashMap;
+use std::fmt::{Display, Formatter};
+
+/// How the suits on the board line up. Rainbow only happens on the flop and turn, since five
+/// cards can't all be different suits. A river can still be two-tone, like `A♠ K♠ 7♦ 7♥ 2♣`,
+/// as long as no suit shows up three times.
+#[derive(Clone, Copy, Debug, Eq, Hash, Ord, PartialEq, PartialOrd)]
+pub enum Suitedness {
+    /// No two cards share a suit.
+    Rainbow,
+    /// At least two cards share a suit, but no three, so a flush needs both hole cards.
+    TwoTone,
+    /// Three or more of a suit. Somebody might already have a flush.
+    FlushPossible,
+    /// Every card is the same suit.
+    Monotone,
+}
+
+#[derive(Clone, Copy, Debug, Eq, Hash, Ord, PartialEq, PartialOrd)]
+pub enum Pairing {
+    Unpaired,
+    Paired,
+    TwoPair,
+    Trips,
+    FullHouse,
+    Quads,
+}
+
+/// The most board cards that fit inside any five rank stretch, aces counting both high and low.
+#[derive(Clone, Copy, Debug, Eq, Hash, Ord, PartialEq, PartialOrd)]
+pub enum Connectedness {
+    /// No two cards are close enough to help make the same straight.
+    Disconnected,
+    /// Two cards could be part of the same straight, so there are straight draws.
+    Draws,
+    /// Three or four cards fit, so somebody might already have a straight.
+    StraightPossible,
+    /// The board is a straight.
+    StraightOnBoard,
+}
+
+/// The highest card on the board.
+#[derive(Clone, Copy, Debug, Eq, Hash, Ord, PartialEq, PartialOrd)]
+pub enum Height {
+    /// Eight high or lower.
+    Low,
+    /// Nine, ten or jack high.
+    Middle,
+    /// Queen or king high.
+    High,
+    AceHigh,
+}
+
+/// Every time we want to bucket boards, we end up writing the same loops over the suits and
+/// ranks of a `Three`, `Four` or `Five`. `BoardTexture` does it once, for a flop, turn or river:
+///
+/// * How the suits line up: `Suitedness`
+/// * Whether it's paired: `Pairing`
+/// * How close together the ranks are: `Connectedness`, plus `gaps()` and `has_wheel_potential()`
+/// * How high it is: `Height`, plus `broadway_count()`
+///
+/// and rolls it all up into a `wetness()` score.
+///
+/// The predicates all take `&self` and return a `bool`, so they slot straight into
+/// `flops_where()`:
+///
+/// ```
+/// use pkcore::analysis::board_texture::BoardTexture;
+/// use pkcore::cards::Cards;
+/// use std::str::FromStr;
+///
+/// let texture = BoardTexture::try_from(&Cards::from_str("J♥ T♥ 8♣").unwrap()).unwrap();
+///
+/// assert!(texture.is_two_tone());
+/// assert!(texture.is_straight_possible());
+/// assert_eq!(1, texture.gaps());
+/// assert_eq!("J♥ T♥ 8♣: two-tone, unpaired, straight possible, middle (wetness 0.85)", texture.to_string());
+///
+/// assert_eq!(1_144, BoardTexture::flops_where(BoardTexture::is_monotone).len());
+/// ```
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct BoardTexture {
+    pub cards: Cards,
+    pub suitedness: Suitedness,
+    pub pairing: Pairing,
+    pub connectedness: Connectedness,
+    pub height: Height,
+}
+
+impl BoardTexture {
+    /// Every one of the 22,100 flops that passes the test.
+    #[must_use]
+    pub fn flops_where<F: Fn(&BoardTexture) -> bool>(predicate: F) -> Vec<Three> {
+        Cards::deck()
+            .combinations(3)
+            .map(Three::from)
+            .filter(|flop| BoardTexture::try_from(flop).is_ok_and(|texture| predicate(&texture)))
+            .collect()
+    }
+
+    // region suitedness
+
+    #[must_use]
+    pub fn is_rainbow(&self) -> bool {
+        self.suitedness == Suitedness::Rainbow
+    }
+
+    #[must_use]
+    pub fn is_two_tone(&self) -> bool {
+        self.suitedness == Suitedness::TwoTone
+    }
+
+    #[must_use]
+    pub fn is_monotone(&self) -> bool {
+        self.suitedness == Suitedness::Monotone
+    }
+
+    /// Three or more cards of the same suit, so a flush only needs two from the hole.
+    #[must_use]
+    pub fn is_flush_possible(&self) -> bool {
+        self.max_suit_count() >= 3
+    }
+
+    /// The biggest number of cards of any one suit.
+    #[must_use]
+    pub fn max_suit_count(&self) -> usize {
+        self.suit_counts().values().max().copied().unwrap_or_default()
+    }
+
+    // endregion
+
+    // region pairing
+
+    #[must_use]
+    pub fn is_unpaired(&self) -> bool {
+        self.pairing == Pairing::Unpaired
+    }
+
+    /// Any board with two or more cards of the same rank, trips and all.
+    #[must_use]
+    pub fn is_paired(&self) -> bool {
+        self.pairing != Pairing::Unpaired
+    }
+
+    /// Trips or better on the board.
+    #[must_use]
+    pub fn is_trips(&self) -> bool {
+        matches!(self.pairing, Pairing::Trips | Pairing::FullHouse | Pairing::Quads)
+    }
+
+    // endregion
+
+    // region connectedness
+
+    #[must_use]
+    pub fn is_disconnected(&self) -> bool {
+        self.connectedness == Connectedness::Disconnected
+    }
+
+    /// Three cards inside five ranks, so two hole cards can make a straight.
+    #[must_use]
+    pub fn is_straight_possible(&self) -> bool {
+        self.connectedness >= Connectedness::StraightPossible
+    }
+
+    /// Two or more different cards from ace to five.
+    #[must_use]
+    pub fn has_wheel_potential(&self) -> bool {
+        self.distinct_values()
+            .iter()
+            .filter(|value| **value <= 5 || **value == 14)
+            .count()
+            >= 2
+    }
+
+    /// The number of missing ranks inside the most connected cards on the board. `J T 8` has one,
+    /// the nine. `9 8 7` has none, and so does `A K 2`, since the `A K` is as connected as it
+    /// gets.
+    #[must_use]
+    pub fn gaps(&self) -> usize {
+        let values = self.distinct_values();
+        let best = BoardTexture::max_in_window(&values);
+        (1..=10)
+            .filter_map(|low| {
+                let inside: Vec<u8> = BoardTexture::in_window(&values, low);
+                if inside.len() == best && best > 1 {
+                    let high = inside.iter().max().copied().unwrap_or_default();
+                    let low = inside.iter().min().copied().unwrap_or_default();
+                    Some(usize::from(high - low + 1) - inside.len())
+                } else {
+                    None
+                }
+            })
+            .min()
+            .unwrap_or_default()
+    }
+
+    // endregion
+
+    // region height
+
+    #[must_use]
+    pub fn high_card(&self) -> Rank {
+        self.cards.iter().map(Card::get_rank).max().unwrap_or_default()
+    }
+
+    #[must_use]
+    pub fn is_ace_high(&self) -> bool {
+        self.height == Height::AceHigh
+    }
+
+    /// Eight high or lower.
+    #[must_use]
+    pub fn is_low(&self) -> bool {
+        self.height == Height::Low
+    }
+
+    /// How many cards are ten or higher.
+    #[must_use]
+    pub fn broadway_count(&self) -> usize {
+        self.cards.iter().filter(|card| card.get_rank() >= Rank::TEN).count()
+    }
+
+    // endregion
+
+    /// A rule of thumb, from 0 for bone dry to 1 for soaking wet, for how much the board is
+    /// likely to change who's ahead: flush draws and flushes, straight draws and straights, and
+    /// lots of high cards for ranges to connect with. Pairs dry a board out.
+    #[must_use]
+    pub fn wetness(&self) -> f64 {
+        let suits = match self.max_suit_count() {
+            0 | 1 => 0.0,
+            2 => 0.3,
+            _ => 0.4,
+        };
+        let straights = match self.connectedness {
+            Connectedness::Disconnected => 0.0,
+            Connectedness::Draws => 0.15,
+            Connectedness::StraightPossible => 0.35,
+            Connectedness::StraightOnBoard => 0.4,
+        };
+        let high_cards = if self.broadway_count() >= 2 { 0.2 } else { 0.0 };
+        let score = suits + straights + high_cards;
+
+        if self.is_paired() { score * 0.75 } else { score }
+    }
+
+    /// Below a third.
+    #[must_use]
+    pub fn is_dry(&self) -> bool {
+        self.wetness() < 1.0 / 3.0
+    }
+
+    /// Above two thirds.
+    #[must_use]
+    pub fn is_wet(&self) -> bool {
+        self.wetness() > 2.0 / 3.0
+    }
+
+    fn suit_counts(&self) -> HashMap<Suit, usize> {
+        let mut counts = HashMap::new();
+        for card in self.cards.iter() {
+            *counts.entry(card.get_suit()).or_default() += 1;
+        }
+        counts
+    }
+
+    /// The board's ranks as numbers from 2 to 14, without repeats. Aces are 14 here, and
+    /// `in_window()` counts them as 1 as well.
+    fn distinct_values(&self) -> Vec<u8> {
+        let mut values: Vec<u8> = self.cards.iter().map(|card| card.get_rank() as u8).collect();
+        values.sort_unstable();
+        values.dedup();
+        values
+    }
+
+    fn in_window(values: &[u8], low: u8) -> Vec<u8> {
+        values
+            .iter()
+            .map(|value| if *value == 14 && low == 1 { 1 } else { *value })
+            .filter(|value| (low..low + 5).contains(value))
+            .collect()
+    }
+
+    fn max_in_window(values: &[u8]) -> usize {
+        (1..=10)
+            .map(|low| BoardTexture::in_window(values, low).len())
+            .max()
+            .unwrap_or_default()
+    }
+}
+
+impl Display for BoardTexture {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let suitedness = match self.suitedness {
+            Suitedness::Rainbow => "rainbow",
+            Suitedness::TwoTone => "two-tone",
+            Suitedness::FlushPossible => "flush possible",
+            Suitedness::Monotone => "monotone",
+        };
+        let pairing = match self.pairing {
+            Pairing::Unpaired => "unpaired",
+            Pairing::Paired => "paired",
+            Pairing::TwoPair => "two pair",
+            Pairing::Trips => "trips",
+            Pairing::FullHouse => "full house",
+            Pairing::Quads => "quads",
+        };
+        let connectedness = match self.connectedness {
+            Connectedness::Disconnected => "disconnected",
+            Connectedness::Draws => "straight draws",
+            Connectedness::StraightPossible => "straight possible",
+            Connectedness::StraightOnBoard => "straight on board",
+        };
+        let height = match self.height {
+            Height::Low => "low",
+            Height::Middle => "middle",
+            Height::High => "high",
+            Height::AceHigh => "ace high",
+        };
+        write!(
+            f,
+            "{}: {suitedness}, {pairing}, {connectedness}, {height} (wetness {:.2})",
+            self.cards,
+            self.wetness()
+        )
+    }
+}
+
+impl TryFrom<&Board> for BoardTexture {
+    type Error = PKError;
+
+    /// Only the cards that have been dealt, so a `Board` with just a flop gets you the flop's
+    /// texture.
+    fn try_from(board: &Board) -> Result<Self, Self::Error> {
+        let cards: Vec<Card> = board.to_vec().into_iter().filter(Card::is_dealt).collect();
+        BoardTexture::try_from(&Cards::from(cards))
+    }
+}
+
+impl TryFrom<&Cards> for BoardTexture {
+    type Error = PKError;
+
+    /// # Errors
+    ///
+    /// `PKError::InvalidCardCount` unless it's three, four or five cards.
+    fn try_from(cards: &Cards) -> Result<Self, Self::Error> {
+        if !(3..=5).contains(&cards.len()) {
+            return Err(PKError::InvalidCardCount);
+        }
+
+        let mut suits: HashMap<Suit, usize> = HashMap::new();
+        let mut ranks: HashMap<Rank, usize> = HashMap::new();
+        for card in cards.iter() {
+            *suits.entry(card.get_suit()).or_default() += 1;
+            *ranks.entry(card.get_rank()).or_default() += 1;
+        }
+
+        let max_suit = suits.values().max().copied().unwrap_or_default();
+        let suitedness = if suits.len() == 1 {
+            Suitedness::Monotone
+        } else if max_suit >= 3 {
+            Suitedness::FlushPossible
+        } else if max_suit == 2 {
+            Suitedness::TwoTone
+        } else {
+            Suitedness::Rainbow
+        };
+
+        let mut counts: Vec<usize> = ranks.values().copied().collect();
+        counts.sort_unstable_by(|a, b| b.cmp(a));
+        let pairing = match (counts[0], counts.get(1).copied().unwrap_or_default()) {
+            (4, _) => Pairing::Quads,
+            (3, 2) => Pairing::FullHouse,
+            (3, _) => Pairing::Trips,
+            (2, 2) => Pairing::TwoPair,
+            (2, _) => Pairing::Paired,
+            _ => Pairing::Unpaired,
+        };
+
+        let mut texture = BoardTexture {
+            cards: cards.sort(),
+            suitedness,
+            pairing,
+            connectedness: Connectedness::Disconnected,
+            height: Height::Low,
+        };
+
+        texture.connectedness = match BoardTexture::max_in_window(&texture.distinct_values()) {
+            0 | 1 => Connectedness::Disconnected,
+            2 => Connectedness::Draws,
+            3 | 4 => Connectedness::StraightPossible,
+            _ => Connectedness::StraightOnBoard,
+        };
+        texture.height = match texture.high_card() {
+            Rank::ACE => Height::AceHigh,
+            Rank::KING | Rank::QUEEN => Height::High,
+            Rank::JACK | Rank::TEN | Rank::NINE => Height::Middle,
+            _ => Height::Low,
+        };
+
+        Ok(texture)
+    }
+}
+
+impl TryFrom<&Three> for BoardTexture {
+    type Error = PKError;
+
+    fn try_from(three: &Three) -> Result<Self, Self::Error> {
+        BoardTexture::try_from(&Cards::from(three.to_vec()))
+    }
+}
+
+#[cfg(test)]
+#[allow(non_snake_case)]
+mod analysis__board_texture_tests {
+    use super::*;
+    use rstest::rstest;
+    use std::str::FromStr;
+
+    fn texture(s: &str) -> BoardTexture {
+        BoardTexture::try_from(&Cards::from_str(s).unwrap()).unwrap()
+    }
+
+    #[rstest]
+    #[case("A♠ K♥ 7♦", Suitedness::Rainbow)]
+    #[case("A♠ K♠ 7♦", Suitedness::TwoTone)]
+    #[case("A♠ K♠ 7♠", Suitedness::Monotone)]
+    #[case("A♠ K♠ 7♦ 7♥", Suitedness::TwoTone)]
+    #[case("A♠ K♠ 7♠ 7♥", Suitedness::FlushPossible)]
+    #[case("A♠ K♥ 7♦ 2♣", Suitedness::Rainbow)]
+    #[case("A♠ K♠ 7♦ 7♥ 2♣", Suitedness::TwoTone)]
+    #[case("A♠ K♠ 7♦ 7♥ 2♦", Suitedness::TwoTone)]
+    #[case("A♠ K♠ 7♠ 2♠ 3♠", Suitedness::Monotone)]
+    fn suitedness(#[case] board: &str, #[case] expected: Suitedness) {
+        assert_eq!(expected, texture(board).suitedness);
+    }
+
+    #[rstest]
+    #[case("A♠ K♥ 7♦", Pairing::Unpaired)]
+    #[case("A♠ A♥ 7♦", Pairing::Paired)]
+    #[case("A♠ A♥ A♦", Pairing::Trips)]
+    #[case("A♠ A♥ 7♦ 7♣", Pairing::TwoPair)]
+    #[case("A♠ A♥ 7♦ 7♣ 7♥", Pairing::FullHouse)]
+    #[case("A♠ A♥ A♦ A♣ 7♥", Pairing::Quads)]
+    fn pairing(#[case] board: &str, #[case] expected: Pairing) {
+        assert_eq!(expected, texture(board).pairing);
+    }
+
+    #[rstest]
+    #[case("K♠ 8♥ 2♦", Connectedness::Disconnected, 0)]
+    #[case("A♠ K♥ 7♦", Connectedness::Draws, 0)]
+    #[case("J♥ T♥ 8♣", Connectedness::StraightPossible, 1)]
+    #[case("9♥ 7♥ 5♣", Connectedness::StraightPossible, 2)]
+    #[case("A♥ 2♥ 3♣", Connectedness::StraightPossible, 0)]
+    #[case("A♥ 4♥ 5♣ K♦", Connectedness::StraightPossible, 2)]
+    #[case("9♥ 8♥ 7♣ 6♦ 5♠", Connectedness::StraightOnBoard, 0)]
+    fn connectedness(#[case] board: &str, #[case] expected: Connectedness, #[case] gaps: usize) {
+        let texture = texture(board);
+
+        assert_eq!(expected, texture.connectedness, "{board}");
+        assert_eq!(gaps, texture.gaps(), "{board}");
+    }
+
+    #[test]
+    fn has_wheel_potential() {
+        assert!(texture("A♠ 4♥ 9♦").has_wheel_potential());
+        assert!(texture("5♠ 3♥ K♦").has_wheel_potential());
+        assert!(!texture("A♠ K♥ 9♦").has_wheel_potential());
+    }
+
+    #[rstest]
+    #[case("8♠ 5♥ 2♦", Height::Low)]
+    #[case("J♠ 5♥ 2♦", Height::Middle)]
+    #[case("K♠ Q♥ 2♦", Height::High)]
+    #[case("A♠ 5♥ 2♦", Height::AceHigh)]
+    fn height(#[case] board: &str, #[case] expected: Height) {
+        assert_eq!(expected, texture(board).height);
+    }
+
+    #[test]
+    fn wetness() {
+        assert!(texture("K♠ 7♥ 2♦").is_dry());
+        assert!(texture("J♥ T♥ 9♣").is_wet());
+        assert!(texture("J♥ T♥ 9♣").wetness() > texture("J♥ J♦ 9♣").wetness());
+        assert!((texture("J♠ T♠ 9♠ 8♠ 7♥").wetness() - 1.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn try_from__board() {
+        let board = Board::from_str("A♠ K♠ 7♦").unwrap();
+        let river = Board::from_str("A♠ K♠ 7♦ 7♠ 2♠").unwrap();
+
+        assert_eq!(texture("A♠ K♠ 7♦"), BoardTexture::try_from(&board).unwrap());
+        assert_eq!(
+            Suitedness::FlushPossible,
+            BoardTexture::try_from(&river).unwrap().suitedness
+        );
+        assert_eq!(
+            PKError::InvalidCardCount,
+            BoardTexture::try_from(&Board::default()).unwrap_err()
+        );
+    }
+
+    #[test]
+    fn try_from__errors() {
+        assert_eq!(
+            PKError::InvalidCardCount,
+            BoardTexture::try_from(&Cards::from_str("A♠ K♠").unwrap()).unwrap_err()
+        );
+    }
+
+    /// | Flops      | Count  |
+    /// |------------|--------|
+    /// | All        | 22,100 |
+    /// | Rainbow    | 8,788  |
+    /// | Two-tone   | 12,168 |
+    /// | Monotone   | 1,144  |
+    /// | Paired     | 3,744  |
+    /// | Trips      | 52     |
+    #[test]
+    fn flops_where() {
+        assert_eq!(22_100, BoardTexture::flops_where(|_| true).len());
+        assert_eq!(8_788, BoardTexture::flops_where(BoardTexture::is_rainbow).len());
+        assert_eq!(12_168, BoardTexture::flops_where(BoardTexture::is_two_tone).len());
+        assert_eq!(3_744 + 52, BoardTexture::flops_where(BoardTexture::is_paired).len());
+        assert_eq!(52, BoardTexture::flops_where(BoardTexture::is_trips).len());
+        assert!(BoardTexture::flops_where(|texture| texture.is_monotone() && texture.is_paired()).is_empty());
+    }
+}
@@ -1,5 +1,6 @@
 use crate::analysis::eval::Eval;
 use crate::analysis::hand_rank::HandRank;
+use crate::analysis::seven_card_evaluator::SevenCardEvaluator;
 use crate::arrays::five::Five;
 use crate::arrays::seven::Seven;
 use crate::arrays::three::Three;
@@ -116,7 +117,7 @@ impl CaseEval {
     /// Returns a `PKError` if any of the cards is invalid.
     pub fn from_holdem_at_flop(board: Three, case: Two, hands: &HoleCards) -> Result<Self, PKError> {
         if board.is_dealt() && case.is_dealt() {
-            let mut case_eval = CaseEval::default();
+            let mut case_eval = CaseEval::new(case.cards());
 
             for player in hands.iter() {
                 if !player.is_dealt() {
@@ -133,6 +134,9 @@ impl CaseEval {
         }
     }
 
+    /// Since this is called for every possible board before the flop, it goes through the
+    /// `SevenCardEvaluator` instead of the 21 permutation scan.
+    ///
     /// # Errors
     ///
     /// ¯\_ (ツ)_/¯
@@ -145,7 +149,7 @@ impl CaseEval {
                     return Err(PKError::InvalidHand);
                 }
                 let seven = Seven::from_case_at_deal(*player, case)?;
-                let eval = Eval::from(seven);
+                let eval = SevenCardEvaluator::eval(&seven);
                 case_eval.push(eval);
             }
 
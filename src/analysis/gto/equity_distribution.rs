@@ -0,0 +1,368 @@
+use crate::arrays::two::Two;
+use csv::WriterBuilder;
+use serde::Serialize;
+use std::fmt::{Display, Formatter};
+use std::io::Write;
+
+/// One point in an `EquityDistribution`: the hands, what they're worth, and how much they count.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct EquityPoint {
+    /// A single `Two` for a range's own distribution, or every player's hand for a matchup.
+    pub hands: Vec<Two>,
+    pub equity: f64,
+    pub weight: f64,
+}
+
+/// A range's equity isn't one number. Two ranges can both have 50% equity on a board, one by
+/// having a lot of hands at 50%, and the other with half its hands near 100% and half near 0%.
+/// The first one is condensed, and the second one is polarized, and they play completely
+/// differently.
+///
+/// `EquityDistribution` keeps every hand's equity, sorted from worst to best, so that we can
+/// look at the shape:
+///
+/// * `histogram()` - how much of the range falls into each equity bucket.
+/// * `curve()` - the sorted equity curve, with how much of the range is at or below each point.
+///   This is the graph that solvers show.
+/// * `percentile()` - the equity of the hand at that spot in the range.
+///
+/// Hands count by their weight, so a hand that shows up in more matchups, or at a higher
+/// frequency, counts for more. That way `mean()` comes out the same as the range's equity.
+///
+/// ```
+/// use pkcore::analysis::gto::range_equity::RangeEquity;
+/// use pkcore::analysis::gto::twos::Twos;
+/// use pkcore::cards::Cards;
+/// use std::str::FromStr;
+///
+/// let results = RangeEquity::new(vec![Twos::from_str("AA 22").unwrap(), Twos::from_str("KK").unwrap()])
+///     .with_board(Cards::from_str("9♥ 7♦ 3♣").unwrap())
+///     .calculate()
+///     .unwrap();
+///
+/// let distribution = results.distribution(0);
+///
+/// // Aces are way ahead and deuces are way behind: polarized.
+/// assert!((distribution.mean() - results.equity(0)).abs() < 1e-9);
+/// assert!(distribution.percentile(0.25) < 0.2);
+/// assert!(distribution.percentile(0.75) > 0.8);
+/// assert_eq!(0.0, distribution.histogram(5)[2]);
+/// ```
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct EquityDistribution(Vec<EquityPoint>);
+
+impl EquityDistribution {
+    /// Sorts the points from worst to best.
+    #[must_use]
+    pub fn new(mut points: Vec<EquityPoint>) -> EquityDistribution {
+        points.sort_by(|a, b| a.equity.total_cmp(&b.equity));
+        EquityDistribution(points)
+    }
+
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.0.len()
+    }
+
+    /// Worst first.
+    #[must_use]
+    pub fn points(&self) -> &Vec<EquityPoint> {
+        &self.0
+    }
+
+    #[must_use]
+    pub fn weight(&self) -> f64 {
+        self.0.iter().map(|point| point.weight).sum()
+    }
+
+    /// The weighted average equity.
+    #[must_use]
+    pub fn mean(&self) -> f64 {
+        let weight = self.weight();
+        if weight > 0.0 {
+            self.0.iter().map(|point| point.equity * point.weight).sum::<f64>() / weight
+        } else {
+            0.0
+        }
+    }
+
+    /// How spread out the equities are. Condensed ranges are close to zero, and a range that's
+    /// all nuts and air is close to 0.5.
+    #[must_use]
+    pub fn std_dev(&self) -> f64 {
+        let weight = self.weight();
+        if weight <= 0.0 {
+            return 0.0;
+        }
+        let mean = self.mean();
+        let variance = self
+            .0
+            .iter()
+            .map(|point| (point.equity - mean).powi(2) * point.weight)
+            .sum::<f64>()
+            / weight;
+        variance.sqrt()
+    }
+
+    /// The share of the range's weight in each of `buckets` equal slices of equity from 0 to 1.
+    /// The last bucket includes 100%.
+    #[must_use]
+    #[allow(
+        clippy::cast_possible_truncation,
+        clippy::cast_sign_loss,
+        clippy::cast_precision_loss
+    )]
+    pub fn histogram(&self, buckets: usize) -> Vec<f64> {
+        let mut histogram = vec![0.0; buckets];
+        let weight = self.weight();
+        if buckets == 0 || weight <= 0.0 {
+            return histogram;
+        }
+        for point in &self.0 {
+            let bucket = ((point.equity * buckets as f64) as usize).min(buckets - 1);
+            histogram[bucket] += point.weight / weight;
+        }
+        histogram
+    }
+
+    /// The sorted equity curve: for each point, worst first, how much of the range is at or
+    /// below it, and its equity.
+    #[must_use]
+    pub fn curve(&self) -> Vec<(f64, f64)> {
+        let weight = self.weight();
+        let mut cumulative = 0.0;
+        self.0
+            .iter()
+            .map(|point| {
+                cumulative += point.weight;
+                (if weight > 0.0 { cumulative / weight } else { 0.0 }, point.equity)
+            })
+            .collect()
+    }
+
+    /// The equity of the hand `p` of the way through the range, from worst to best, where `p`
+    /// is between 0 and 1. `percentile(0.5)` is the median.
+    #[must_use]
+    pub fn percentile(&self, p: f64) -> f64 {
+        self.curve()
+            .into_iter()
+            .find(|(share, _)| *share >= p.clamp(0.0, 1.0) - f64::EPSILON)
+            .or_else(|| self.curve().last().copied())
+            .map_or(0.0, |(_, equity)| equity)
+    }
+
+    /// Writes every point, worst first, with a header: `hands,equity,weight`. The hands of a
+    /// matchup are separated by ` v `.
+    ///
+    /// # Errors
+    ///
+    /// If the writer fails.
+    pub fn write_csv<W: Write>(&self, writer: W) -> Result<(), Box<dyn std::error::Error>> {
+        let mut wtr = WriterBuilder::new().has_headers(true).from_writer(writer);
+        for point in &self.0 {
+            wtr.serialize(PointRow {
+                hands: point
+                    .hands
+                    .iter()
+                    .map(ToString::to_string)
+                    .collect::<Vec<String>>()
+                    .join(" v "),
+                equity: point.equity,
+                weight: point.weight,
+            })?;
+        }
+        wtr.flush()?;
+        Ok(())
+    }
+
+    /// Writes the histogram with a header: `low,high,share`.
+    ///
+    /// # Errors
+    ///
+    /// If the writer fails.
+    #[allow(clippy::cast_precision_loss)]
+    pub fn write_histogram_csv<W: Write>(&self, buckets: usize, writer: W) -> Result<(), Box<dyn std::error::Error>> {
+        let mut wtr = WriterBuilder::new().has_headers(true).from_writer(writer);
+        for (i, share) in self.histogram(buckets).into_iter().enumerate() {
+            wtr.serialize(BucketRow {
+                low: i as f64 / buckets as f64,
+                high: (i + 1) as f64 / buckets as f64,
+                share,
+            })?;
+        }
+        wtr.flush()?;
+        Ok(())
+    }
+
+    /// # Errors
+    ///
+    /// Unable to create csv file.
+    pub fn generate_csv(&self, path: &str) -> Result<(), Box<dyn std::error::Error>> {
+        self.write_csv(std::fs::File::create(path)?)
+    }
+}
+
+/// A text histogram in ten buckets, with the summary on top:
+///
+/// ```txt
+/// mean 67.50% sd 36.31% p25 5.00% p50 85.00% p75 85.00%
+///     0-10 % █████████████                                      25.00%
+///    10-20 %                                                    0.00%
+///    ...
+///    90-100% █████████████                                      25.00%
+/// ```
+impl Display for EquityDistribution {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "mean {:.2}% sd {:.2}% p25 {:.2}% p50 {:.2}% p75 {:.2}%",
+            self.mean() * 100.0,
+            self.std_dev() * 100.0,
+            self.percentile(0.25) * 100.0,
+            self.percentile(0.5) * 100.0,
+            self.percentile(0.75) * 100.0
+        )?;
+        for (i, share) in self.histogram(10).into_iter().enumerate() {
+            write!(
+                f,
+                "\n  {:>3}-{:<3}% {:<50} {:.2}%",
+                i * 10,
+                (i + 1) * 10,
+                "█".repeat(EquityDistribution::bar(share)),
+                share * 100.0
+            )?;
+        }
+        Ok(())
+    }
+}
+
+impl EquityDistribution {
+    #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+    fn bar(share: f64) -> usize {
+        (share * 50.0).round() as usize
+    }
+}
+
+#[derive(Serialize)]
+struct PointRow {
+    hands: String,
+    equity: f64,
+    weight: f64,
+}
+
+#[derive(Serialize)]
+struct BucketRow {
+    low: f64,
+    high: f64,
+    share: f64,
+}
+
+#[cfg(test)]
+#[allow(non_snake_case)]
+mod analysis__gto__equity_distribution_tests {
+    use super::*;
+
+    fn point(two: Two, equity: f64, weight: f64) -> EquityPoint {
+        EquityPoint {
+            hands: vec![two],
+            equity,
+            weight,
+        }
+    }
+
+    fn polarized() -> EquityDistribution {
+        EquityDistribution::new(vec![
+            point(Two::HAND_AS_AH, 0.95, 1.0),
+            point(Two::HAND_2S_2H, 0.05, 1.0),
+            point(Two::HAND_KS_KH, 0.85, 2.0),
+        ])
+    }
+
+    #[test]
+    fn new__sorted() {
+        let distribution = polarized();
+
+        assert_eq!(Two::HAND_2S_2H, distribution.points()[0].hands[0]);
+        assert_eq!(Two::HAND_AS_AH, distribution.points()[2].hands[0]);
+    }
+
+    #[test]
+    fn mean() {
+        assert!((polarized().mean() - (0.95 + 0.05 + 1.7) / 4.0).abs() < 1e-9);
+        assert_eq!(0.0, EquityDistribution::default().mean());
+    }
+
+    #[test]
+    fn std_dev() {
+        let condensed =
+            EquityDistribution::new(vec![point(Two::HAND_AS_AH, 0.5, 1.0), point(Two::HAND_KS_KH, 0.5, 1.0)]);
+
+        assert!(condensed.std_dev() < f64::EPSILON);
+        assert!(polarized().std_dev() > 0.3);
+    }
+
+    #[test]
+    fn histogram() {
+        let histogram = polarized().histogram(10);
+
+        assert_eq!(10, histogram.len());
+        assert_eq!(0.25, histogram[0]);
+        assert_eq!(0.5, histogram[8]);
+        assert_eq!(0.25, histogram[9]);
+        assert_eq!(
+            1.0,
+            EquityDistribution::new(vec![point(Two::HAND_AS_AH, 1.0, 1.0)]).histogram(4)[3]
+        );
+    }
+
+    #[test]
+    fn curve() {
+        assert_eq!(vec![(0.25, 0.05), (0.75, 0.85), (1.0, 0.95)], polarized().curve());
+    }
+
+    #[test]
+    fn percentile() {
+        let distribution = polarized();
+
+        assert_eq!(0.05, distribution.percentile(0.0));
+        assert_eq!(0.05, distribution.percentile(0.25));
+        assert_eq!(0.85, distribution.percentile(0.5));
+        assert_eq!(0.95, distribution.percentile(1.0));
+        assert_eq!(0.0, EquityDistribution::default().percentile(0.5));
+    }
+
+    #[test]
+    fn write_csv() {
+        let mut csv = Vec::new();
+        polarized().write_csv(&mut csv).unwrap();
+
+        assert_eq!(
+            "hands,equity,weight\n2♠ 2♥,0.05,1.0\nK♠ K♥,0.85,2.0\nA♠ A♥,0.95,1.0\n",
+            String::from_utf8(csv).unwrap()
+        );
+    }
+
+    #[test]
+    fn write_histogram_csv() {
+        let mut csv = Vec::new();
+        polarized().write_histogram_csv(2, &mut csv).unwrap();
+
+        assert_eq!(
+            "low,high,share\n0.0,0.5,0.25\n0.5,1.0,0.75\n",
+            String::from_utf8(csv).unwrap()
+        );
+    }
+
+    #[test]
+    fn display() {
+        let display = polarized().to_string();
+
+        assert!(display.starts_with("mean 67.50% sd "));
+        assert!(display.contains("\n    0-10 % █████████████"));
+    }
+}
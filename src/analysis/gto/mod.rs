@@ -6,9 +6,13 @@ pub mod twos;
 pub mod combo_pairs;
 pub mod combo_range;
 pub mod combos;
+pub mod equity_distribution;
 pub mod odds;
+pub mod preflop_ordering;
+pub mod range_equity;
 pub mod ranger;
 pub mod vs;
+pub mod weighted_range;
 
 // region hand range array constants
 // region pocket pair range array constants
@@ -33,6 +33,42 @@ impl WinLoseDraw {
     pub fn draw_percentage(&self) -> f32 {
         Percentage::new(self.draws as usize, self.total() as usize).calculate()
     }
+
+    /// The share of the pot, with a draw counting as half. `None` if there aren't any games to
+    /// go on.
+    ///
+    /// ```
+    /// use pkcore::analysis::gto::odds::WinLoseDraw;
+    ///
+    /// let odds = WinLoseDraw { wins: 6, losses: 2, draws: 2 };
+    ///
+    /// assert_eq!(Some(0.7), odds.equity());
+    /// assert_eq!(None, WinLoseDraw::default().equity());
+    /// ```
+    #[allow(clippy::cast_precision_loss)]
+    #[must_use]
+    pub fn equity(&self) -> Option<f64> {
+        match self.total() {
+            0 => None,
+            total => Some((self.wins as f64 + self.draws as f64 / 2.0) / total as f64),
+        }
+    }
+
+    /// Every count times the weight. Used to count a matchup by how often the villain has the
+    /// hand.
+    ///
+    /// This hands back a `WeightedOdds` instead of a `WinLoseDraw` so that nothing gets rounded.
+    /// Round each matchup to whole games and a hand the villain only has one time in a thousand
+    /// rounds right down to nothing.
+    #[allow(clippy::cast_precision_loss)]
+    #[must_use]
+    pub fn weighted(&self, weight: f64) -> WeightedOdds {
+        WeightedOdds {
+            wins: self.wins as f64 * weight,
+            losses: self.losses as f64 * weight,
+            draws: self.draws as f64 * weight,
+        }
+    }
 }
 
 impl std::ops::Add for WinLoseDraw {
@@ -62,6 +98,103 @@ impl std::fmt::Display for WinLoseDraw {
     }
 }
 
+/// `WinLoseDraw` with the counts as `f64`s, for adding up matchups that each count by their
+/// weight in a range. The counts don't mean much on their own, but the percentages and the
+/// equity come out right however small the weights are.
+///
+/// ```
+/// use pkcore::analysis::gto::odds::{WeightedOdds, WinLoseDraw};
+///
+/// let odds = WinLoseDraw { wins: 6, losses: 2, draws: 2 };
+/// let weighted = WeightedOdds::default() + odds.weighted(0.001) + odds.weighted(0.001);
+///
+/// assert_eq!(odds.equity(), weighted.equity());
+/// assert_eq!(None, WeightedOdds::default().equity());
+/// ```
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, Default, PartialEq)]
+#[serde(rename_all = "PascalCase")]
+pub struct WeightedOdds {
+    pub wins: f64,
+    pub losses: f64,
+    pub draws: f64,
+}
+
+impl WeightedOdds {
+    #[must_use]
+    pub fn total(&self) -> f64 {
+        self.wins + self.losses + self.draws
+    }
+
+    #[must_use]
+    pub fn win_percentage(&self) -> f32 {
+        self.percentage(self.wins)
+    }
+
+    #[must_use]
+    pub fn loss_percentage(&self) -> f32 {
+        self.percentage(self.losses)
+    }
+
+    #[must_use]
+    pub fn draw_percentage(&self) -> f32 {
+        self.percentage(self.draws)
+    }
+
+    /// The share of the pot, with a draw counting as half. `None` if there's no weight to go on.
+    #[must_use]
+    pub fn equity(&self) -> Option<f64> {
+        let total = self.total();
+        if total > 0.0 {
+            Some((self.wins + self.draws / 2.0) / total)
+        } else {
+            None
+        }
+    }
+
+    #[allow(clippy::cast_possible_truncation)]
+    fn percentage(&self, count: f64) -> f32 {
+        let total = self.total();
+        if total > 0.0 {
+            (count / total * 100.0) as f32
+        } else {
+            0.0
+        }
+    }
+}
+
+impl std::ops::Add for WeightedOdds {
+    type Output = Self;
+
+    fn add(self, other: Self) -> Self {
+        Self {
+            wins: self.wins + other.wins,
+            losses: self.losses + other.losses,
+            draws: self.draws + other.draws,
+        }
+    }
+}
+
+impl std::fmt::Display for WeightedOdds {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{:.2}% ({}), {:.2}% ({}), {:.2}% ({})",
+            self.win_percentage(),
+            self.wins,
+            self.loss_percentage(),
+            self.losses,
+            self.draw_percentage(),
+            self.draws,
+        )
+    }
+}
+
+impl From<WinLoseDraw> for WeightedOdds {
+    fn from(odds: WinLoseDraw) -> Self {
+        odds.weighted(1.0)
+    }
+}
+
 impl From<FlopEval> for WinLoseDraw {
     fn from(fe: FlopEval) -> Self {
         let (wins, draws) = fe.results.wins_and_ties(0);
@@ -102,6 +235,53 @@ mod analysis__gto__odds_tests {
         );
     }
 
+    #[test]
+    fn weighted() {
+        let a = WinLoseDraw {
+            wins: 10,
+            losses: 5,
+            draws: 3,
+        };
+
+        assert_eq!(WeightedOdds::from(a), a.weighted(1.0));
+        assert_eq!(
+            WeightedOdds {
+                wins: 5.0,
+                losses: 2.5,
+                draws: 1.5
+            },
+            a.weighted(0.5)
+        );
+        assert_eq!(WeightedOdds::default(), a.weighted(0.0));
+    }
+
+    #[test]
+    fn weighted_odds__equity() {
+        let a = WinLoseDraw {
+            wins: 337,
+            losses: 653,
+            draws: 0,
+        };
+
+        for weight in [1.0, 0.01, 0.001] {
+            let equity = a.weighted(weight).equity().unwrap();
+            assert!((a.equity().unwrap() - equity).abs() < 1e-12);
+        }
+        assert_eq!(None, a.weighted(0.0).equity());
+    }
+
+    #[test]
+    fn weighted_odds__display() {
+        let a = WinLoseDraw {
+            wins: 1,
+            losses: 2,
+            draws: 3,
+        };
+
+        assert_eq!(a.to_string(), WeightedOdds::from(a).to_string());
+        assert_eq!("0.00% (0), 0.00% (0), 0.00% (0)", WeightedOdds::default().to_string());
+    }
+
     #[test]
     fn display() {
         let a = WinLoseDraw {
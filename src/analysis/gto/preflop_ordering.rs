@@ -0,0 +1,590 @@
+use crate::analysis::gto::combo::{Combo, Qualifier};
+use crate::analysis::gto::combos::Combos;
+use crate::analysis::gto::twos::Twos;
+use crate::analysis::hand_rank::HandRankValue;
+use crate::analysis::monte_carlo::MonteCarlo;
+use crate::analysis::seven_card_evaluator::SevenCardEvaluator;
+use crate::analysis::store::db::hup::HUPResult;
+use crate::arrays::seven::Seven;
+use crate::arrays::two::Two;
+use crate::bard::Bard;
+use crate::card::Card;
+use crate::cards::Cards;
+use crate::rank::Rank;
+use crate::util::Util;
+use crate::{PKError, Pile};
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+use rayon::prelude::*;
+use rusqlite::Connection;
+use std::fmt::{Display, Formatter};
+use std::str::FromStr;
+use strum::IntoEnumIterator;
+
+/// The 169 starting hands, best first.
+///
+/// `Combos::PERCENT_2_5` and friends are typed in by hand, and only come in five sizes. This
+/// works out the order of every starting hand, so that you can ask for any percentage you like.
+/// There's more than one way to say what the best hand is, so there's more than one way to
+/// build it:
+///
+/// * `from_hups()` averages each hand's results against every other hand out of the `HUPResult`
+///   table. That's equity against a random hand, and it's the default.
+/// * `multiway()` deals each hand against some number of random hands with `MonteCarlo`. Small
+///   pairs and suited connectors do better the more people there are in the pot.
+///
+/// `default()` is `HEADS_UP`, which was worked out with `multiway()` against one random hand, a
+/// million boards for every hand, so you don't have to.
+///
+/// ```
+/// use pkcore::analysis::gto::preflop_ordering::PreflopOrdering;
+///
+/// let ordering = PreflopOrdering::default();
+///
+/// assert_eq!("AA", ordering.hands[0].0.to_string());
+/// assert_eq!("32o", ordering.hands[168].0.to_string());
+/// assert_eq!("AKs", ordering.hands[7].0.to_string());
+///
+/// // AA, KK, QQ and JJ are 24 combos, which is 1.8%, so it takes TT to get to 2%.
+/// assert_eq!(5, ordering.top_percent(2.0).len());
+/// ```
+#[derive(Clone, Debug, PartialEq)]
+pub struct PreflopOrdering {
+    /// Each hand with its equity, best first.
+    pub hands: Vec<(Combo, f64)>,
+}
+
+impl PreflopOrdering {
+    /// Equity against one random hand, from `multiway(1, MonteCarlo::new(1_000_000).with_seed(0))`.
+    /// `T8s` and `K4o` come out close enough to π / 6 that clippy thinks somebody typed it wrong.
+    #[allow(clippy::approx_constant)]
+    pub const HEADS_UP: [(&'static str, f64); 169] = [
+        ("AA", 0.8522),
+        ("KK", 0.8237),
+        ("QQ", 0.7987),
+        ("JJ", 0.7743),
+        ("TT", 0.7504),
+        ("99", 0.7214),
+        ("88", 0.6911),
+        ("AKs", 0.6706),
+        ("AQs", 0.6628),
+        ("77", 0.6627),
+        ("AJs", 0.6538),
+        ("AKo", 0.6535),
+        ("ATs", 0.6454),
+        ("AQo", 0.6444),
+        ("AJo", 0.6347),
+        ("KQs", 0.6337),
+        ("66", 0.6331),
+        ("A9s", 0.6288),
+        ("ATo", 0.6273),
+        ("KJs", 0.6255),
+        ("A8s", 0.6191),
+        ("KTs", 0.6176),
+        ("KQo", 0.6145),
+        ("A7s", 0.6101),
+        ("A9o", 0.6067),
+        ("KJo", 0.6049),
+        ("55", 0.6030),
+        ("QJs", 0.6022),
+        ("K9s", 0.5996),
+        ("A6s", 0.5996),
+        ("A5s", 0.5995),
+        ("A8o", 0.5988),
+        ("KTo", 0.5982),
+        ("QTs", 0.5943),
+        ("A4s", 0.5899),
+        ("A7o", 0.5888),
+        ("K8s", 0.5827),
+        ("QJo", 0.5824),
+        ("A3s", 0.5823),
+        ("K9o", 0.5778),
+        ("A5o", 0.5768),
+        ("Q9s", 0.5767),
+        ("A6o", 0.5764),
+        ("K7s", 0.5754),
+        ("JTs", 0.5747),
+        ("A2s", 0.5733),
+        ("QTo", 0.5726),
+        ("44", 0.5697),
+        ("A4o", 0.5674),
+        ("K6s", 0.5660),
+        ("Q8s", 0.5610),
+        ("K8o", 0.5602),
+        ("A3o", 0.5591),
+        ("K5s", 0.5576),
+        ("J9s", 0.5559),
+        ("Q9o", 0.5533),
+        ("JTo", 0.5518),
+        ("K7o", 0.5516),
+        ("A2o", 0.5490),
+        ("K4s", 0.5484),
+        ("Q7s", 0.5428),
+        ("K6o", 0.5421),
+        ("K3s", 0.5405),
+        ("T9s", 0.5402),
+        ("J8s", 0.5398),
+        ("33", 0.5373),
+        ("Q8o", 0.5363),
+        ("Q6s", 0.5355),
+        ("K5o", 0.5327),
+        ("K2s", 0.5321),
+        ("J9o", 0.5319),
+        ("Q5s", 0.5278),
+        ("K4o", 0.5236),
+        ("T8s", 0.5234),
+        ("J7s", 0.5223),
+        ("Q4s", 0.5183),
+        ("Q7o", 0.5170),
+        ("T9o", 0.5149),
+        ("J8o", 0.5145),
+        ("K3o", 0.5145),
+        ("Q3s", 0.5107),
+        ("Q6o", 0.5095),
+        ("98s", 0.5087),
+        ("T7s", 0.5065),
+        ("J6s", 0.5061),
+        ("K2o", 0.5047),
+        ("22", 0.5038),
+        ("Q2s", 0.5013),
+        ("Q5o", 0.5010),
+        ("J5s", 0.4999),
+        ("T8o", 0.4971),
+        ("J7o", 0.4969),
+        ("Q4o", 0.4913),
+        ("J4s", 0.4911),
+        ("97s", 0.4906),
+        ("T6s", 0.4895),
+        ("J3s", 0.4834),
+        ("Q3o", 0.4824),
+        ("98o", 0.4808),
+        ("T7o", 0.4794),
+        ("87s", 0.4790),
+        ("J6o", 0.4786),
+        ("96s", 0.4752),
+        ("J2s", 0.4741),
+        ("Q2o", 0.4730),
+        ("T5s", 0.4723),
+        ("J5o", 0.4721),
+        ("T4s", 0.4664),
+        ("97o", 0.4628),
+        ("J4o", 0.4622),
+        ("86s", 0.4617),
+        ("T6o", 0.4591),
+        ("95s", 0.4572),
+        ("T3s", 0.4568),
+        ("76s", 0.4530),
+        ("J3o", 0.4530),
+        ("87o", 0.4501),
+        ("T2s", 0.4478),
+        ("85s", 0.4455),
+        ("96o", 0.4452),
+        ("J2o", 0.4436),
+        ("T5o", 0.4427),
+        ("94s", 0.4392),
+        ("75s", 0.4371),
+        ("T4o", 0.4353),
+        ("93s", 0.4333),
+        ("86o", 0.4320),
+        ("65s", 0.4313),
+        ("T3o", 0.4269),
+        ("84s", 0.4267),
+        ("95o", 0.4265),
+        ("92s", 0.4243),
+        ("76o", 0.4236),
+        ("74s", 0.4179),
+        ("T2o", 0.4169),
+        ("85o", 0.4149),
+        ("54s", 0.4147),
+        ("64s", 0.4132),
+        ("83s", 0.4088),
+        ("94o", 0.4062),
+        ("75o", 0.4056),
+        ("82s", 0.4026),
+        ("73s", 0.4005),
+        ("65o", 0.3996),
+        ("93o", 0.3990),
+        ("53s", 0.3973),
+        ("63s", 0.3955),
+        ("84o", 0.3950),
+        ("92o", 0.3918),
+        ("43s", 0.3859),
+        ("74o", 0.3855),
+        ("72s", 0.3807),
+        ("64o", 0.3798),
+        ("54o", 0.3797),
+        ("52s", 0.3787),
+        ("62s", 0.3763),
+        ("83o", 0.3751),
+        ("82o", 0.3686),
+        ("42s", 0.3679),
+        ("73o", 0.3660),
+        ("53o", 0.3623),
+        ("63o", 0.3609),
+        ("32s", 0.3595),
+        ("43o", 0.3510),
+        ("72o", 0.3458),
+        ("52o", 0.3434),
+        ("62o", 0.3413),
+        ("42o", 0.3319),
+        ("32o", 0.3233),
+    ];
+
+    /// Builds the ordering from the equity of one `Two` of each hand against every other `Two`
+    /// in the `HUPResult` table. Suits don't matter against a random hand, so one is enough.
+    /// Matchups that aren't in the table are left out.
+    ///
+    /// # Errors
+    ///
+    /// `PKError::Incomplete` if any of the 169 hands doesn't have a single matchup in the table.
+    pub fn from_hups(conn: &Connection) -> Result<PreflopOrdering, PKError> {
+        let mut hands = Vec::with_capacity(crate::DISTINCT_2_CARD_HANDS);
+        for combo in PreflopOrdering::classes() {
+            let hero = PreflopOrdering::representative(combo);
+            let mut total = 0.0;
+            let mut count = 0_usize;
+            for villain in crate::analysis::gto::twos::DISTINCT_POCKET_PAIRS.to_vec() {
+                if (Bard::from(hero) & Bard::from(villain)).as_u64() != 0 {
+                    continue;
+                }
+                if let Some(equity) = HUPResult::from_db(conn, &hero, &villain)
+                    .ok()
+                    .and_then(|hup| hup.equity_for(&hero))
+                {
+                    total += equity;
+                    count += 1;
+                }
+            }
+            if count == 0 {
+                return Err(PKError::Incomplete);
+            }
+            hands.push((combo, total / Util::as_f64(count)));
+        }
+        Ok(PreflopOrdering::sorted(hands))
+    }
+
+    /// Deals each hand against `opponents` random hands, `monte_carlo.samples` times. Each hand
+    /// gets its own RNG, seeded off of the `MonteCarlo` seed and where the hand sits in the grid
+    /// by `MonteCarlo::stream_seed()`, so the same seed always gets you the same ordering.
+    ///
+    /// # Errors
+    ///
+    /// * `PKError::NotEnoughHands` for no opponents.
+    /// * `PKError::TooManyHands` for more than nine.
+    pub fn multiway(opponents: usize, monte_carlo: MonteCarlo) -> Result<PreflopOrdering, PKError> {
+        if opponents == 0 {
+            return Err(PKError::NotEnoughHands);
+        }
+        if opponents > 9 {
+            return Err(PKError::TooManyHands);
+        }
+        let seed = monte_carlo.seed.unwrap_or_else(|| rand::rng().random());
+
+        let hands = PreflopOrdering::classes()
+            .into_par_iter()
+            .enumerate()
+            .map(|(i, combo)| {
+                let mut rng = StdRng::seed_from_u64(MonteCarlo::stream_seed(seed, i as u64));
+                let equity = PreflopOrdering::deal(&mut rng, combo, opponents, monte_carlo.samples);
+                (combo, equity)
+            })
+            .collect();
+        Ok(PreflopOrdering::sorted(hands))
+    }
+
+    /// The best hands, in order, until they cover at least `percent` of the 1,326 possible
+    /// `Twos`. Each hand counts for the number of ways it can be dealt: six for a pair, four
+    /// suited, twelve offsuit.
+    #[must_use]
+    pub fn top_percent(&self, percent: f64) -> Combos {
+        let target = Util::as_f64(crate::UNIQUE_2_CARD_HANDS) * percent.clamp(0.0, 100.0) / 100.0;
+        let mut combos = Vec::new();
+        let mut count = 0;
+        for (combo, _) in &self.hands {
+            if Util::as_f64(count) >= target {
+                break;
+            }
+            combos.push(*combo);
+            count += PreflopOrdering::combo_count(combo);
+        }
+        Combos::from(combos)
+    }
+
+    /// Where the hand sits in the ordering, from 1 for the best to 169 for the worst.
+    #[must_use]
+    pub fn rank(&self, combo: &Combo) -> Option<usize> {
+        self.hands.iter().position(|(c, _)| c == combo).map(|i| i + 1)
+    }
+
+    /// Six for a pair, four for suited, twelve for offsuit.
+    #[must_use]
+    pub fn combo_count(combo: &Combo) -> usize {
+        match combo.qualifier {
+            _ if combo.first == combo.second => 6,
+            Qualifier::SUITED => 4,
+            Qualifier::OFFSUIT => 12,
+            Qualifier::ALL => 16,
+        }
+    }
+
+    /// Every one of the 169 hands, pairs as well as suited and offsuit unpaired hands.
+    #[must_use]
+    pub fn classes() -> Vec<Combo> {
+        let ranks: Vec<Rank> = Rank::iter().filter(|rank| *rank != Rank::BLANK).collect();
+        let mut classes = Vec::with_capacity(crate::DISTINCT_2_CARD_HANDS);
+        for first in &ranks {
+            for second in ranks.iter().filter(|rank| *rank <= first) {
+                let qualifiers = if first == second {
+                    vec![Qualifier::ALL]
+                } else {
+                    vec![Qualifier::SUITED, Qualifier::OFFSUIT]
+                };
+                for qualifier in qualifiers {
+                    classes.push(Combo {
+                        first: *first,
+                        second: *second,
+                        plus: false,
+                        qualifier,
+                    });
+                }
+            }
+        }
+        classes
+    }
+
+    fn representative(combo: Combo) -> Two {
+        Twos::from(combo).to_vec().first().copied().unwrap_or_default()
+    }
+
+    fn deal(rng: &mut StdRng, combo: Combo, opponents: usize, samples: usize) -> f64 {
+        let hero = PreflopOrdering::representative(combo);
+        let deck = Cards::deck_minus(&hero.cards()).to_vec();
+        let needed = opponents * 2 + 5;
+
+        let mut cards = [Card::BLANK; 7];
+        let mut share = 0.0;
+        for _ in 0..samples {
+            let dealt: Vec<Card> = rand::seq::index::sample(rng, deck.len(), needed)
+                .iter()
+                .map(|i| deck[i])
+                .collect();
+            cards[2..].copy_from_slice(&dealt[..5]);
+
+            cards[0] = hero.first();
+            cards[1] = hero.second();
+            let mine = SevenCardEvaluator::hand_rank_value(&Seven::from(cards));
+
+            let mut best = HandRankValue::MAX;
+            let mut ties = 0;
+            for opponent in dealt[5..].chunks(2) {
+                cards[0] = opponent[0];
+                cards[1] = opponent[1];
+                let theirs = SevenCardEvaluator::hand_rank_value(&Seven::from(cards));
+                if theirs < best {
+                    best = theirs;
+                    ties = 0;
+                }
+                if theirs == best {
+                    ties += 1;
+                }
+            }
+
+            if mine < best {
+                share += 1.0;
+            } else if mine == best {
+                share += 1.0 / f64::from(ties + 1);
+            }
+        }
+
+        if samples == 0 {
+            0.0
+        } else {
+            share / Util::as_f64(samples)
+        }
+    }
+
+    fn sorted(mut hands: Vec<(Combo, f64)>) -> PreflopOrdering {
+        hands.sort_by(|a, b| b.1.total_cmp(&a.1).then(b.0.cmp(&a.0)));
+        PreflopOrdering { hands }
+    }
+}
+
+impl Default for PreflopOrdering {
+    fn default() -> Self {
+        PreflopOrdering {
+            hands: PreflopOrdering::HEADS_UP
+                .iter()
+                .filter_map(|(combo, equity)| Combo::from_str(combo).ok().map(|combo| (combo, *equity)))
+                .collect(),
+        }
+    }
+}
+
+/// One hand a line, with its place in the ordering and its equity.
+impl Display for PreflopOrdering {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        for (i, (combo, equity)) in self.hands.iter().enumerate() {
+            writeln!(f, "{:>3}. {:<3} {:.2}%", i + 1, combo.to_string(), equity * 100.0)?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+#[allow(non_snake_case)]
+mod analysis__gto__preflop_ordering_tests {
+    use super::*;
+    use crate::analysis::gto::odds::WinLoseDraw;
+    use crate::analysis::store::db::sqlite::Sqlable;
+    use crate::arrays::matchups::sorted_heads_up::SortedHeadsUp;
+    use std::collections::HashSet;
+
+    #[test]
+    fn classes() {
+        let classes = PreflopOrdering::classes();
+
+        assert_eq!(crate::DISTINCT_2_CARD_HANDS, classes.len());
+        assert_eq!(
+            crate::UNIQUE_2_CARD_HANDS,
+            classes.iter().map(PreflopOrdering::combo_count).sum::<usize>()
+        );
+        assert!(
+            classes
+                .iter()
+                .all(|combo| Twos::from(*combo).len() == PreflopOrdering::combo_count(combo))
+        );
+    }
+
+    #[test]
+    fn default() {
+        let ordering = PreflopOrdering::default();
+        let distinct: HashSet<Combo> = ordering.hands.iter().map(|(combo, _)| *combo).collect();
+
+        assert_eq!(crate::DISTINCT_2_CARD_HANDS, distinct.len());
+        assert!(ordering.hands.windows(2).all(|pair| pair[0].1 >= pair[1].1));
+        assert_eq!(Some(1), ordering.rank(&Combo::COMBO_AA));
+        assert_eq!(Some(8), ordering.rank(&Combo::COMBO_AKs));
+        assert_eq!(Some(169), ordering.rank(&Combo::COMBO_32o));
+        assert_eq!(None, ordering.rank(&Combo::COMBO_AK));
+    }
+
+    #[test]
+    fn top_percent() {
+        let ordering = PreflopOrdering::default();
+
+        assert!(ordering.top_percent(0.0).is_empty());
+        assert_eq!(crate::DISTINCT_2_CARD_HANDS, ordering.top_percent(100.0).len());
+        assert_eq!(1, ordering.top_percent(0.1).len());
+        assert_eq!(
+            Combos::from(vec![
+                Combo::COMBO_AA,
+                Combo::COMBO_KK,
+                Combo::COMBO_QQ,
+                Combo::COMBO_JJ,
+                Combo::COMBO_TT
+            ]),
+            ordering.top_percent(2.0)
+        );
+    }
+
+    #[test]
+    fn top_percent__combo_counts() {
+        let ordering = PreflopOrdering::default();
+
+        for percent in [2.5, 5.0, 10.0, 15.0, 20.0, 33.0, 50.0] {
+            let combos = ordering.top_percent(percent).to_vec();
+            let count: usize = combos.iter().map(PreflopOrdering::combo_count).sum();
+            let target = 1_326.0 * percent / 100.0;
+
+            assert_eq!(count, Twos::from(ordering.top_percent(percent)).len());
+            assert!(count as f64 >= target, "{percent}%");
+            assert!((count as f64) < target + 12.0, "{percent}%");
+        }
+    }
+
+    #[test]
+    fn multiway() {
+        let ordering = PreflopOrdering::multiway(2, MonteCarlo::new(300).with_seed(1)).unwrap();
+        let again = PreflopOrdering::multiway(2, MonteCarlo::new(300).with_seed(1)).unwrap();
+
+        assert_eq!(ordering, again);
+        assert_eq!(crate::DISTINCT_2_CARD_HANDS, ordering.hands.len());
+        assert!(ordering.rank(&Combo::COMBO_AA).unwrap() <= 3);
+        assert!(ordering.rank(&Combo::COMBO_72o).unwrap() > 120);
+    }
+
+    #[test]
+    fn multiway__errors() {
+        assert_eq!(
+            PKError::NotEnoughHands,
+            PreflopOrdering::multiway(0, MonteCarlo::default()).unwrap_err()
+        );
+        assert_eq!(
+            PKError::TooManyHands,
+            PreflopOrdering::multiway(10, MonteCarlo::default()).unwrap_err()
+        );
+    }
+
+    /// One matchup for each hand, against the `2♦ 2♣`, rigged so that its equity is the same as
+    /// in `HEADS_UP`.
+    #[test]
+    fn from_hups() {
+        let conn = Connection::open_in_memory().unwrap();
+        HUPResult::create_table(&conn).unwrap();
+        assert_eq!(PKError::Incomplete, PreflopOrdering::from_hups(&conn).unwrap_err());
+
+        for (combo, equity) in PreflopOrdering::default().hands {
+            let hero = PreflopOrdering::representative(combo);
+            let shu = SortedHeadsUp::new(hero, Two::HAND_2D_2C);
+            let wins = (equity * 10_000.0).round() as u64;
+            let odds = if shu.higher() == hero {
+                WinLoseDraw {
+                    wins,
+                    losses: 10_000 - wins,
+                    draws: 0,
+                }
+            } else {
+                WinLoseDraw {
+                    wins: 10_000 - wins,
+                    losses: wins,
+                    draws: 0,
+                }
+            };
+            HUPResult::insert(
+                &conn,
+                &HUPResult {
+                    higher: Bard::from(shu.higher()),
+                    lower: Bard::from(shu.lower()),
+                    odds,
+                },
+            )
+            .unwrap();
+        }
+
+        let ordering = PreflopOrdering::from_hups(&conn).unwrap();
+
+        assert_eq!(Some(1), ordering.rank(&Combo::COMBO_AA));
+        assert_eq!(Some(169), ordering.rank(&Combo::COMBO_32o));
+        assert!((ordering.hands[0].1 - 0.8522).abs() < 1e-9);
+    }
+
+    #[test]
+    fn display() {
+        let display = PreflopOrdering::default().to_string();
+
+        assert!(display.starts_with("  1. AA  85.22%\n  2. KK  82.37%\n"));
+        assert_eq!(169, display.lines().count());
+    }
+
+    /// Regenerates `HEADS_UP`. Takes a few minutes in release mode.
+    #[test]
+    #[ignore]
+    fn heads_up() {
+        let ordering = PreflopOrdering::multiway(1, MonteCarlo::new(1_000_000).with_seed(0)).unwrap();
+
+        for ((combo, equity), (expected, expected_equity)) in ordering.hands.iter().zip(PreflopOrdering::HEADS_UP) {
+            assert_eq!(expected, combo.to_string());
+            assert!((equity - expected_equity).abs() < 0.000_05, "{combo}");
+        }
+    }
+}
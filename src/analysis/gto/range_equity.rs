@@ -0,0 +1,671 @@
+use crate::analysis::gto::combo::Combo;
+use crate::analysis::gto::combos::Combos;
+use crate::analysis::gto::equity_distribution::{EquityDistribution, EquityPoint};
+use crate::analysis::gto::weighted_range::WeightedRange;
+use crate::analysis::hand_rank::HandRankValue;
+use crate::analysis::monte_carlo::MonteCarlo;
+use crate::analysis::seven_card_evaluator::SevenCardEvaluator;
+use crate::analysis::store::db::hup::HUPResult;
+use crate::arrays::seven::Seven;
+use crate::arrays::two::Two;
+use crate::bard::Bard;
+use crate::card::Card;
+use crate::cards::Cards;
+use crate::play::hole_cards::HoleCards;
+use crate::util::Util;
+use crate::util::wincounter::win::Win;
+use crate::{PKError, Pile};
+use rayon::prelude::*;
+use rusqlite::Connection;
+use std::collections::BTreeMap;
+use std::fmt::{Display, Formatter};
+
+/// `Versus` pits one hand against a range. `RangeEquity` pits ranges against ranges.
+///
+/// Every way of picking one `Two` out of each range that doesn't share a card with the others,
+/// the board, or the dead cards is a matchup, and every matchup counts the same. That's what
+/// weighting by combos means: `AKo` has twelve combos to `AKs`'s four, so it counts three times
+/// as much, unless the other range is holding some of its aces or kings, in which case it counts
+/// for less.
+///
+/// Ranges are `WeightedRange`s, so anything that turns into one works: `Combos`, `Twos`, or a
+/// `WeightedRange` with frequencies on it. When the hands have weights, a matchup counts for the
+/// product of them, so `AKs:0.5` against `QQ:0.5` counts for a quarter.
+///
+/// Each matchup is played out:
+///
+/// * On the flop or turn, every runout, same as `CaseEvals`.
+/// * Preflop heads up, from the `HUPResult` table if you pass in a connection to it and it has
+///   the matchup.
+/// * Preflop otherwise, by `MonteCarlo`, since 1.7 million boards a matchup is too many. The
+///   default is a few thousand boards from a fixed seed, so results are repeatable.
+///
+/// ```
+/// use pkcore::analysis::gto::range_equity::RangeEquity;
+/// use pkcore::analysis::gto::twos::Twos;
+/// use pkcore::cards::Cards;
+/// use std::str::FromStr;
+///
+/// let results = RangeEquity::new(vec![Twos::from_str("AA").unwrap(), Twos::from_str("KK").unwrap()])
+///     .with_board(Cards::from_str("9♥ 7♦ 2♣").unwrap())
+///     .calculate()
+///     .unwrap();
+///
+/// assert_eq!(36, results.combo_count());
+/// assert!(results.equity(0) > 0.9);
+/// ```
+#[derive(Clone, Debug, Default)]
+pub struct RangeEquity {
+    pub ranges: Vec<WeightedRange>,
+    pub board: Cards,
+    pub dead: Cards,
+    pub monte_carlo: MonteCarlo,
+}
+
+impl RangeEquity {
+    /// The number of random boards dealt for each preflop matchup that isn't in the database.
+    pub const PREFLOP_SAMPLES: usize = 2_000;
+
+    #[must_use]
+    pub fn new<R: Into<WeightedRange>>(ranges: Vec<R>) -> RangeEquity {
+        RangeEquity {
+            ranges: ranges.into_iter().map(Into::into).collect(),
+            board: Cards::default(),
+            dead: Cards::default(),
+            monte_carlo: MonteCarlo::new(RangeEquity::PREFLOP_SAMPLES).with_seed(0),
+        }
+    }
+
+    #[must_use]
+    pub fn from_combos(ranges: &[Combos]) -> RangeEquity {
+        RangeEquity::new(ranges.iter().map(WeightedRange::from).collect())
+    }
+
+    #[must_use]
+    pub fn with_board(self, board: Cards) -> RangeEquity {
+        RangeEquity { board, ..self }
+    }
+
+    #[must_use]
+    pub fn with_dead(self, dead: Cards) -> RangeEquity {
+        RangeEquity { dead, ..self }
+    }
+
+    #[must_use]
+    pub fn with_monte_carlo(self, monte_carlo: MonteCarlo) -> RangeEquity {
+        RangeEquity { monte_carlo, ..self }
+    }
+
+    /// Every valid matchup, one `Two` from each range in order, with none of them sharing a card
+    /// with each other, the board or the dead cards.
+    #[must_use]
+    pub fn matchups(&self) -> Vec<Vec<Two>> {
+        let ranges: Vec<Vec<Two>> = self
+            .ranges
+            .iter()
+            .map(|range| {
+                range
+                    .explode(&Cards::default())
+                    .into_iter()
+                    .map(|(two, _)| two)
+                    .collect()
+            })
+            .collect();
+        let mut used = Bard::from(self.board.clone()) | Bard::from(self.dead.clone());
+        let mut matchups = Vec::new();
+        let mut current = Vec::with_capacity(ranges.len());
+        RangeEquity::collect_matchups(&ranges, &mut used, &mut current, &mut matchups);
+        matchups
+    }
+
+    fn collect_matchups(ranges: &[Vec<Two>], used: &mut Bard, current: &mut Vec<Two>, matchups: &mut Vec<Vec<Two>>) {
+        let Some((range, rest)) = ranges.split_first() else {
+            matchups.push(current.clone());
+            return;
+        };
+        for two in range {
+            let bard = Bard::from(*two);
+            if (*used & bard).as_u64() != 0 {
+                continue;
+            }
+            *used |= bard;
+            current.push(*two);
+            RangeEquity::collect_matchups(rest, used, current, matchups);
+            current.pop();
+            *used ^= bard;
+        }
+    }
+
+    /// # Errors
+    ///
+    /// * `PKError::NotEnoughHands` if there are fewer than two ranges, or no valid matchups between them.
+    /// * `PKError::TooManyHands` for more than `Win::MAX_PLAYERS` ranges.
+    /// * `PKError::InvalidCardCount` if the board isn't empty, a flop, a turn or a river.
+    /// * `PKError::DuplicateCard` if a dead card is on the board.
+    pub fn calculate(&self) -> Result<RangeEquityResults, PKError> {
+        self.validate()?;
+        let matchups = self.matchups();
+        if matchups.is_empty() {
+            return Err(PKError::NotEnoughHands);
+        }
+
+        let matchups = matchups
+            .into_par_iter()
+            .enumerate()
+            .map(|(index, hands)| self.matchup(index, hands, None))
+            .collect::<Result<Vec<RangeMatchup>, PKError>>()?;
+
+        Ok(RangeEquityResults::new(self.ranges.len(), matchups))
+    }
+
+    /// Same as `calculate()`, except that preflop heads up matchups are looked up in the
+    /// `HUPResult` table first. Anything that isn't there gets dealt out like normal.
+    ///
+    /// # Errors
+    ///
+    /// Same as `calculate()`.
+    pub fn calculate_with_db(&self, conn: &Connection) -> Result<RangeEquityResults, PKError> {
+        self.validate()?;
+        let matchups = self.matchups();
+        if matchups.is_empty() {
+            return Err(PKError::NotEnoughHands);
+        }
+
+        let matchups = matchups
+            .into_iter()
+            .enumerate()
+            .map(|(index, hands)| self.matchup(index, hands, Some(conn)))
+            .collect::<Result<Vec<RangeMatchup>, PKError>>()?;
+
+        Ok(RangeEquityResults::new(self.ranges.len(), matchups))
+    }
+
+    fn matchup(&self, index: usize, hands: Vec<Two>, conn: Option<&Connection>) -> Result<RangeMatchup, PKError> {
+        let weight = self
+            .ranges
+            .iter()
+            .zip(hands.iter())
+            .map(|(range, two)| range.weight(two))
+            .product();
+
+        if !self.board.is_empty() {
+            let equity = self.runouts(&hands);
+            return Ok(RangeMatchup { hands, equity, weight });
+        }
+
+        if let Some(conn) = conn {
+            if hands.len() == 2 && self.dead.is_empty() {
+                if let Some(equity) = HUPResult::from_db(conn, &hands[0], &hands[1])
+                    .ok()
+                    .and_then(|hup| hup.equity_for(&hands[0]))
+                {
+                    return Ok(RangeMatchup {
+                        hands,
+                        equity: vec![equity, 1.0 - equity],
+                        weight,
+                    });
+                }
+            }
+        }
+
+        let mc = self.matchup_monte_carlo(index).holdem_with_dead(
+            &HoleCards::from(hands.clone()),
+            &self.board,
+            &self.dead,
+        )?;
+        let equity = (0..hands.len()).map(|i| mc.equity(i)).collect();
+        Ok(RangeMatchup { hands, equity, weight })
+    }
+
+    /// The `MonteCarlo` for the matchup at `index`. Each matchup gets its own seed, mixed from
+    /// the base seed and its index by `MonteCarlo::stream_seed()`, the same way `MonteCarlo`
+    /// seeds its batches. If they all shared one seed they'd all be dealt the exact same boards,
+    /// and any luck in those boards would land on the whole range at once. No seed means random.
+    #[must_use]
+    pub fn matchup_monte_carlo(&self, index: usize) -> MonteCarlo {
+        match self.monte_carlo.seed {
+            Some(seed) => self.monte_carlo.with_seed(MonteCarlo::stream_seed(seed, index as u64)),
+            None => self.monte_carlo,
+        }
+    }
+
+    /// Plays out every way the board can finish, and returns each hand's average share of the pot.
+    fn runouts(&self, hands: &[Two]) -> Vec<f64> {
+        let mut used = self.board.clone();
+        used.insert_all(&self.dead);
+        for two in hands {
+            used.insert_all(&two.cards());
+        }
+
+        let mut cards = [Card::BLANK; 7];
+        for (i, card) in self.board.iter().enumerate() {
+            cards[i + 2] = *card;
+        }
+
+        let mut shares = vec![0.0; hands.len()];
+        let mut count = 0_usize;
+        let mut values = vec![HandRankValue::MAX; hands.len()];
+        for runout in Cards::deck_minus(&used).combinations(5 - self.board.len()) {
+            for (i, card) in runout.iter().enumerate() {
+                cards[2 + self.board.len() + i] = *card;
+            }
+            for (i, two) in hands.iter().enumerate() {
+                cards[0] = two.first();
+                cards[1] = two.second();
+                values[i] = SevenCardEvaluator::hand_rank_value(&Seven::from(cards));
+            }
+            let best = values.iter().min().copied().unwrap_or_default();
+            let winners = values.iter().filter(|value| **value == best).count();
+            for (i, value) in values.iter().enumerate() {
+                if *value == best {
+                    shares[i] += 1.0 / Util::as_f64(winners);
+                }
+            }
+            count += 1;
+        }
+
+        shares.iter().map(|share| share / Util::as_f64(count)).collect()
+    }
+
+    fn validate(&self) -> Result<(), PKError> {
+        if self.ranges.len() < 2 {
+            return Err(PKError::NotEnoughHands);
+        }
+        if self.ranges.len() > Win::MAX_PLAYERS {
+            return Err(PKError::TooManyHands);
+        }
+        if !matches!(self.board.len(), 0 | 3 | 4 | 5) {
+            return Err(PKError::InvalidCardCount);
+        }
+        if self.dead.iter().any(|card| self.board.contains(card)) {
+            return Err(PKError::DuplicateCard);
+        }
+        Ok(())
+    }
+}
+
+/// One `Two` from each range, each one's share of the pot, from 0 to 1, and how much the
+/// matchup counts for, which is the product of the hands' weights in their ranges.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct RangeMatchup {
+    pub hands: Vec<Two>,
+    pub equity: Vec<f64>,
+    pub weight: f64,
+}
+
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct RangeEquityResults {
+    pub player_count: usize,
+    pub matchups: Vec<RangeMatchup>,
+}
+
+impl RangeEquityResults {
+    #[must_use]
+    pub fn new(player_count: usize, matchups: Vec<RangeMatchup>) -> RangeEquityResults {
+        RangeEquityResults { player_count, matchups }
+    }
+
+    /// The number of valid matchups, after card removal.
+    #[must_use]
+    pub fn combo_count(&self) -> usize {
+        self.matchups.len()
+    }
+
+    /// The total weight of every matchup. Without any frequencies on the ranges, this is the same
+    /// as `combo_count()`.
+    #[must_use]
+    pub fn weight(&self) -> f64 {
+        self.matchups.iter().map(|matchup| matchup.weight).sum()
+    }
+
+    /// The range's equity over every matchup, weighted.
+    #[must_use]
+    pub fn equity(&self, player_index: usize) -> f64 {
+        let total: f64 = self
+            .matchups
+            .iter()
+            .filter_map(|matchup| matchup.equity.get(player_index).map(|equity| equity * matchup.weight))
+            .sum();
+        let weight = self.weight();
+        if weight > 0.0 { total / weight } else { 0.0 }
+    }
+
+    /// Each `Two` in the range, best first, with its equity and the number of matchups it's in.
+    #[must_use]
+    pub fn two_equity(&self, player_index: usize) -> Vec<(Two, f64, usize)> {
+        self.grouped(player_index, |two| two)
+    }
+
+    /// Same as `two_equity()`, but rolled up into `Combos` like `AKs` and `AKo`.
+    #[must_use]
+    pub fn combo_equity(&self, player_index: usize) -> Vec<(Combo, f64, usize)> {
+        self.grouped(player_index, Combo::from)
+    }
+
+    /// Every `Two` in the range with its equity, weighted by how much it counts across all the
+    /// matchups, so you can see the shape of the range and not just its average.
+    #[must_use]
+    pub fn distribution(&self, player_index: usize) -> EquityDistribution {
+        let mut twos: BTreeMap<Two, (f64, f64)> = BTreeMap::new();
+        for matchup in &self.matchups {
+            if let (Some(two), Some(equity)) = (matchup.hands.get(player_index), matchup.equity.get(player_index)) {
+                let entry = twos.entry(*two).or_default();
+                entry.0 += equity * matchup.weight;
+                entry.1 += matchup.weight;
+            }
+        }
+        EquityDistribution::new(
+            twos.into_iter()
+                .map(|(two, (total, weight))| EquityPoint {
+                    hands: vec![two],
+                    equity: if weight > 0.0 { total / weight } else { 0.0 },
+                    weight,
+                })
+                .collect(),
+        )
+    }
+
+    /// The player's equity in every single matchup. For a hand against a range, this is the
+    /// hand's equity against each of the range's hands.
+    #[must_use]
+    pub fn matchup_distribution(&self, player_index: usize) -> EquityDistribution {
+        EquityDistribution::new(
+            self.matchups
+                .iter()
+                .filter_map(|matchup| {
+                    matchup.equity.get(player_index).map(|equity| EquityPoint {
+                        hands: matchup.hands.clone(),
+                        equity: *equity,
+                        weight: matchup.weight,
+                    })
+                })
+                .collect(),
+        )
+    }
+
+    fn grouped<K: Ord, F: Fn(Two) -> K>(&self, player_index: usize, key: F) -> Vec<(K, f64, usize)> {
+        let mut groups: BTreeMap<K, (f64, f64, usize)> = BTreeMap::new();
+        for matchup in &self.matchups {
+            if let (Some(two), Some(equity)) = (matchup.hands.get(player_index), matchup.equity.get(player_index)) {
+                let entry = groups.entry(key(*two)).or_default();
+                entry.0 += equity * matchup.weight;
+                entry.1 += matchup.weight;
+                entry.2 += 1;
+            }
+        }
+        groups
+            .into_iter()
+            .rev()
+            .map(|(k, (total, weight, count))| (k, if weight > 0.0 { total / weight } else { 0.0 }, count))
+            .collect()
+    }
+}
+
+impl Display for RangeEquityResults {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        writeln!(f, "{} matchups", self.combo_count())?;
+        for player_index in 0..self.player_count {
+            writeln!(
+                f,
+                "  Range #{}: {:.2}%",
+                player_index + 1,
+                self.equity(player_index) * 100.0
+            )?;
+            for (combo, equity, count) in self.combo_equity(player_index) {
+                writeln!(f, "    {combo}: {:.2}% ({count})", equity * 100.0)?;
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+#[allow(non_snake_case)]
+mod analysis__gto__range_equity_tests {
+    use super::*;
+    use crate::analysis::gto::odds::WinLoseDraw;
+    use crate::analysis::gto::twos::Twos;
+    use crate::analysis::store::db::sqlite::Sqlable;
+    use std::str::FromStr;
+
+    fn twos(s: &str) -> Twos {
+        Twos::from_str(s).unwrap()
+    }
+
+    fn cards(s: &str) -> Cards {
+        Cards::from_str(s).unwrap()
+    }
+
+    #[test]
+    fn matchups__card_removal() {
+        let rq = RangeEquity::new(vec![twos("AA KK"), twos("AKs AKo")]);
+
+        // Every pair of aces leaves eight of the sixteen ace kings, and so does every pair of kings.
+        assert_eq!(96, rq.matchups().len());
+        assert_eq!(
+            48,
+            rq.clone().with_dead(cards("A♠")).matchups().len(),
+            "AA is down to three combos, and so is every ace in AK"
+        );
+    }
+
+    #[test]
+    fn distribution() {
+        let results = RangeEquity::new(vec![twos("AA 22"), twos("KK")])
+            .with_board(cards("9♥ 7♦ 3♣"))
+            .calculate()
+            .unwrap();
+
+        let distribution = results.distribution(0);
+
+        assert_eq!(12, distribution.len());
+        assert!((distribution.mean() - results.equity(0)).abs() < 1e-9);
+        assert!((distribution.weight() - results.weight()).abs() < 1e-9);
+        assert!(distribution.points()[0].hands[0].is_pair());
+    }
+
+    #[test]
+    fn matchup_distribution__hand_vs_range() {
+        let results = RangeEquity::new(vec![
+            WeightedRange::from(Two::HAND_AS_KS),
+            WeightedRange::from(twos("QQ 76s")),
+        ])
+        .with_board(cards("Q♠ 7♠ 2♦"))
+        .calculate()
+        .unwrap();
+
+        let distribution = results.matchup_distribution(0);
+
+        // QQ less the Q♠ is three combos, and 76s less the 7♠ is three more.
+        assert_eq!(6, distribution.len());
+        assert_eq!(2, distribution.points()[0].hands.len());
+        assert!(distribution.percentile(0.0) < distribution.percentile(1.0));
+    }
+
+    #[test]
+    fn calculate__flop() {
+        let results = RangeEquity::new(vec![twos("AA KK"), twos("AKs AKo")])
+            .with_board(cards("9♥ 7♦ 2♣"))
+            .calculate()
+            .unwrap();
+
+        let combos = results.combo_equity(1);
+
+        assert_eq!(96, results.combo_count());
+        assert!((results.equity(0) + results.equity(1) - 1.0).abs() < 1e-9);
+        assert!(results.equity(0) > 0.85);
+        assert_eq!(2, combos.len());
+        assert_eq!("AKs", combos[0].0.to_string());
+        assert_eq!(24, combos[0].2);
+        assert_eq!(72, combos[1].2);
+        assert_eq!(2, results.combo_equity(0).len());
+        assert_eq!(12, results.two_equity(0).len());
+    }
+
+    #[test]
+    fn calculate__river() {
+        let results = RangeEquity::new(vec![twos("AA"), twos("KK")])
+            .with_board(cards("K♦ 7♦ 2♣ 3♥ 9♠"))
+            .calculate()
+            .unwrap();
+
+        // The K♦ takes three combos of kings with it, each of which has a set.
+        assert_eq!(18, results.combo_count());
+        assert!((results.equity(1) - 1.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn calculate__weighted() {
+        let board = cards("K♦ 7♦ 2♣ 3♥ 9♠");
+        let unweighted = RangeEquity::new(vec![twos("AA"), twos("KK QQ")])
+            .with_board(board.clone())
+            .calculate()
+            .unwrap();
+        let weighted = RangeEquity::new(vec![
+            WeightedRange::from_str("AA").unwrap(),
+            WeightedRange::from_str("KK, QQ:0.25").unwrap(),
+        ])
+        .with_board(board)
+        .calculate()
+        .unwrap();
+
+        // Sets of kings win 18 matchups, and aces win the 36 against queens. With the queens at a
+        // quarter, the aces' share drops from 36 / 54 to 9 / 27.
+        assert_eq!(54, weighted.combo_count());
+        assert!((weighted.weight() - 27.0).abs() < 1e-9);
+        assert!((unweighted.equity(0) - 36.0 / 54.0).abs() < 1e-9);
+        assert!((weighted.equity(0) - 9.0 / 27.0).abs() < 1e-9);
+        let combos = weighted.combo_equity(1);
+        assert_eq!("KK", combos[0].0.to_string());
+        assert_eq!((1.0, 18), (combos[0].1, combos[0].2));
+        assert_eq!((0.0, 36), (combos[1].1, combos[1].2));
+    }
+
+    #[test]
+    fn calculate__three_ranges() {
+        let results = RangeEquity::new(vec![twos("AA"), twos("KK"), twos("QQ")])
+            .with_board(cards("Q♦ 7♦ 2♣ 3♥"))
+            .calculate()
+            .unwrap();
+
+        assert_eq!(6 * 6 * 3, results.combo_count());
+        assert!(results.equity(2) > results.equity(0));
+        assert!(((0..3).map(|i| results.equity(i)).sum::<f64>() - 1.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn calculate__preflop_monte_carlo() {
+        let results = RangeEquity::new(vec![
+            Twos::from(vec![Two::HAND_AS_AH]),
+            Twos::from(vec![Two::HAND_KS_KH]),
+        ])
+        .calculate()
+        .unwrap();
+
+        assert_eq!(1, results.combo_count());
+        assert!((results.equity(0) - 0.82).abs() < 0.05, "{}", results.equity(0));
+    }
+
+    #[test]
+    fn matchup_monte_carlo() {
+        let equity = RangeEquity::new(vec![twos("AA"), twos("KK")]).with_monte_carlo(MonteCarlo::new(100).with_seed(7));
+
+        assert_eq!(Some(MonteCarlo::stream_seed(7, 0)), equity.matchup_monte_carlo(0).seed);
+        assert_eq!(Some(MonteCarlo::stream_seed(7, 3)), equity.matchup_monte_carlo(3).seed);
+        assert_eq!(100, equity.matchup_monte_carlo(3).samples);
+        assert_eq!(
+            None,
+            equity
+                .with_monte_carlo(MonteCarlo::new(100))
+                .matchup_monte_carlo(3)
+                .seed
+        );
+    }
+
+    /// Same seed, same answer, even with every matchup dealt its own boards.
+    #[test]
+    fn calculate__preflop_monte_carlo__repeatable() {
+        let equity = RangeEquity::new(vec![twos("AA"), twos("KK")]).with_monte_carlo(MonteCarlo::new(200).with_seed(3));
+
+        let first = equity.calculate().unwrap();
+        let second = equity.calculate().unwrap();
+
+        assert_eq!(36, first.combo_count());
+        assert_eq!(first.equity(0).to_bits(), second.equity(0).to_bits());
+    }
+
+    #[test]
+    fn calculate_with_db() {
+        let conn = Connection::open_in_memory().unwrap();
+        HUPResult::create_table(&conn).unwrap();
+        HUPResult::insert(
+            &conn,
+            &HUPResult {
+                higher: Bard::from(Two::HAND_AS_AH),
+                lower: Bard::from(Two::HAND_KS_KH),
+                odds: WinLoseDraw {
+                    wins: 75,
+                    losses: 20,
+                    draws: 10,
+                },
+            },
+        )
+        .unwrap();
+
+        let results = RangeEquity::new(vec![
+            Twos::from(vec![Two::HAND_KS_KH]),
+            Twos::from(vec![Two::HAND_AS_AH]),
+        ])
+        .calculate_with_db(&conn)
+        .unwrap();
+
+        // Kings first, so the lookup gets flipped around: (20 + 10 / 2) / 105.
+        assert!((results.equity(0) - 25.0 / 105.0).abs() < 1e-9);
+        assert!((results.equity(1) - 80.0 / 105.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn calculate__errors() {
+        assert_eq!(
+            PKError::NotEnoughHands,
+            RangeEquity::new(vec![twos("AA")]).calculate().unwrap_err()
+        );
+        assert_eq!(
+            PKError::NotEnoughHands,
+            RangeEquity::new(vec![
+                Twos::from(vec![Two::HAND_AS_AH]),
+                Twos::from(vec![Two::HAND_AS_AH])
+            ])
+            .calculate()
+            .unwrap_err()
+        );
+        assert_eq!(
+            PKError::InvalidCardCount,
+            RangeEquity::new(vec![twos("AA"), twos("KK")])
+                .with_board(cards("9♥ 7♦"))
+                .calculate()
+                .unwrap_err()
+        );
+        assert_eq!(
+            PKError::DuplicateCard,
+            RangeEquity::new(vec![twos("AA"), twos("KK")])
+                .with_board(cards("9♥ 7♦ 2♣"))
+                .with_dead(cards("9♥"))
+                .calculate()
+                .unwrap_err()
+        );
+    }
+
+    #[test]
+    fn display() {
+        let results = RangeEquity::new(vec![twos("AA"), twos("KK")])
+            .with_board(cards("K♦ 7♦ 2♣ 3♥ 9♠"))
+            .calculate()
+            .unwrap();
+
+        assert_eq!(
+            "18 matchups\n  Range #1: 0.00%\n    AA: 0.00% (18)\n  Range #2: 100.00%\n    KK: 100.00% (18)\n",
+            results.to_string()
+        );
+    }
+}
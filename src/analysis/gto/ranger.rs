@@ -1,9 +1,372 @@
-pub struct Ranger;
+use crate::PKError;
+use crate::analysis::gto::combo::Qualifier;
+use crate::analysis::gto::preflop_ordering::PreflopOrdering;
+use crate::analysis::gto::twos::{DISTINCT_POCKET_PAIRS, Twos};
+use crate::arrays::two::Two;
+use crate::card::Card;
+use crate::rank::Rank;
+use crate::suit::Suit;
+use std::collections::HashSet;
+use std::error::Error;
+use std::fmt::{Display, Formatter};
+use strum::IntoEnumIterator;
 
 /// Ranger is a utility for processing `Combo` `Ranges` from strings.
 ///
 /// Example 13% calling range: `JJ-22,AQs-ATs,KJs+,QJs,JTs,T9s,98s,87s,76s,65s,54s,AQo-ATo,KJo+`
 ///
 /// from [Poker Ranges & Range Reading](https://www.splitsuit.com/poker-ranges-reading)
-#[allow(dead_code)]
-impl Ranger {}
+///
+/// `Combos::from_str()` only knows the hands it has constants for, and can't say anything about
+/// suits. Ranger is the whole [PokerStove](https://github.com/andrewprock/pokerstove) syntax,
+/// and goes straight to `Twos`, since something like `A♠ K♠` isn't a `Combo`.
+///
+/// | Token             | Means                                                  |
+/// |-------------------|--------------------------------------------------------|
+/// | `QQ`, `AKs`, `AKo`, `AK` | The pair, suited, offsuit, or both              |
+/// | `JJ-22`, `AQs-ATs` | Everything from one to the other                      |
+/// | `77+`, `KJs+`, `A9o+` | The pair and every pair above it, or the kicker and every kicker up to the top card |
+/// | `AhKh`, `A♠K♠`    | Exactly that hand                                      |
+/// | `AsKx`, `KQss`    | Suits by position, with `x` for any suit               |
+/// | `random`, `XxXx`  | Any two cards                                          |
+/// | `15%`             | The top fifteen percent of hands, by `PreflopOrdering` |
+/// | `!AKo`            | Take these hands out of the range, wherever it appears |
+///
+/// Spaces are ignored, and ranks and suits can be upper or lower case.
+///
+/// ```
+/// use pkcore::analysis::gto::ranger::Ranger;
+///
+/// let twos = Ranger::parse("JJ-22,AQs-ATs,KJs+,QJs,JTs,T9s,98s,87s,76s,65s,54s,AQo-ATo,KJo+").unwrap();
+/// assert_eq!(172, twos.len());
+///
+/// let twos = Ranger::parse("QQ+, AsKx, !AsAh").unwrap();
+/// assert_eq!(17 + 4, twos.len());
+///
+/// let error = Ranger::parse("QQ+, AKx, 22").unwrap_err();
+/// assert_eq!("Invalid Range Index Error at token 2: `AKx`", error.to_string());
+/// ```
+pub struct Ranger;
+
+impl Ranger {
+    /// # Errors
+    ///
+    /// `RangerError` for the first token that isn't valid, with its position in the string.
+    pub fn parse(s: &str) -> Result<Twos, RangerError> {
+        let mut included: HashSet<Two> = HashSet::new();
+        let mut excluded: HashSet<Two> = HashSet::new();
+
+        let tokens = s.split(',').map(|token| token.replace(' ', ""));
+        for (index, token) in tokens.enumerate().filter(|(_, token)| !token.is_empty()) {
+            let (set, hands) = match token.strip_prefix('!') {
+                Some(hands) => (&mut excluded, hands),
+                None => (&mut included, token.as_str()),
+            };
+            let twos = Ranger::token(hands).map_err(|error| RangerError {
+                token: token.clone(),
+                index,
+                error,
+            })?;
+            set.extend(twos);
+        }
+
+        Ok(Twos::from(
+            included.difference(&excluded).copied().collect::<HashSet<Two>>(),
+        ))
+    }
+
+    fn token(token: &str) -> Result<Vec<Two>, PKError> {
+        if token.eq_ignore_ascii_case("random") || token.eq_ignore_ascii_case("xxxx") {
+            return Ok(DISTINCT_POCKET_PAIRS.clone().into_iter().collect());
+        }
+        if let Some(percent) = token.strip_suffix('%') {
+            return Ranger::percent(percent);
+        }
+        if let Some((from, to)) = token.split_once('-') {
+            return Ranger::dash(from, to);
+        }
+        if let Some(class) = token.strip_suffix('+') {
+            return Ranger::plus(class);
+        }
+
+        let chars: Vec<char> = token.chars().collect();
+        if chars.len() == 4 {
+            Ranger::explicit(&chars)
+        } else {
+            let (first, second, qualifier) = Ranger::class(token)?;
+            Ranger::twos(first, second, qualifier)
+        }
+    }
+
+    /// `JJ-22` or `AQs-ATs`. Both ends have to be the same kind of hand, and for unpaired hands
+    /// the top card has to match.
+    fn dash(from: &str, to: &str) -> Result<Vec<Two>, PKError> {
+        let (from_first, from_second, from_qualifier) = Ranger::class(from)?;
+        let (to_first, to_second, to_qualifier) = Ranger::class(to)?;
+        if from_qualifier != to_qualifier {
+            return Err(PKError::InvalidRangeIndex);
+        }
+
+        let mut twos = Vec::new();
+        if from_first == from_second && to_first == to_second {
+            for rank in Ranger::ranks_between(from_first, to_first) {
+                twos.extend(Ranger::twos(rank, rank, from_qualifier)?);
+            }
+        } else if from_first == to_first && from_first != from_second && to_first != to_second {
+            for rank in Ranger::ranks_between(from_second, to_second) {
+                twos.extend(Ranger::twos(from_first, rank, from_qualifier)?);
+            }
+        } else {
+            return Err(PKError::InvalidRangeIndex);
+        }
+        Ok(twos)
+    }
+
+    /// `77+` is sevens and every pair above it. `KJs+` is every suited king with a jack or better.
+    fn plus(class: &str) -> Result<Vec<Two>, PKError> {
+        let (first, second, qualifier) = Ranger::class(class)?;
+        let mut twos = Vec::new();
+        if first == second {
+            for rank in Ranger::ranks_between(first, Rank::ACE) {
+                twos.extend(Ranger::twos(rank, rank, qualifier)?);
+            }
+        } else {
+            for rank in Ranger::ranks_between(second, first).filter(|rank| *rank != first) {
+                twos.extend(Ranger::twos(first, rank, qualifier)?);
+            }
+        }
+        Ok(twos)
+    }
+
+    /// Four characters with suits in them: either rank suit rank suit, like `AhKh` and `AsKx`, or
+    /// rank rank suit suit, like `KQss`.
+    fn explicit(chars: &[char]) -> Result<Vec<Two>, PKError> {
+        let (ranks, suits) = if Ranger::is_suit(chars[1]) && Ranger::is_suit(chars[3]) {
+            ([chars[0], chars[2]], [chars[1], chars[3]])
+        } else if Ranger::is_suit(chars[2]) && Ranger::is_suit(chars[3]) {
+            ([chars[0], chars[1]], [chars[2], chars[3]])
+        } else {
+            return Err(PKError::InvalidRangeIndex);
+        };
+        let first = Ranger::rank(ranks[0])?;
+        let second = Ranger::rank(ranks[1])?;
+
+        let mut twos = Vec::new();
+        for first_suit in Ranger::suits(suits[0]) {
+            for second_suit in Ranger::suits(suits[1]) {
+                let a = Card::new(first, first_suit);
+                let b = Card::new(second, second_suit);
+                if a != b {
+                    twos.push(Two::new(a, b)?);
+                }
+            }
+        }
+        if twos.is_empty() {
+            return Err(PKError::DuplicateCard);
+        }
+        Ok(twos)
+    }
+
+    /// `N%`, as `PreflopOrdering::top_percent()`: the best hands against a random hand, until
+    /// they cover at least that much of the 1,326.
+    fn percent(percent: &str) -> Result<Vec<Two>, PKError> {
+        let percent: f64 = percent.parse().map_err(|_| PKError::InvalidRangeIndex)?;
+        if !(0.0..=100.0).contains(&percent) {
+            return Err(PKError::InvalidRangeIndex);
+        }
+
+        let mut twos = Vec::new();
+        for combo in PreflopOrdering::default().top_percent(percent).iter() {
+            twos.extend(Ranger::twos(combo.first, combo.second, combo.qualifier)?);
+        }
+        Ok(twos)
+    }
+
+    /// Two or three characters: `AA`, `AK`, `AKs` or `AKo`. The higher rank always comes first.
+    fn class(class: &str) -> Result<(Rank, Rank, Qualifier), PKError> {
+        let chars: Vec<char> = class.chars().collect();
+        let qualifier = match chars.get(2) {
+            None => Qualifier::ALL,
+            Some('s' | 'S') => Qualifier::SUITED,
+            Some('o' | 'O') => Qualifier::OFFSUIT,
+            Some(_) => return Err(PKError::InvalidRangeIndex),
+        };
+        if !matches!(chars.len(), 2 | 3) {
+            return Err(PKError::InvalidRangeIndex);
+        }
+
+        let a = Ranger::rank(chars[0])?;
+        let b = Ranger::rank(chars[1])?;
+        if a == b && qualifier != Qualifier::ALL {
+            return Err(PKError::InvalidRangeIndex);
+        }
+        Ok((a.max(b), a.min(b), qualifier))
+    }
+
+    fn twos(first: Rank, second: Rank, qualifier: Qualifier) -> Result<Vec<Two>, PKError> {
+        let mut twos = Vec::new();
+        for first_suit in Suit::all() {
+            for second_suit in Suit::all() {
+                let keep = match qualifier {
+                    _ if first == second => (first_suit as u8) > (second_suit as u8),
+                    Qualifier::SUITED => first_suit == second_suit,
+                    Qualifier::OFFSUIT => first_suit != second_suit,
+                    Qualifier::ALL => true,
+                };
+                if keep {
+                    twos.push(Two::new(Card::new(first, first_suit), Card::new(second, second_suit))?);
+                }
+            }
+        }
+        Ok(twos)
+    }
+
+    fn rank(c: char) -> Result<Rank, PKError> {
+        match Rank::from(c) {
+            Rank::BLANK => Err(PKError::InvalidRankIndex),
+            rank => Ok(rank),
+        }
+    }
+
+    fn ranks() -> impl Iterator<Item = Rank> {
+        Rank::iter().filter(|rank| *rank != Rank::BLANK)
+    }
+
+    fn ranks_between(a: Rank, b: Rank) -> impl Iterator<Item = Rank> {
+        Ranger::ranks().filter(move |rank| *rank >= a.min(b) && *rank <= a.max(b))
+    }
+
+    fn is_suit(c: char) -> bool {
+        c == 'x' || c == 'X' || Suit::from(c) != Suit::BLANK
+    }
+
+    fn suits(c: char) -> Vec<Suit> {
+        match c {
+            'x' | 'X' => Suit::iter().filter(|suit| *suit != Suit::BLANK).collect(),
+            _ => vec![Suit::from(c)],
+        }
+    }
+}
+
+/// What went wrong, and which token it went wrong on. `index` counts from zero, but `Display`
+/// counts from one, because that's how people count commas.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct RangerError {
+    pub token: String,
+    pub index: usize,
+    pub error: PKError,
+}
+
+impl Display for RangerError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{} at token {}: `{}`", self.error, self.index + 1, self.token)
+    }
+}
+
+impl Error for RangerError {}
+
+impl From<RangerError> for PKError {
+    fn from(error: RangerError) -> Self {
+        error.error
+    }
+}
+
+#[cfg(test)]
+#[allow(non_snake_case)]
+mod analysis__gto__ranger_tests {
+    use super::*;
+    use crate::analysis::gto::combos::Combos;
+    use rstest::rstest;
+    use std::str::FromStr;
+
+    fn parse(s: &str) -> Twos {
+        Ranger::parse(s).unwrap()
+    }
+
+    #[rstest]
+    #[case("AA", 6)]
+    #[case("AKs", 4)]
+    #[case("AKo", 12)]
+    #[case("AK", 16)]
+    #[case("KA", 16)]
+    #[case("JJ-22", 60)]
+    #[case("22-JJ", 60)]
+    #[case("AQs-ATs", 12)]
+    #[case("KQo-K9o", 48)]
+    #[case("77+", 48)]
+    #[case("KJs+", 8)]
+    #[case("A2+", 192)]
+    #[case("AhKh", 1)]
+    #[case("A♠K♠", 1)]
+    #[case("A♠ K♠", 1)]
+    #[case("AsKx", 4)]
+    #[case("KQss", 1)]
+    #[case("AAxx", 6)]
+    #[case("random", 1_326)]
+    #[case("XxXx", 1_326)]
+    #[case("0%", 0)]
+    #[case("100%", 1_326)]
+    fn parse__counts(#[case] s: &str, #[case] count: usize) {
+        assert_eq!(count, parse(s).len(), "{s}");
+    }
+
+    #[test]
+    fn parse__matches_combos() {
+        let s = "JJ-22,AQs-ATs,KJs+,QJs,JTs,T9s,98s,87s,76s,65s,54s,AQo-ATo,KJo+";
+
+        assert_eq!(Twos::from(Combos::from_str(s).unwrap()), parse(s));
+    }
+
+    #[test]
+    fn parse__explicit() {
+        assert!(parse("AhKh").contains(&Two::HAND_AH_KH));
+        assert!(parse("KQss").contains(&Two::HAND_KS_QS));
+        assert!(parse("AsKx").contains(&Two::HAND_AS_KD));
+        assert!(!parse("AsKx").contains(&Two::HAND_AH_KD));
+    }
+
+    #[test]
+    fn parse__exclusions() {
+        let twos = parse("!AKo, AK, QQ+, !A♠A♥");
+
+        assert_eq!(4 + 17, twos.len());
+        assert!(!twos.contains(&Two::HAND_AS_AH));
+        assert!(!twos.contains(&Two::HAND_AS_KH));
+        assert!(parse("random, !random").is_empty());
+    }
+
+    #[test]
+    fn parse__percent() {
+        let twos = parse("5%");
+
+        assert!(twos.len() >= 66);
+        assert!(twos.contains(&Two::HAND_AS_AH));
+        assert!(twos.contains(&Two::HAND_AS_KS));
+        assert!(!twos.contains(&Two::HAND_7S_2H));
+        assert_eq!(Twos::from(PreflopOrdering::default().top_percent(15.0)), parse("15%"));
+    }
+
+    #[rstest]
+    #[case("QQ+, AKx", 1, PKError::InvalidRangeIndex)]
+    #[case("AZs", 0, PKError::InvalidRankIndex)]
+    #[case("AA, KK, QQs", 2, PKError::InvalidRangeIndex)]
+    #[case("JJ-AKs", 0, PKError::InvalidRangeIndex)]
+    #[case("AQs-KTs", 0, PKError::InvalidRangeIndex)]
+    #[case("101%", 0, PKError::InvalidRangeIndex)]
+    #[case("AA, !", 1, PKError::InvalidRangeIndex)]
+    #[case("AsAs", 0, PKError::DuplicateCard)]
+    fn parse__errors(#[case] s: &str, #[case] index: usize, #[case] error: PKError) {
+        let actual = Ranger::parse(s).unwrap_err();
+
+        assert_eq!(index, actual.index, "{s}");
+        assert_eq!(error, actual.error, "{s}");
+    }
+
+    #[test]
+    fn error__display() {
+        assert_eq!(
+            "Invalid Rank Index Error at token 3: `!AZo`",
+            Ranger::parse("AA, KK, !AZo").unwrap_err().to_string()
+        );
+    }
+}
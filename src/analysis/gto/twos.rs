@@ -653,6 +653,9 @@ impl From<Combo> for Twos {
             Combo::COMBO_52s_PLUS => range!(52s+),
             Combo::COMBO_52o_PLUS => range!(52o+),
             Combo::COMBO_43 => range!(43),
+            Combo::COMBO_42 => range!(42),
+            Combo::COMBO_42s => range!(42s),
+            Combo::COMBO_42o => range!(42o),
             Combo::COMBO_43s => range!(43s),
             Combo::COMBO_43o => range!(43o),
             Combo::COMBO_32 => range!(32),
@@ -1,6 +1,6 @@
-use crate::analysis::gto::combos::Combos;
-use crate::analysis::gto::odds::WinLoseDraw;
+use crate::analysis::gto::odds::{WeightedOdds, WinLoseDraw};
 use crate::analysis::gto::twos::Twos;
+use crate::analysis::gto::weighted_range::WeightedRange;
 use crate::analysis::store::db::hup::HUPResult;
 use crate::arrays::two::Two;
 use crate::bard::Bard;
@@ -13,41 +13,62 @@ use rusqlite::Connection;
 use std::collections::HashMap;
 use std::fmt::Display;
 
-#[derive(Clone, Debug, Default, Eq, PartialEq)]
+/// A hand against a range. The villain can be anything that turns into a `WeightedRange`:
+/// `Combos`, `Twos`, or a `WeightedRange` itself. Plain sets come in with every hand at 1.0, so
+/// they work the way they always have.
+///
+/// When the villain's hands have frequencies, the combined odds count each matchup by its
+/// weight. A hand the villain only has half the time counts for half as much.
+#[derive(Clone, Debug, Default, PartialEq)]
 pub struct Versus {
     pub hero: Two,
-    pub villain: Combos,
+    pub villain: WeightedRange,
     pub board: Board,
 }
 
 impl Versus {
     #[must_use]
-    pub fn new(hero: Two, villain: Combos) -> Self {
+    pub fn new<R: Into<WeightedRange>>(hero: Two, villain: R) -> Self {
         Versus {
             hero,
-            villain,
+            villain: villain.into(),
             board: Board::default(),
         }
     }
 
     #[must_use]
-    pub fn new_with_board(hero: Two, villain: Combos, board: Board) -> Self {
-        Versus { hero, villain, board }
+    pub fn new_with_board<R: Into<WeightedRange>>(hero: Two, villain: R, board: Board) -> Self {
+        Versus {
+            hero,
+            villain: villain.into(),
+            board,
+        }
     }
 
+    /// Adds up the odds without any weights. See `Versus::weighted_odds_at_deal()` for a
+    /// range with frequencies.
     #[must_use]
     pub fn combined_odds_at_deal(hups: &[&HUPResult]) -> WinLoseDraw {
         hups.iter().fold(WinLoseDraw::default(), |acc, hup| acc + hup.odds)
     }
 
+    /// Adds up the odds from `Versus::hups_at_deal()`, with each villain hand counting by its
+    /// weight in the range.
+    #[must_use]
+    pub fn weighted_odds_at_deal(&self, hups: &HashMap<Two, HUPResult>) -> WeightedOdds {
+        hups.iter().fold(WeightedOdds::default(), |acc, (two, hup)| {
+            acc + hup.odds.weighted(self.villain.weight(two))
+        })
+    }
+
     #[must_use]
-    pub fn combined_odds_at_flop(&self) -> WinLoseDraw {
-        Versus::combined_odds_from_games(&self.games_at_flop())
+    pub fn combined_odds_at_flop(&self) -> WeightedOdds {
+        self.combined_odds_from_twos(&self.remaining_at_flop())
     }
 
     #[must_use]
-    pub fn combined_odds_at_turn(&self) -> WinLoseDraw {
-        Versus::combined_odds_from_games(&self.games_at_turn())
+    pub fn combined_odds_at_turn(&self) -> WeightedOdds {
+        self.combined_odds_from_twos(&self.remaining_at_turn())
     }
 
     /// Originally was:
@@ -58,12 +79,23 @@ impl Versus {
     ///     .fold(WinLoseDraw::default(), |acc, fe| acc + WinLoseDraw::from(fe))
     /// ```
     ///
-    /// This was an educational refactoring on how to avoid unwraps.
-    fn combined_odds_from_games(games: &[Game]) -> WinLoseDraw {
-        games
+    /// This was an educational refactoring on how to avoid unwraps. Then it needed to know
+    /// which villain hand each game was for, so that it could weight it, so it works from the
+    /// `Twos` now instead of the `Games`, and adds up `WeightedOdds` so that nothing gets rounded
+    /// away.
+    fn combined_odds_from_twos(&self, twos: &Twos) -> WeightedOdds {
+        twos.to_vec()
             .iter()
-            .filter_map(|game| FlopEval::try_from(game.clone()).ok())
-            .fold(WinLoseDraw::default(), |acc, fe| acc + WinLoseDraw::from(fe))
+            .map(|two| {
+                (
+                    Game::new(HoleCards::from(vec![self.hero, *two]), self.board),
+                    self.villain.weight(two),
+                )
+            })
+            .filter_map(|(game, weight)| FlopEval::try_from(game).ok().map(|fe| (fe, weight)))
+            .fold(WeightedOdds::default(), |acc, (fe, weight)| {
+                acc + WinLoseDraw::from(fe).weighted(weight)
+            })
     }
 
     #[must_use]
@@ -131,14 +163,15 @@ impl Versus {
     }
 
     #[must_use]
-    pub fn villain(&self) -> &Combos {
+    pub fn villain(&self) -> &WeightedRange {
         &self.villain
     }
 
     /// The remaining `Twos` that the villain can have, excluding the hero's cards.
     #[must_use]
     pub fn remaining(&self) -> Twos {
-        Twos::from(self.villain.clone())
+        self.villain
+            .twos()
             .filter_on_not_card(self.hero.first())
             .filter_on_not_card(self.hero.second())
     }
@@ -159,7 +192,7 @@ impl Versus {
     /// All the `Twos` including ones in the hero's hand.
     #[must_use]
     pub fn twos(&self) -> Twos {
-        Twos::from(self.villain.clone())
+        self.villain.twos()
     }
 }
 
@@ -189,6 +222,7 @@ mod arrays__combos__solver_tests {
     use super::*;
     use crate::analysis::gto::combo::Combo;
     use crate::analysis::gto::combo_pairs::ComboPairs;
+    use crate::analysis::gto::combos::Combos;
     use crate::analysis::gto::odds::WinLoseDraw;
     use std::collections::HashMap;
     use std::str::FromStr;
@@ -381,6 +415,83 @@ mod arrays__combos__solver_tests {
         assert_eq!(expected, actual);
     }
 
+    #[test]
+    fn new__weighted_range() {
+        let villain = WeightedRange::from_str("AA, KK:0.5").unwrap();
+        let solver = Versus::new(Two::HAND_KS_KH, villain.clone());
+
+        assert_eq!(&villain, solver.villain());
+        assert_eq!(7, solver.remaining().len());
+        assert_eq!(
+            6,
+            Versus::new(Two::HAND_KS_KH, Combos::from_str("AA").unwrap())
+                .villain()
+                .len()
+        );
+    }
+
+    /// Every ace pair counts in full, and the one pair of kings that's left counts for half.
+    #[test]
+    fn combined_odds_at_flop__weighted() {
+        let board = Board::from_str("Q♠ 7♦ 2♣").unwrap();
+        let hero = Two::HAND_KS_KH;
+        let aces = Versus::new_with_board(hero, Combos::from_str("AA").unwrap(), board);
+        let kings = Versus::new_with_board(hero, WeightedRange::from_str("KK:0.5").unwrap(), board);
+        let weighted = Versus::new_with_board(hero, WeightedRange::from_str("AA, KK:0.5").unwrap(), board);
+
+        assert_eq!(
+            aces.combined_odds_at_flop() + kings.combined_odds_at_flop(),
+            weighted.combined_odds_at_flop()
+        );
+    }
+
+    /// With only one hand in the range there's nothing to weigh it against, so the weight has to
+    /// cancel out, however small it is.
+    #[test]
+    fn combined_odds_at_flop__single_combo_any_weight() {
+        let board = Board::from_str("K♥ 7♥ 2♠ 3♦").unwrap();
+        let hero = Two::from_str("9♥ 8♥").unwrap();
+        let full = Versus::new_with_board(hero, Two::HAND_AS_AH, board)
+            .combined_odds_at_flop()
+            .equity()
+            .unwrap();
+
+        for weight in [0.5, 0.01, 0.001] {
+            let mut villain = WeightedRange::default();
+            villain.insert(Two::HAND_AS_AH, weight);
+
+            let equity = Versus::new_with_board(hero, villain, board)
+                .combined_odds_at_flop()
+                .equity()
+                .unwrap();
+
+            assert!((full - equity).abs() < 1e-9, "{weight}: {full} vs {equity}");
+        }
+    }
+
+    #[test]
+    fn weighted_odds_at_deal() {
+        let solver = Versus::new(Two::HAND_KS_KH, WeightedRange::from_str("AA:0.5, KK").unwrap());
+        let odds = WinLoseDraw {
+            wins: 10,
+            losses: 20,
+            draws: 2,
+        };
+        let hup = HUPResult {
+            higher: Bard::from(Two::HAND_AS_AH),
+            lower: Bard::from(Two::HAND_KS_KH),
+            odds,
+        };
+        let mut hups = HashMap::new();
+        hups.insert(Two::HAND_AS_AH, hup.clone());
+        hups.insert(Two::HAND_KD_KC, hup);
+
+        assert_eq!(
+            odds.weighted(1.0) + odds.weighted(0.5),
+            solver.weighted_odds_at_deal(&hups)
+        );
+    }
+
     #[test]
     fn hup_flip() {
         let hero = Two::HAND_KS_KH;
@@ -0,0 +1,367 @@
+use crate::analysis::gto::combo::Combo;
+use crate::analysis::gto::combos::Combos;
+use crate::analysis::gto::ranger::{Ranger, RangerError};
+use crate::analysis::gto::twos::Twos;
+use crate::arrays::two::Two;
+use crate::cards::Cards;
+use crate::{GTO, PKError, Pile};
+use std::collections::BTreeMap;
+use std::fmt::{Display, Formatter};
+use std::str::FromStr;
+
+/// A range where every hand has a frequency, the way solvers and study tools hand them out:
+///
+/// ```txt
+/// AKs:0.5, QQ+, 76s:0.25
+/// ```
+///
+/// That's half the suited ace kings, all the queens, kings and aces, and a quarter of the suited
+/// seven sixes. Anything without a weight is all the time.
+///
+/// `Combos` and `Twos` are plain sets, so they convert straight over at a weight of 1.0. Under
+/// the hood the weights are kept per `Two`, so that you can say things like "only the `A♠ K♠`"
+/// and card removal just works. When it prints, any `Combo` whose hands are all there at the
+/// same weight gets rolled back up, so `AKs:0.5` comes back out as `AKs:0.5`. Anything else
+/// prints hand by hand.
+///
+/// Weights have to be between 0 and 1. Zero means the hand isn't in the range, so it's dropped.
+///
+/// ```
+/// use pkcore::analysis::gto::weighted_range::WeightedRange;
+/// use std::str::FromStr;
+///
+/// let range = WeightedRange::from_str("AKs:0.5, QQ+, 76s:0.25").unwrap();
+///
+/// assert_eq!(26, range.len());
+/// assert_eq!(4.0 * 0.5 + 18.0 + 4.0 * 0.25, range.combo_count());
+/// assert_eq!("AA, AKs:0.5, KK, QQ, 76s:0.25", range.to_string());
+/// ```
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct WeightedRange(BTreeMap<Two, f64>);
+
+impl WeightedRange {
+    /// Sets the weight for the `Two`, replacing whatever was there. A weight of zero or less
+    /// takes it out.
+    pub fn insert(&mut self, two: Two, weight: f64) {
+        if weight <= 0.0 {
+            self.0.remove(&two);
+        } else {
+            self.0.insert(two, weight.min(1.0));
+        }
+    }
+
+    /// The weight of the `Two` in the range, zero if it isn't there.
+    #[must_use]
+    pub fn weight(&self, two: &Two) -> f64 {
+        self.0.get(two).copied().unwrap_or_default()
+    }
+
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    /// The number of distinct `Twos` in the range, whatever their weight.
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.0.len()
+    }
+
+    /// The weighted number of combos: `AKs:0.5` counts for two.
+    #[must_use]
+    pub fn combo_count(&self) -> f64 {
+        self.0.values().sum()
+    }
+
+    /// Every `Two` in the range with its weight, best first, leaving out any that use one of the
+    /// cards. That's card removal: pass in the board, the dead cards, or your own hand.
+    #[must_use]
+    pub fn explode(&self, cards: &Cards) -> Vec<(Two, f64)> {
+        self.0
+            .iter()
+            .rev()
+            .filter(|(two, _)| !two.cards().iter().any(|card| cards.contains(card)))
+            .map(|(two, weight)| (*two, *weight))
+            .collect()
+    }
+
+    /// Every weight times the factor, capped at 1.
+    #[must_use]
+    pub fn scale(&self, factor: f64) -> WeightedRange {
+        let mut range = WeightedRange::default();
+        for (two, weight) in &self.0 {
+            range.insert(*two, weight * factor);
+        }
+        range
+    }
+
+    /// Everything in either range. Where they overlap, the bigger weight wins.
+    #[must_use]
+    pub fn union(&self, other: &WeightedRange) -> WeightedRange {
+        let mut range = self.clone();
+        for (two, weight) in &other.0 {
+            range.insert(*two, weight.max(self.weight(two)));
+        }
+        range
+    }
+
+    /// Takes the other range's weights off of this one. `AKs:0.75` minus `AKs:0.5` leaves
+    /// `AKs:0.25`.
+    #[must_use]
+    pub fn subtract(&self, other: &WeightedRange) -> WeightedRange {
+        let mut range = self.clone();
+        for (two, weight) in &other.0 {
+            range.insert(*two, self.weight(two) - weight);
+        }
+        range
+    }
+
+    /// The plain `Twos`, forgetting the weights.
+    #[must_use]
+    pub fn twos(&self) -> Twos {
+        Twos::from(self.0.keys().copied().collect::<Vec<Two>>())
+    }
+
+    fn parse_token(token: &str) -> Result<(Twos, f64), PKError> {
+        let (hands, weight) = match token.split_once(':') {
+            Some((hands, weight)) => (
+                hands,
+                weight.trim().parse::<f64>().map_err(|_| PKError::InvalidRangeIndex)?,
+            ),
+            None => (token, 1.0),
+        };
+        if !(0.0..=1.0).contains(&weight) {
+            return Err(PKError::InvalidRangeIndex);
+        }
+
+        Ok((Ranger::parse(hands)?, weight))
+    }
+}
+
+impl Display for WeightedRange {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let mut by_combo: BTreeMap<Combo, Vec<(Two, f64)>> = BTreeMap::new();
+        for (two, weight) in &self.0 {
+            by_combo.entry(Combo::from(*two)).or_default().push((*two, *weight));
+        }
+
+        let mut tokens = Vec::new();
+        for (combo, twos) in by_combo.iter().rev() {
+            let weight = twos[0].1;
+            if twos.len() == Twos::from(combo).len() && twos.iter().all(|(_, w)| (w - weight).abs() < f64::EPSILON) {
+                tokens.push(WeightedRange::token(&combo.to_string(), weight));
+            } else {
+                for (two, weight) in twos.iter().rev() {
+                    tokens.push(WeightedRange::token(&two.to_string(), *weight));
+                }
+            }
+        }
+        write!(f, "{}", tokens.join(", "))
+    }
+}
+
+impl WeightedRange {
+    fn token(hands: &str, weight: f64) -> String {
+        if (weight - 1.0).abs() < f64::EPSILON {
+            hands.to_string()
+        } else {
+            format!("{hands}:{weight}")
+        }
+    }
+}
+
+/// Forgets the weights, so that a weighted range can be broken down into its `ComboPairs`.
+impl GTO for WeightedRange {
+    fn explode(&self) -> Twos {
+        self.twos()
+    }
+}
+
+impl From<Combos> for WeightedRange {
+    fn from(combos: Combos) -> Self {
+        WeightedRange::from(Twos::from(combos))
+    }
+}
+
+impl From<&Combos> for WeightedRange {
+    fn from(combos: &Combos) -> Self {
+        WeightedRange::from(Twos::from(combos))
+    }
+}
+
+impl From<Twos> for WeightedRange {
+    fn from(twos: Twos) -> Self {
+        WeightedRange(twos.into_iter().map(|two| (two, 1.0)).collect())
+    }
+}
+
+impl From<Two> for WeightedRange {
+    fn from(two: Two) -> Self {
+        WeightedRange::from(Twos::from(vec![two]))
+    }
+}
+
+/// Comma separated, with an optional `:weight` after each part. Later parts override earlier
+/// ones, so `QQ+, AA:0.5` is all the queens and kings, and half the aces.
+///
+/// A part can be anything `Ranger` can parse, like `QQ+`, `JJ-99`, `AsKx` or a single hand
+/// like `A♠ K♠`. Exclusions like `!QQ` work the same way they do in `Ranger`: they take the
+/// hands out of the whole range, wherever they show up, so `QQ+, !QQ` is just kings and aces.
+/// An exclusion can't have a weight. If you want less of a hand, give it a smaller weight.
+///
+/// Errors come back as a `RangerError`, so you know which part of the string was bad.
+impl FromStr for WeightedRange {
+    type Err = RangerError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut range = WeightedRange::default();
+        let mut excluded: Vec<Two> = Vec::new();
+
+        let tokens = s.split(',').map(str::trim).enumerate();
+        for (index, token) in tokens.filter(|(_, token)| !token.is_empty()) {
+            let error = |error| RangerError {
+                token: token.to_string(),
+                index,
+                error,
+            };
+            if let Some(hands) = token.strip_prefix('!') {
+                if hands.contains(':') {
+                    return Err(error(PKError::InvalidRangeIndex));
+                }
+                excluded.extend(Ranger::parse(hands).map_err(|e| error(e.error))?.into_iter());
+            } else {
+                let (twos, weight) = WeightedRange::parse_token(token).map_err(error)?;
+                for two in twos.into_iter() {
+                    range.insert(two, weight);
+                }
+            }
+        }
+
+        for two in excluded {
+            range.insert(two, 0.0);
+        }
+        Ok(range)
+    }
+}
+
+#[cfg(test)]
+#[allow(non_snake_case)]
+mod analysis__gto__weighted_range_tests {
+    use super::*;
+
+    fn range(s: &str) -> WeightedRange {
+        WeightedRange::from_str(s).unwrap()
+    }
+
+    #[test]
+    fn from_str() {
+        let range = range("AKs:0.5, QQ+, 76s:0.25");
+
+        assert_eq!(0.5, range.weight(&Two::HAND_AS_KS));
+        assert_eq!(1.0, range.weight(&Two::HAND_QS_QH));
+        assert_eq!(0.0, range.weight(&Two::HAND_AS_KH));
+        assert_eq!(21.0, range.combo_count());
+    }
+
+    #[test]
+    fn from_str__overrides() {
+        let range = range("QQ+, AA:0.5, K♠ K♥:0");
+
+        assert_eq!(0.5, range.weight(&Two::HAND_AS_AH));
+        assert_eq!(0.0, range.weight(&Two::HAND_KS_KH));
+        assert_eq!(17, range.len());
+    }
+
+    #[test]
+    fn from_str__exclusions() {
+        let excluded = range("QQ+, !QQ, AKs:0.5, !AhKh");
+
+        assert_eq!(0.0, excluded.weight(&Two::HAND_QS_QH));
+        assert_eq!(0.0, excluded.weight(&Two::HAND_AH_KH));
+        assert_eq!(0.5, excluded.weight(&Two::HAND_AS_KS));
+        assert_eq!(15, excluded.len());
+        assert_eq!(range("AA"), range("!KK, KK+"));
+    }
+
+    #[test]
+    fn from_str__errors() {
+        assert_eq!(
+            PKError::InvalidRangeIndex,
+            WeightedRange::from_str("AKs:1.5").unwrap_err().error
+        );
+        assert_eq!(
+            PKError::InvalidRangeIndex,
+            WeightedRange::from_str("AKs:half").unwrap_err().error
+        );
+        assert_eq!(
+            PKError::InvalidRangeIndex,
+            WeightedRange::from_str("QQ+, !AKs:0.5").unwrap_err().error
+        );
+
+        let error = WeightedRange::from_str("QQ+, 76s:0.25, AKx").unwrap_err();
+        assert_eq!(2, error.index);
+        assert_eq!("AKx", error.token);
+        assert_eq!("Invalid Range Index Error at token 3: `AKx`", error.to_string());
+        assert_eq!(1, WeightedRange::from_str("QQ+, !AKx").unwrap_err().index);
+        assert_eq!(3.0, range("AsKx:0.5, KQss").combo_count());
+    }
+
+    #[test]
+    fn display() {
+        assert_eq!("A♠ K♠:0.5, JJ, TT:0.75", range("JJ, TT:0.75, A♠ K♠:0.5").to_string());
+        assert_eq!("AKs, AKo:0.25", range("AK, AKo:0.25").to_string());
+        assert_eq!("", WeightedRange::default().to_string());
+    }
+
+    #[test]
+    fn display__round_trip() {
+        let original = range("AKs:0.5, QQ+, 76s:0.25, J♠ T♠:0.1");
+
+        assert_eq!(original, range(&original.to_string()));
+    }
+
+    #[test]
+    fn from__combos() {
+        let range = WeightedRange::from(Combos::from_str("QQ+, AK").unwrap());
+
+        assert_eq!(34, range.len());
+        assert_eq!(34.0, range.combo_count());
+    }
+
+    #[test]
+    fn explode() {
+        let exploded = range("AA:0.5, KK").explode(&Cards::from_str("A♠ K♦").unwrap());
+
+        assert_eq!(6, exploded.len());
+        assert_eq!((Two::HAND_AH_AD, 0.5), exploded[0]);
+        assert!(
+            exploded
+                .iter()
+                .all(|(two, _)| !two.contains_card(crate::card::Card::ACE_SPADES))
+        );
+    }
+
+    #[test]
+    fn scale() {
+        let scaled = range("AKs:0.5, QQ").scale(0.5);
+
+        assert_eq!(0.25, scaled.weight(&Two::HAND_AS_KS));
+        assert_eq!(0.5, scaled.weight(&Two::HAND_QS_QH));
+        assert_eq!(1.0, range("AKs:0.5").scale(4.0).weight(&Two::HAND_AS_KS));
+        assert!(range("AKs:0.5").scale(0.0).is_empty());
+    }
+
+    #[test]
+    fn union() {
+        let union = range("AKs:0.5, QQ:0.25").union(&range("AKs:0.25, JJ"));
+
+        assert_eq!("AKs:0.5, QQ:0.25, JJ", union.to_string());
+    }
+
+    #[test]
+    fn subtract() {
+        let subtracted = range("AKs:0.75, QQ+").subtract(&range("AKs:0.5, AA"));
+
+        assert_eq!("AKs:0.25, KK, QQ", subtracted.to_string());
+    }
+}
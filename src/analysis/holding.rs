@@ -0,0 +1,705 @@
+use crate::analysis::eval::Eval;
+use crate::analysis::name::HandRankName;
+use crate::arrays::HandRanker;
+use crate::arrays::five::Five;
+use crate::arrays::seven::Seven;
+use crate::arrays::six::Six;
+use crate::arrays::two::Two;
+use crate::card::Card;
+use crate::cards::Cards;
+use crate::rank::Rank;
+use crate::suit::Suit;
+use crate::{PKError, Pile};
+use std::collections::HashMap;
+use std::fmt::{Display, Formatter};
+use strum::IntoEnumIterator;
+
+/// How good the kicker is that goes with top pair. `Top` is the best kicker that's left, after
+/// the board and the pair, so with the ace on the board a king is a top kicker. `Good` is one of
+/// the next two below it, and anything else is `Weak`.
+#[derive(Clone, Copy, Debug, Eq, Hash, Ord, PartialEq, PartialOrd)]
+pub enum Kicker {
+    Weak,
+    Good,
+    Top,
+}
+
+/// What a holding has made, in the way players talk about it. Worst first, so that they sort.
+#[derive(Clone, Copy, Debug, Eq, Hash, Ord, PartialEq, PartialOrd)]
+pub enum MadeHand {
+    /// None of hero's cards matter. On the river that means chopping with the board at best.
+    BoardPlays,
+    /// Hero's cards only play as kickers. That's the same whether the board has nothing, a pair,
+    /// or quads: a category only counts as hero's if a hole card is part of what makes it.
+    HighCard,
+    /// A pocket pair under every card on the board.
+    Underpair,
+    /// Third pair, bottom pair, or a pocket pair under the second card on the board.
+    WeakPair,
+    /// A pocket pair between the top two cards on the board.
+    PocketPairBelowTop,
+    SecondPair,
+    TopPair(Kicker),
+    /// A pocket pair over every card on the board.
+    Overpair,
+    /// One hole card pairs the board, and the board has a pair of its own, or a pocket pair
+    /// plus a pair on the board.
+    TwoPairOneCard,
+    /// Each hole card pairs a different card on the board.
+    TwoPairBothCards,
+    /// One hole card with a pair on the board.
+    Trips,
+    /// A pocket pair with one on the board.
+    Set,
+    Straight,
+    NutStraight,
+    Flush,
+    NutFlush,
+    FullHouse,
+    Quads,
+    StraightFlush,
+}
+
+/// What a holding could turn into. Only for the flop and the turn; there's nothing left to draw
+/// to on the river.
+#[derive(Clone, Copy, Debug, Eq, Hash, Ord, PartialEq, PartialOrd)]
+pub enum Draw {
+    /// Two hole cards higher than anything on the board.
+    Overcards,
+    /// One hole card higher than anything on the board.
+    Overcard,
+    /// Three to a flush on the flop, with at least one of them in the hole.
+    BackdoorFlushDraw,
+    /// Three cards inside five ranks on the flop, with a hole card among them, that two more
+    /// cards could turn into a straight.
+    BackdoorStraightDraw,
+    /// One rank makes the straight.
+    Gutshot,
+    /// Two ranks make the straight. Double gutters count, since they have the same eight outs.
+    OpenEnder,
+    FlushDraw,
+    /// A flush draw to the best flush there can be.
+    NutFlushDraw,
+    /// A flush draw and a straight draw.
+    ComboDraw,
+}
+
+/// `HandRankClass` says `AcesOverKings`. `Holding` says what a `Two` actually has on a
+/// particular board: top pair with a weak kicker, a set, two pair using both cards, a nut flush
+/// draw. These are the categories we use to break down ranges and label hand histories.
+///
+/// The board can be a flop, turn or river, and anything that's a `Pile` works, so `Three`,
+/// `Four`, `Five`, `Cards` and `Board` all do.
+///
+/// ```
+/// use pkcore::analysis::holding::{Draw, Holding, Kicker, MadeHand};
+/// use pkcore::arrays::three::Three;
+/// use pkcore::arrays::two::Two;
+/// use std::str::FromStr;
+///
+/// let holding = Holding::new(&Two::HAND_AS_KS, &Three::from_str("K♥ 7♠ 2♠").unwrap()).unwrap();
+///
+/// assert_eq!(MadeHand::TopPair(Kicker::Top), holding.made);
+/// assert_eq!(vec![Draw::NutFlushDraw], holding.draws);
+/// assert_eq!("top pair, top kicker + nut flush draw", holding.to_string());
+/// ```
+#[derive(Clone, Debug, PartialEq)]
+pub struct Holding {
+    pub made: MadeHand,
+    /// Best first.
+    pub draws: Vec<Draw>,
+    pub eval: Eval,
+}
+
+impl Holding {
+    /// # Errors
+    ///
+    /// * `PKError::InvalidCardCount` unless the board is three, four or five cards.
+    /// * `PKError::BlankCard` if the `Two` isn't dealt.
+    /// * `PKError::DuplicateCard` if the `Two` shares a card with the board.
+    pub fn new<P: Pile>(two: &Two, board: &P) -> Result<Holding, PKError> {
+        let board = board.cards();
+        Holding::validate(two, &board)?;
+        let eval = Holding::eval(two, &board)?;
+
+        let spot = Spot::new(*two, &board);
+        let made = spot.made(&eval);
+        let draws = if board.len() == 5 { Vec::new() } else { spot.draws(made) };
+
+        Ok(Holding { made, draws, eval })
+    }
+
+    /// The checks that everything that looks at a `Two` on a flop, turn or river needs to make
+    /// before it gets going. `Blockers`, `NutRank`, and `HandPotential` all lean on this too.
+    ///
+    /// # Errors
+    ///
+    /// * `PKError::InvalidCardCount` unless the board is three, four or five cards.
+    /// * `PKError::BlankCard` if the `Two` isn't dealt.
+    /// * `PKError::DuplicateCard` if the `Two` shares a card with the board.
+    pub fn validate(two: &Two, board: &Cards) -> Result<(), PKError> {
+        if !(3..=5).contains(&board.len()) {
+            return Err(PKError::InvalidCardCount);
+        }
+        if !two.is_dealt() {
+            return Err(PKError::BlankCard);
+        }
+        if two.cards().iter().any(|card| board.contains(card)) {
+            return Err(PKError::DuplicateCard);
+        }
+        Ok(())
+    }
+
+    /// The `Eval` for the `Two` and the board, whichever street the board is on.
+    ///
+    /// # Errors
+    ///
+    /// `PKError::InvalidCardCount` if the cards don't come out to five, six or seven.
+    pub fn eval(two: &Two, board: &Cards) -> Result<Eval, PKError> {
+        let mut all = board.clone();
+        all.insert_all(&two.cards());
+        Ok(match all.len() {
+            5 => Five::try_from(all)?.eval(),
+            6 => Six::try_from(all)?.eval(),
+            7 => Seven::try_from(all)?.eval(),
+            _ => return Err(PKError::InvalidCardCount),
+        })
+    }
+
+    #[must_use]
+    pub fn has_draw(&self, draw: Draw) -> bool {
+        self.draws.contains(&draw)
+    }
+
+    /// Any flush draw, nut or not.
+    #[must_use]
+    pub fn has_flush_draw(&self) -> bool {
+        self.has_draw(Draw::FlushDraw) || self.has_draw(Draw::NutFlushDraw)
+    }
+
+    /// An open ender or a gutshot.
+    #[must_use]
+    pub fn has_straight_draw(&self) -> bool {
+        self.has_draw(Draw::OpenEnder) || self.has_draw(Draw::Gutshot)
+    }
+}
+
+impl Display for Holding {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.made)?;
+        for draw in &self.draws {
+            write!(f, " + {draw}")?;
+        }
+        Ok(())
+    }
+}
+
+impl Display for Kicker {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Kicker::Top => write!(f, "top kicker"),
+            Kicker::Good => write!(f, "good kicker"),
+            Kicker::Weak => write!(f, "weak kicker"),
+        }
+    }
+}
+
+impl Display for MadeHand {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            MadeHand::BoardPlays => write!(f, "board plays"),
+            MadeHand::HighCard => write!(f, "high card"),
+            MadeHand::Underpair => write!(f, "underpair"),
+            MadeHand::WeakPair => write!(f, "weak pair"),
+            MadeHand::PocketPairBelowTop => write!(f, "pocket pair below top pair"),
+            MadeHand::SecondPair => write!(f, "second pair"),
+            MadeHand::TopPair(kicker) => write!(f, "top pair, {kicker}"),
+            MadeHand::Overpair => write!(f, "overpair"),
+            MadeHand::TwoPairOneCard => write!(f, "two pair using one card"),
+            MadeHand::TwoPairBothCards => write!(f, "two pair using both cards"),
+            MadeHand::Trips => write!(f, "trips"),
+            MadeHand::Set => write!(f, "set"),
+            MadeHand::Straight => write!(f, "straight"),
+            MadeHand::NutStraight => write!(f, "nut straight"),
+            MadeHand::Flush => write!(f, "flush"),
+            MadeHand::NutFlush => write!(f, "nut flush"),
+            MadeHand::FullHouse => write!(f, "full house"),
+            MadeHand::Quads => write!(f, "quads"),
+            MadeHand::StraightFlush => write!(f, "straight flush"),
+        }
+    }
+}
+
+impl Display for Draw {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Draw::Overcards => write!(f, "overcards"),
+            Draw::Overcard => write!(f, "overcard"),
+            Draw::BackdoorFlushDraw => write!(f, "backdoor flush draw"),
+            Draw::BackdoorStraightDraw => write!(f, "backdoor straight draw"),
+            Draw::Gutshot => write!(f, "gutshot"),
+            Draw::OpenEnder => write!(f, "open ended straight draw"),
+            Draw::FlushDraw => write!(f, "flush draw"),
+            Draw::NutFlushDraw => write!(f, "nut flush draw"),
+            Draw::ComboDraw => write!(f, "combo draw"),
+        }
+    }
+}
+
+/// The working parts: hero's cards, the board's, and the counts that the categories are built
+/// out of. Rank values go from 2 to 14, and aces count as 1 as well for straights.
+struct Spot {
+    hole: [Card; 2],
+    board: Vec<Card>,
+    board_ranks: HashMap<u8, usize>,
+}
+
+impl Spot {
+    fn new(two: Two, board: &Cards) -> Spot {
+        let board: Vec<Card> = board.iter().copied().collect();
+        let mut board_ranks = HashMap::new();
+        for card in &board {
+            *board_ranks.entry(Spot::value(card.get_rank())).or_default() += 1;
+        }
+        Spot {
+            hole: [two.first(), two.second()],
+            board,
+            board_ranks,
+        }
+    }
+
+    fn value(rank: Rank) -> u8 {
+        rank as u8
+    }
+
+    fn hole_values(&self) -> [u8; 2] {
+        [
+            Spot::value(self.hole[0].get_rank()),
+            Spot::value(self.hole[1].get_rank()),
+        ]
+    }
+
+    fn is_pocket_pair(&self) -> bool {
+        self.hole[0].get_rank() == self.hole[1].get_rank()
+    }
+
+    /// The board's ranks without repeats, highest first.
+    fn board_values(&self) -> Vec<u8> {
+        let mut values: Vec<u8> = self.board_ranks.keys().copied().collect();
+        values.sort_unstable_by(|a, b| b.cmp(a));
+        values
+    }
+
+    fn all_cards(&self) -> Vec<Card> {
+        let mut cards = self.board.clone();
+        cards.extend(self.hole);
+        cards
+    }
+
+    fn made(&self, eval: &Eval) -> MadeHand {
+        match eval.hand_rank.name {
+            HandRankName::StraightFlush => self.unless_board_plays(eval, MadeHand::StraightFlush),
+            HandRankName::FourOfAKind => self.unless_only_kickers(eval, MadeHand::Quads),
+            HandRankName::FullHouse => self.unless_only_kickers(eval, MadeHand::FullHouse),
+            HandRankName::Flush => self.flush(eval),
+            HandRankName::Straight => self.straight(eval),
+            HandRankName::ThreeOfAKind => self.three_of_a_kind(eval),
+            HandRankName::TwoPair => self.two_pair(eval),
+            HandRankName::Pair => self.pair(eval),
+            _ => self.unless_board_plays(eval, MadeHand::HighCard),
+        }
+    }
+
+    /// If neither hole card is in the best five, it's all the board.
+    fn unless_board_plays(&self, eval: &Eval, made: MadeHand) -> MadeHand {
+        if self.hole_values().iter().any(|value| self.plays(eval, *value)) {
+            made
+        } else {
+            MadeHand::BoardPlays
+        }
+    }
+
+    /// For the hands made out of groups of cards. If no hole card is in one of the groups, the
+    /// board made the hand, and hero's cards are kickers at best.
+    fn unless_only_kickers(&self, eval: &Eval, made: MadeHand) -> MadeHand {
+        let grouped = Spot::grouped(eval);
+        if self
+            .hole_values()
+            .iter()
+            .any(|value| grouped.contains(value) && self.plays(eval, *value))
+        {
+            made
+        } else {
+            self.unless_board_plays(eval, MadeHand::HighCard)
+        }
+    }
+
+    /// `true` if the best five has more cards of the rank than the board does, so one of them
+    /// has to be hero's. Going by rank instead of by card means that when hero and the board
+    /// both have a nine, it's the board's nine that plays.
+    fn plays(&self, eval: &Eval, value: u8) -> bool {
+        let in_hand = eval
+            .hand
+            .iter()
+            .filter(|card| Spot::value(card.get_rank()) == value)
+            .count();
+        in_hand > self.board_ranks.get(&value).copied().unwrap_or_default()
+    }
+
+    /// The ranks that show up more than once in the best five, most cards first.
+    fn grouped(eval: &Eval) -> Vec<u8> {
+        let mut counts: HashMap<u8, usize> = HashMap::new();
+        for card in eval.hand.iter() {
+            *counts.entry(Spot::value(card.get_rank())).or_default() += 1;
+        }
+        let mut grouped: Vec<(u8, usize)> = counts.into_iter().filter(|(_, count)| *count > 1).collect();
+        grouped.sort_unstable_by(|a, b| b.1.cmp(&a.1).then(b.0.cmp(&a.0)));
+        grouped.into_iter().map(|(value, _)| value).collect()
+    }
+
+    fn flush(&self, eval: &Eval) -> MadeHand {
+        let suit = eval.hand.first().get_suit();
+        let best_hole = self
+            .hole
+            .iter()
+            .filter(|card| card.get_suit() == suit && eval.hand.contains(card))
+            .map(Card::get_rank)
+            .max();
+        match best_hole {
+            None => MadeHand::BoardPlays,
+            Some(rank) if Some(rank) == self.top_missing(suit) => MadeHand::NutFlush,
+            Some(_) => MadeHand::Flush,
+        }
+    }
+
+    /// The highest card of the suit that isn't on the board.
+    fn top_missing(&self, suit: Suit) -> Option<Rank> {
+        Rank::iter()
+            .filter(|rank| *rank != Rank::BLANK && !self.board.contains(&Card::new(*rank, suit)))
+            .max()
+    }
+
+    fn straight(&self, eval: &Eval) -> MadeHand {
+        let made = self.unless_board_plays(eval, MadeHand::Straight);
+        if made == MadeHand::BoardPlays {
+            return made;
+        }
+        let high = Spot::straight_high(Spot::mask(eval.hand.iter().copied()));
+        if high == self.nut_straight_high() {
+            MadeHand::NutStraight
+        } else {
+            MadeHand::Straight
+        }
+    }
+
+    /// The top of the best straight that any two cards could make on this board.
+    fn nut_straight_high(&self) -> Option<u8> {
+        let board = Spot::mask(self.board.iter().copied());
+        (5..=14)
+            .rev()
+            .find(|high| (Spot::window(*high) & board).count_ones() >= 3)
+    }
+
+    fn three_of_a_kind(&self, eval: &Eval) -> MadeHand {
+        let trips = Spot::grouped(eval).first().copied().unwrap_or_default();
+        let [a, b] = self.hole_values();
+        if self.is_pocket_pair() && a == trips {
+            MadeHand::Set
+        } else if a == trips || b == trips {
+            MadeHand::Trips
+        } else {
+            self.unless_board_plays(eval, MadeHand::HighCard)
+        }
+    }
+
+    fn two_pair(&self, eval: &Eval) -> MadeHand {
+        let pairs = Spot::grouped(eval);
+        let [a, b] = self.hole_values();
+        let pairing = [a, b]
+            .iter()
+            .filter(|value| pairs.contains(value) && self.board_ranks.contains_key(value))
+            .count();
+
+        if !self.is_pocket_pair() && pairing == 2 {
+            MadeHand::TwoPairBothCards
+        } else if pairing == 1 || (self.is_pocket_pair() && pairs.contains(&a)) {
+            MadeHand::TwoPairOneCard
+        } else {
+            self.unless_board_plays(eval, MadeHand::HighCard)
+        }
+    }
+
+    fn pair(&self, eval: &Eval) -> MadeHand {
+        let pair = Spot::grouped(eval).first().copied().unwrap_or_default();
+        let [a, b] = self.hole_values();
+        let board = self.board_values();
+
+        if self.is_pocket_pair() && a == pair {
+            return if pair > board[0] {
+                MadeHand::Overpair
+            } else if pair > board[1] {
+                MadeHand::PocketPairBelowTop
+            } else if pair < board[board.len() - 1] {
+                MadeHand::Underpair
+            } else {
+                MadeHand::WeakPair
+            };
+        }
+
+        let kicker = if a == pair {
+            b
+        } else if b == pair {
+            a
+        } else {
+            return self.unless_board_plays(eval, MadeHand::HighCard);
+        };
+
+        match board.iter().position(|value| *value == pair) {
+            Some(0) => MadeHand::TopPair(self.kicker(pair, kicker)),
+            Some(1) => MadeHand::SecondPair,
+            _ => MadeHand::WeakPair,
+        }
+    }
+
+    fn kicker(&self, pair: u8, kicker: u8) -> Kicker {
+        let better = (kicker + 1..=14)
+            .filter(|value| *value != pair && !self.board_ranks.contains_key(value))
+            .count();
+        match better {
+            0 => Kicker::Top,
+            1 | 2 => Kicker::Good,
+            _ => Kicker::Weak,
+        }
+    }
+
+    fn draws(&self, made: MadeHand) -> Vec<Draw> {
+        let mut draws = Vec::new();
+
+        let flush_draw = self.flush_draw(made);
+        let straight_draw = self.straight_draw(made);
+        if flush_draw.is_some() && straight_draw.is_some() {
+            draws.push(Draw::ComboDraw);
+        }
+        draws.extend(flush_draw);
+        draws.extend(straight_draw);
+
+        if self.board.len() == 3 {
+            if flush_draw.is_none() && made < MadeHand::Flush && self.suited_with_hole(3).is_some() {
+                draws.push(Draw::BackdoorFlushDraw);
+            }
+            if straight_draw.is_none() && made < MadeHand::Straight && self.backdoor_straight() {
+                draws.push(Draw::BackdoorStraightDraw);
+            }
+        }
+
+        if matches!(made, MadeHand::HighCard | MadeHand::BoardPlays) {
+            let top = self.board_values()[0];
+            match self.hole_values().iter().filter(|value| **value > top).count() {
+                2 => draws.push(Draw::Overcards),
+                1 => draws.push(Draw::Overcard),
+                _ => {}
+            }
+        }
+
+        draws
+    }
+
+    /// A suit with exactly `count` cards between the board and the hole, at least one of them
+    /// in the hole.
+    fn suited_with_hole(&self, count: usize) -> Option<Suit> {
+        let mut suits: HashMap<Suit, usize> = HashMap::new();
+        for card in self.all_cards() {
+            *suits.entry(card.get_suit()).or_default() += 1;
+        }
+        suits
+            .into_iter()
+            .find(|(suit, n)| *n == count && self.hole.iter().any(|card| card.get_suit() == *suit))
+            .map(|(suit, _)| suit)
+    }
+
+    fn flush_draw(&self, made: MadeHand) -> Option<Draw> {
+        if made >= MadeHand::Flush {
+            return None;
+        }
+        let suit = self.suited_with_hole(4)?;
+        let best = self
+            .hole
+            .iter()
+            .filter(|card| card.get_suit() == suit)
+            .map(Card::get_rank)
+            .max();
+        if best == self.top_missing(suit) {
+            Some(Draw::NutFlushDraw)
+        } else {
+            Some(Draw::FlushDraw)
+        }
+    }
+
+    /// How many ranks would give hero a straight that the board alone wouldn't.
+    fn straight_draw(&self, made: MadeHand) -> Option<Draw> {
+        if made >= MadeHand::Straight {
+            return None;
+        }
+        let all = Spot::mask(self.all_cards().into_iter());
+        let board = Spot::mask(self.board.iter().copied());
+        let outs = (2..=14)
+            .filter(|value| {
+                let card = Spot::bit(*value);
+                Spot::straight_high(all | card).is_some() && Spot::straight_high(board | card).is_none()
+            })
+            .count();
+        match outs {
+            0 => None,
+            1 => Some(Draw::Gutshot),
+            _ => Some(Draw::OpenEnder),
+        }
+    }
+
+    /// Three of a five rank stretch, at least one of them from the hole.
+    fn backdoor_straight(&self) -> bool {
+        let all = Spot::mask(self.all_cards().into_iter());
+        let hole = Spot::mask(self.hole.iter().copied());
+        (5..=14).any(|high| {
+            let window = Spot::window(high);
+            (window & all).count_ones() >= 3 && (window & hole) != 0
+        })
+    }
+
+    /// A bit for each rank value, with aces at both 14 and 1.
+    fn mask(cards: impl Iterator<Item = Card>) -> u16 {
+        cards.fold(0, |mask, card| mask | Spot::bit(Spot::value(card.get_rank())))
+    }
+
+    fn bit(value: u8) -> u16 {
+        if value == 14 { (1 << 14) | (1 << 1) } else { 1 << value }
+    }
+
+    fn window(high: u8) -> u16 {
+        (high - 4..=high).fold(0, |mask, value| mask | (1 << value))
+    }
+
+    fn straight_high(mask: u16) -> Option<u8> {
+        (5..=14)
+            .rev()
+            .find(|high| mask & Spot::window(*high) == Spot::window(*high))
+    }
+}
+
+#[cfg(test)]
+#[allow(non_snake_case)]
+mod analysis__holding_tests {
+    use super::*;
+    use crate::arrays::three::Three;
+    use crate::play::board::Board;
+    use rstest::rstest;
+    use std::str::FromStr;
+
+    fn holding(two: &str, board: &str) -> Holding {
+        Holding::new(&Two::from_str(two).unwrap(), &Cards::from_str(board).unwrap()).unwrap()
+    }
+
+    #[rstest]
+    #[case("A♠ K♣", "K♥ 7♠ 2♦", MadeHand::TopPair(Kicker::Top))]
+    #[case("K♠ Q♣", "A♥ A♠ K♦", MadeHand::TwoPairOneCard)]
+    #[case("K♠ Q♣", "A♥ K♦ 7♠", MadeHand::SecondPair)]
+    #[case("K♠ Q♣", "K♥ 7♠ 2♦", MadeHand::TopPair(Kicker::Good))]
+    #[case("K♠ 9♣", "K♥ 7♠ 2♦", MadeHand::TopPair(Kicker::Weak))]
+    #[case("A♠ 2♣", "5♥ 4♠ 3♦", MadeHand::Straight)]
+    #[case("A♠ 2♣", "K♥ 7♠ 2♦", MadeHand::WeakPair)]
+    #[case("Q♠ Q♣", "J♥ 7♠ 2♦", MadeHand::Overpair)]
+    #[case("9♠ 9♣", "J♥ 7♠ 2♦", MadeHand::PocketPairBelowTop)]
+    #[case("5♠ 5♣", "J♥ 7♠ 2♦", MadeHand::WeakPair)]
+    #[case("2♠ 2♣", "J♥ 7♠ 3♦", MadeHand::Underpair)]
+    #[case("J♠ 7♣", "J♥ 7♠ 2♦", MadeHand::TwoPairBothCards)]
+    #[case("J♠ 7♣", "J♥ 2♠ 2♦", MadeHand::TwoPairOneCard)]
+    #[case("Q♠ Q♣", "J♥ 2♠ 2♦", MadeHand::TwoPairOneCard)]
+    #[case("7♠ 7♣", "J♥ 7♥ 2♦", MadeHand::Set)]
+    #[case("A♠ 7♣", "J♥ 7♥ 7♦", MadeHand::Trips)]
+    #[case("A♠ K♣", "7♥ 7♠ 7♦", MadeHand::HighCard)]
+    #[case("A♠ K♣", "Q♥ J♠ T♦", MadeHand::NutStraight)]
+    #[case("9♠ 8♣", "Q♥ J♠ T♦", MadeHand::Straight)]
+    #[case("A♥ 2♥", "K♥ 7♥ 3♥", MadeHand::NutFlush)]
+    #[case("Q♥ 2♥", "K♥ 7♥ 3♥", MadeHand::Flush)]
+    #[case("K♥ 2♥", "A♥ 7♥ 3♥", MadeHand::NutFlush)]
+    #[case("7♠ 7♣", "J♥ 7♥ J♦", MadeHand::FullHouse)]
+    #[case("A♠ K♣", "Q♥ 8♠ 4♦ 3♣ 2♥", MadeHand::HighCard)]
+    #[case("4♠ 3♣", "A♥ K♥ Q♥ J♥ T♥", MadeHand::BoardPlays)]
+    #[case("4♠ 3♣", "A♥ A♠ K♦ K♣ Q♥", MadeHand::BoardPlays)]
+    #[case("A♠ K♣", "Q♥ Q♠ 8♦ 4♣ 2♥", MadeHand::HighCard)]
+    #[case("A♠ 3♣", "Q♥ Q♠ 8♦ 8♣ 2♥", MadeHand::HighCard)]
+    #[case("4♠ 3♣", "Q♥ Q♠ 8♦ 8♣ 5♥", MadeHand::BoardPlays)]
+    #[case("A♠ K♣", "7♥ 7♠ 7♦ 7♣", MadeHand::HighCard)]
+    #[case("4♠ 3♣", "7♥ 7♠ 7♦ 7♣ A♥", MadeHand::BoardPlays)]
+    #[case("A♠ K♣", "J♥ J♠ J♦ 7♣ 7♥", MadeHand::BoardPlays)]
+    #[case("A♠ 7♦", "J♥ J♠ J♦ 7♣ 2♥", MadeHand::FullHouse)]
+    #[case("9♠ 2♦", "K♥ Q♠ J♦ T♣ 9♥", MadeHand::BoardPlays)]
+    fn made(#[case] two: &str, #[case] board: &str, #[case] expected: MadeHand) {
+        assert_eq!(expected, holding(two, board).made, "{two} on {board}");
+    }
+
+    #[rstest]
+    #[case("A♥ 2♥", "K♥ 7♥ 3♠", vec![Draw::NutFlushDraw, Draw::BackdoorStraightDraw, Draw::Overcard])]
+    #[case("Q♥ 2♥", "K♥ 7♥ 3♠", vec![Draw::FlushDraw])]
+    #[case("9♠ 8♣", "T♥ 7♠ 2♦", vec![Draw::OpenEnder])]
+    #[case("9♠ 8♣", "Q♥ T♠ 2♦", vec![Draw::Gutshot])]
+    #[case("9♥ 8♥", "T♥ 7♥ 2♦", vec![Draw::ComboDraw, Draw::FlushDraw, Draw::OpenEnder])]
+    #[case("A♠ K♣", "9♥ 6♠ 2♦", vec![Draw::Overcards])]
+    #[case("A♠ 3♣", "9♥ 6♠ 2♦", vec![Draw::BackdoorStraightDraw, Draw::Overcard])]
+    #[case("A♠ K♠", "9♥ 6♠ 2♦", vec![Draw::BackdoorFlushDraw, Draw::Overcards])]
+    #[case("Q♠ J♣", "T♥ 4♠ 2♦", vec![Draw::BackdoorStraightDraw, Draw::Overcards])]
+    #[case("A♠ K♣", "9♥ 6♠ 2♦ 3♣", vec![Draw::Overcards])]
+    fn draws(#[case] two: &str, #[case] board: &str, #[case] expected: Vec<Draw>) {
+        assert_eq!(expected, holding(two, board).draws, "{two} on {board}");
+    }
+
+    #[test]
+    fn draws__none_on_the_river() {
+        assert!(holding("9♥ 8♥", "T♥ 7♥ 2♦ K♣ 3♠").draws.is_empty());
+    }
+
+    #[test]
+    fn draws__board_straight_draw_isnt_hero_draw() {
+        // The board's 9 8 7 6 makes a straight with any ten or five, with or without the A K.
+        assert_eq!(vec![Draw::Overcards], holding("A♠ K♣", "9♥ 8♠ 7♦ 6♣").draws);
+    }
+
+    #[test]
+    fn new__piles() {
+        let two = Two::HAND_AS_KS;
+
+        assert_eq!(
+            MadeHand::TopPair(Kicker::Top),
+            Holding::new(&two, &Three::from_str("K♥ 7♠ 2♦").unwrap()).unwrap().made
+        );
+        assert_eq!(
+            MadeHand::TopPair(Kicker::Top),
+            Holding::new(&two, &Board::from_str("K♥ 7♠ 2♦ 3♣").unwrap())
+                .unwrap()
+                .made
+        );
+    }
+
+    #[test]
+    fn new__errors() {
+        let board = Cards::from_str("K♥ 7♠ 2♦").unwrap();
+
+        assert_eq!(
+            PKError::InvalidCardCount,
+            Holding::new(&Two::HAND_AS_KS, &Cards::from_str("K♥ 7♠").unwrap()).unwrap_err()
+        );
+        assert_eq!(PKError::BlankCard, Holding::new(&Two::default(), &board).unwrap_err());
+        assert_eq!(
+            PKError::DuplicateCard,
+            Holding::new(&Two::from_str("K♥ Q♥").unwrap(), &board).unwrap_err()
+        );
+    }
+
+    #[test]
+    fn display() {
+        assert_eq!(
+            "second pair + combo draw + flush draw + gutshot",
+            holding("J♥ 9♥", "Q♥ J♠ 8♥ 3♣").to_string()
+        );
+        assert_eq!("set", holding("7♠ 7♣", "J♥ 7♥ 2♦ 3♠").to_string());
+    }
+}
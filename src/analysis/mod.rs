@@ -3,6 +3,8 @@ use crate::arrays::three::Three;
 use crate::play::hole_cards::HoleCards;
 use case_evals::CaseEvals;
 
+pub mod blockers;
+pub mod board_texture;
 pub mod case_eval;
 pub mod case_evals;
 pub mod class;
@@ -10,10 +12,17 @@ pub mod eval;
 pub mod evals;
 pub mod gto;
 pub mod hand_rank;
+pub mod holding;
+pub mod monte_carlo;
 pub mod name;
+pub mod nut_rank;
 pub mod omaha;
 pub mod outs;
 pub mod player_wins;
+pub mod potential;
+pub mod runouts;
+pub mod sampling;
+pub mod seven_card_evaluator;
 pub mod store;
 pub mod the_nuts;
 
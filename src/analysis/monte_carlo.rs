@@ -0,0 +1,588 @@
+use crate::analysis::hand_rank::HandRankValue;
+use crate::analysis::seven_card_evaluator::SevenCardEvaluator;
+use crate::arrays::seven::Seven;
+use crate::card::Card;
+use crate::cards::Cards;
+use crate::play::hole_cards::HoleCards;
+use crate::util::Util;
+use crate::util::wincounter::PlayerFlag;
+use crate::util::wincounter::results::Results;
+use crate::util::wincounter::win::Win;
+use crate::util::wincounter::wins::Wins;
+use crate::{PKError, Pile};
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+use rayon::prelude::*;
+use std::fmt::{Display, Formatter};
+use std::time::{Duration, Instant};
+
+/// `CaseEvals::from_holdem_at_deal()` plays out every one of the 1.7 million boards. Heads up that's
+/// slow. Three ways it's a coffee break. `MonteCarlo` deals out random boards instead, and tells
+/// you how much to trust the answer.
+///
+/// The boards are dealt in batches of `batch_size`, spread across threads by rayon. Each batch
+/// gets its own RNG, seeded off of the run's seed and the batch number by `stream_seed()`, so the
+/// same seed gets you the same boards no matter how many threads you have. Leave the seed out and we pick one
+/// for you, and put it in the `MonteCarloEquity` so you can run it again.
+///
+/// It stops at whichever of these comes first:
+///
+/// * It's dealt `samples` boards.
+/// * It's been running longer than the time budget.
+/// * Every player's standard error is at or under the target precision.
+///
+/// The budget and precision are only checked between rounds of `ROUND_BATCHES` batches. A run
+/// that stops on the clock isn't going to be reproducible, since how far it gets depends on how
+/// fast the machine is.
+///
+/// ```
+/// use pkcore::analysis::monte_carlo::MonteCarlo;
+/// use pkcore::cards::Cards;
+/// use pkcore::play::hole_cards::HoleCards;
+/// use std::str::FromStr;
+///
+/// let hands = HoleCards::from_str("A♠ A♥ K♠ K♥ Q♦ J♦").unwrap();
+/// let equity = MonteCarlo::new(2_000).with_seed(42).holdem(&hands, &Cards::default()).unwrap();
+/// let again = MonteCarlo::new(2_000).with_seed(42).holdem(&hands, &Cards::default()).unwrap();
+///
+/// assert_eq!(2_000, equity.samples());
+/// assert_eq!(equity.wins, again.wins);
+/// assert!(equity.equity(0) > equity.equity(1));
+/// ```
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct MonteCarlo {
+    pub samples: usize,
+    pub seed: Option<u64>,
+    pub batch_size: usize,
+    pub time_budget: Option<Duration>,
+    /// The standard error, as a fraction of the pot, that's good enough.
+    pub precision: Option<f64>,
+}
+
+/// Why a run stopped.
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub enum StopReason {
+    #[default]
+    Samples,
+    TimeBudget,
+    Precision,
+}
+
+impl MonteCarlo {
+    pub const DEFAULT_SAMPLES: usize = 100_000;
+    pub const DEFAULT_BATCH_SIZE: usize = 1_000;
+
+    /// How many batches run between checks of the clock and the precision.
+    pub const ROUND_BATCHES: usize = 16;
+
+    /// The z score for a 95% confidence interval.
+    pub const Z_95: f64 = 1.96;
+
+    #[must_use]
+    pub fn new(samples: usize) -> MonteCarlo {
+        MonteCarlo {
+            samples,
+            ..Default::default()
+        }
+    }
+
+    #[must_use]
+    pub fn with_seed(self, seed: u64) -> MonteCarlo {
+        MonteCarlo {
+            seed: Some(seed),
+            ..self
+        }
+    }
+
+    #[must_use]
+    pub fn with_batch_size(self, batch_size: usize) -> MonteCarlo {
+        MonteCarlo {
+            batch_size: batch_size.max(1),
+            ..self
+        }
+    }
+
+    #[must_use]
+    pub fn with_time_budget(self, time_budget: Duration) -> MonteCarlo {
+        MonteCarlo {
+            time_budget: Some(time_budget),
+            ..self
+        }
+    }
+
+    /// Stop once every player's standard error is at or below this, e.g. `0.005` for half a
+    /// percent.
+    #[must_use]
+    pub fn with_precision(self, standard_error: f64) -> MonteCarlo {
+        MonteCarlo {
+            precision: Some(standard_error),
+            ..self
+        }
+    }
+
+    /// The seed for stream number `stream` of a run seeded with `seed`: a batch of boards, a
+    /// matchup in a range, whatever needs its own RNG.
+    ///
+    /// Just adding the stream to the seed doesn't cut it. Batch one of seed 7 would be batch zero
+    /// of seed 8, and two runs with seeds next to each other would deal almost all the same
+    /// boards. So both go through `SplitMix64`, which scrambles the bits well enough that
+    /// neighbouring seeds and streams land nowhere near each other. For any one seed, every
+    /// stream still gets a different seed.
+    ///
+    /// ```
+    /// use pkcore::analysis::monte_carlo::MonteCarlo;
+    ///
+    /// assert_eq!(MonteCarlo::stream_seed(7, 1), MonteCarlo::stream_seed(7, 1));
+    /// assert_ne!(MonteCarlo::stream_seed(7, 1), MonteCarlo::stream_seed(8, 0));
+    /// ```
+    #[must_use]
+    pub fn stream_seed(seed: u64, stream: u64) -> u64 {
+        MonteCarlo::split_mix(MonteCarlo::split_mix(seed) ^ stream)
+    }
+
+    fn split_mix(x: u64) -> u64 {
+        let mut z = x.wrapping_add(0x9E37_79B9_7F4A_7C15);
+        z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+        z ^ (z >> 31)
+    }
+
+    /// Random hold'em boards for the hands, finishing whatever's already on the board.
+    ///
+    /// # Errors
+    ///
+    /// * `PKError::NotEnoughHands` for fewer than two hands.
+    /// * `PKError::TooManyHands` for more than sixteen, which is as many as a `PlayerFlag` holds.
+    /// * `PKError::InvalidCardCount` if the board isn't empty, a flop, or a turn.
+    /// * `PKError::BlankCard` if a hand has a blank card.
+    /// * `PKError::DuplicateCard` if a card is in more than one place.
+    pub fn holdem(&self, hands: &HoleCards, board: &Cards) -> Result<MonteCarloEquity, PKError> {
+        self.holdem_with_dead(hands, board, &Cards::default())
+    }
+
+    /// Same as `holdem()`, but the dead cards, folded hands or burn cards you happened to see,
+    /// never come on the board.
+    ///
+    /// # Errors
+    ///
+    /// Same as `holdem()`.
+    pub fn holdem_with_dead(
+        &self,
+        hands: &HoleCards,
+        board: &Cards,
+        dead: &Cards,
+    ) -> Result<MonteCarloEquity, PKError> {
+        let remaining = MonteCarlo::validate(hands, board, dead)?;
+        let seed = self.seed.unwrap_or_else(|| rand::rng().random());
+        let needed = 5 - board.len();
+        let batch_size = self.batch_size.max(1);
+        let start = Instant::now();
+
+        let mut wins = Wins::default();
+        let mut batch = 0;
+        let mut reason = StopReason::Samples;
+
+        while wins.len() < self.samples {
+            let round: Vec<(usize, usize)> = (0..MonteCarlo::ROUND_BATCHES)
+                .map(|i| {
+                    let first = wins.len() + i * batch_size;
+                    (batch + i, batch_size.min(self.samples.saturating_sub(first)))
+                })
+                .filter(|(_, size)| *size > 0)
+                .collect();
+            batch += round.len();
+
+            let dealt: Vec<Wins> = round
+                .par_iter()
+                .map(|(batch, size)| {
+                    let mut rng = StdRng::seed_from_u64(MonteCarlo::stream_seed(seed, *batch as u64));
+                    MonteCarlo::batch(&mut rng, hands, board, &remaining, needed, *size)
+                })
+                .collect();
+            for w in &dealt {
+                wins.extend(w);
+            }
+
+            if wins.len() >= self.samples {
+                break;
+            }
+            if let Some(precision) = self.precision {
+                let worst = (0..hands.len())
+                    .map(|i| MonteCarloEquity::standard_error_for(&wins, i))
+                    .fold(0.0, f64::max);
+                if worst <= precision {
+                    reason = StopReason::Precision;
+                    break;
+                }
+            }
+            if self.time_budget.is_some_and(|budget| start.elapsed() >= budget) {
+                reason = StopReason::TimeBudget;
+                break;
+            }
+        }
+
+        Ok(MonteCarloEquity {
+            hands: hands.clone(),
+            board: board.clone(),
+            results: Results::from_wins(&wins, hands.len()),
+            wins,
+            seed,
+            reason,
+            elapsed: start.elapsed(),
+        })
+    }
+
+    fn batch(
+        rng: &mut StdRng,
+        hands: &HoleCards,
+        board: &Cards,
+        remaining: &[Card],
+        needed: usize,
+        size: usize,
+    ) -> Wins {
+        let mut wins = Wins::default();
+        let mut cards = [Card::BLANK; 7];
+        for (i, card) in board.iter().enumerate() {
+            cards[i + 2] = *card;
+        }
+
+        for _ in 0..size {
+            for (i, index) in rand::seq::index::sample(rng, remaining.len(), needed)
+                .into_iter()
+                .enumerate()
+            {
+                cards[2 + board.len() + i] = remaining[index];
+            }
+
+            let mut best = HandRankValue::MAX;
+            let mut flag = PlayerFlag::default();
+            for (i, hand) in hands.iter().enumerate() {
+                cards[0] = hand.first();
+                cards[1] = hand.second();
+                let value = SevenCardEvaluator::hand_rank_value(&Seven::from(cards));
+                match value.cmp(&best) {
+                    std::cmp::Ordering::Less => {
+                        best = value;
+                        flag = Win::from_index(i);
+                    }
+                    std::cmp::Ordering::Equal => flag |= Win::from_index(i),
+                    std::cmp::Ordering::Greater => {}
+                }
+            }
+            wins.add(flag);
+        }
+        wins
+    }
+
+    fn validate(hands: &HoleCards, board: &Cards, dead: &Cards) -> Result<Vec<Card>, PKError> {
+        if hands.len() < 2 {
+            return Err(PKError::NotEnoughHands);
+        }
+        if hands.len() > Win::MAX_PLAYERS {
+            return Err(PKError::TooManyHands);
+        }
+        if !matches!(board.len(), 0 | 3 | 4) {
+            return Err(PKError::InvalidCardCount);
+        }
+
+        let mut used = board.clone();
+        used.insert_all(dead);
+        for hand in hands.iter() {
+            if !hand.is_dealt() {
+                return Err(PKError::BlankCard);
+            }
+            used.insert_all(&hand.cards());
+        }
+        if used.len() != board.len() + dead.len() + hands.len() * 2 {
+            return Err(PKError::DuplicateCard);
+        }
+
+        Ok(Cards::deck_minus(&used).iter().copied().collect())
+    }
+}
+
+impl Default for MonteCarlo {
+    fn default() -> Self {
+        MonteCarlo {
+            samples: MonteCarlo::DEFAULT_SAMPLES,
+            seed: None,
+            batch_size: MonteCarlo::DEFAULT_BATCH_SIZE,
+            time_budget: None,
+            precision: None,
+        }
+    }
+}
+
+/// What a `MonteCarlo` run came up with. The `Wins` and `Results` are the same ones you'd get
+/// from `CaseEvals`, so anything that displays those works here too.
+///
+/// Each board is worth one pot, so a player's share of it is one if they win, a half if they
+/// chop it two ways, and so on. Equity is the average share, and the standard error is how far
+/// off that average is likely to be.
+#[derive(Clone, Debug, Default)]
+pub struct MonteCarloEquity {
+    pub hands: HoleCards,
+    pub board: Cards,
+    pub wins: Wins,
+    pub results: Results,
+    pub seed: u64,
+    pub reason: StopReason,
+    pub elapsed: Duration,
+}
+
+impl MonteCarloEquity {
+    #[must_use]
+    pub fn samples(&self) -> usize {
+        self.wins.len()
+    }
+
+    /// The player's average share of the pot, from 0 to 1.
+    #[must_use]
+    pub fn equity(&self, player_index: usize) -> f64 {
+        let (sum, _) = MonteCarloEquity::shares(&self.wins, player_index);
+        MonteCarloEquity::mean(sum, self.samples())
+    }
+
+    #[must_use]
+    pub fn standard_error(&self, player_index: usize) -> f64 {
+        MonteCarloEquity::standard_error_for(&self.wins, player_index)
+    }
+
+    /// The 95% confidence interval for the player's equity, clamped to between 0 and 1.
+    #[must_use]
+    pub fn confidence_interval(&self, player_index: usize) -> (f64, f64) {
+        let equity = self.equity(player_index);
+        let margin = MonteCarlo::Z_95 * self.standard_error(player_index);
+        ((equity - margin).max(0.0), (equity + margin).min(1.0))
+    }
+
+    fn standard_error_for(wins: &Wins, player_index: usize) -> f64 {
+        let n = wins.len();
+        if n < 2 {
+            return 1.0;
+        }
+        let (sum, sum_of_squares) = MonteCarloEquity::shares(wins, player_index);
+        let mean = MonteCarloEquity::mean(sum, n);
+        let variance = (MonteCarloEquity::mean(sum_of_squares, n) - mean * mean).max(0.0);
+        (variance / Util::as_f64(n)).sqrt()
+    }
+
+    /// Adds up the player's share of each pot, and the squares of the shares.
+    fn shares(wins: &Wins, player_index: usize) -> (f64, f64) {
+        let player = Win::from_index(player_index);
+        wins.get()
+            .iter()
+            .filter(|flag| *flag & player == player)
+            .map(|flag| 1.0 / f64::from(flag.count_ones()))
+            .fold((0.0, 0.0), |(sum, sum_of_squares), share| {
+                (sum + share, sum_of_squares + share * share)
+            })
+    }
+
+    fn mean(total: f64, n: usize) -> f64 {
+        if n == 0 { 0.0 } else { total / Util::as_f64(n) }
+    }
+}
+
+impl Display for MonteCarloEquity {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        if !self.board.is_empty() {
+            writeln!(f, "Board: {}", self.board)?;
+        }
+        writeln!(f, "{} samples, seed {}", self.samples(), self.seed)?;
+        for (i, hand) in self.hands.iter().enumerate() {
+            writeln!(
+                f,
+                "  Player #{} [{}] {} ±{:.2}%",
+                i + 1,
+                hand,
+                self.results.player_to_string(i),
+                MonteCarlo::Z_95 * self.standard_error(i) * 100.0
+            )?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+#[allow(non_snake_case)]
+mod analysis__monte_carlo_tests {
+    use super::*;
+    use std::str::FromStr;
+
+    fn hands(s: &str) -> HoleCards {
+        HoleCards::from_str(s).unwrap()
+    }
+
+    #[test]
+    fn holdem() {
+        let equity = MonteCarlo::new(10_000)
+            .with_seed(1)
+            .holdem(&hands("A♠ A♥ K♠ K♥"), &Cards::default())
+            .unwrap();
+        let (low, high) = equity.confidence_interval(0);
+
+        // Aces are 82.36% to win over kings.
+        assert_eq!(10_000, equity.samples());
+        assert_eq!(StopReason::Samples, equity.reason);
+        assert!(low < 0.8236 && 0.8236 < high, "{low} {high}");
+        assert_eq!(10_000, equity.results.case_count);
+        assert!((equity.equity(0) + equity.equity(1) - 1.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn holdem__seeded() {
+        let hands = hands("A♠ K♠ 8♦ 8♣ Q♥ J♥");
+        let first = MonteCarlo::new(3_000)
+            .with_seed(7)
+            .holdem(&hands, &Cards::default())
+            .unwrap();
+        let second = MonteCarlo::new(3_000)
+            .with_seed(7)
+            .holdem(&hands, &Cards::default())
+            .unwrap();
+        let other = MonteCarlo::new(3_000)
+            .with_seed(8)
+            .holdem(&hands, &Cards::default())
+            .unwrap();
+
+        assert_eq!(7, first.seed);
+        assert_eq!(first.wins, second.wins);
+        assert_ne!(first.wins, other.wins);
+    }
+
+    /// Neighbouring seeds mustn't share any batches, however the batches line up.
+    #[test]
+    fn stream_seed() {
+        let seven: Vec<u64> = (0..100).map(|batch| MonteCarlo::stream_seed(7, batch)).collect();
+        let eight: Vec<u64> = (0..100).map(|batch| MonteCarlo::stream_seed(8, batch)).collect();
+
+        assert!(seven.iter().all(|seed| !eight.contains(seed)));
+        assert_eq!(100, seven.iter().collect::<std::collections::HashSet<_>>().len());
+    }
+
+    /// A smaller batch means more batches, each with their own RNG, so it's a different run.
+    /// Same batches, though, and the thread count doesn't matter.
+    #[test]
+    fn holdem__batch_size() {
+        let hands = hands("A♠ K♠ 8♦ 8♣");
+        let run = |batch_size| {
+            MonteCarlo::new(2_500)
+                .with_seed(7)
+                .with_batch_size(batch_size)
+                .holdem(&hands, &Cards::default())
+                .unwrap()
+        };
+
+        assert_eq!(2_500, run(100).samples());
+        assert_eq!(run(100).wins, run(100).wins);
+        assert_ne!(run(100).wins, run(1_000).wins);
+    }
+
+    #[test]
+    fn holdem__precision() {
+        let equity = MonteCarlo::new(1_000_000)
+            .with_seed(3)
+            .with_precision(0.01)
+            .holdem(&hands("A♠ A♥ K♠ K♥"), &Cards::default())
+            .unwrap();
+
+        assert_eq!(StopReason::Precision, equity.reason);
+        assert_eq!(16_000, equity.samples());
+        assert!(equity.standard_error(0) <= 0.01);
+    }
+
+    #[test]
+    fn holdem__time_budget() {
+        let equity = MonteCarlo::new(1_000_000)
+            .with_time_budget(Duration::ZERO)
+            .holdem(&hands("A♠ A♥ K♠ K♥"), &Cards::default())
+            .unwrap();
+
+        assert_eq!(StopReason::TimeBudget, equity.reason);
+        assert_eq!(16_000, equity.samples());
+    }
+
+    /// On the turn there are only 44 rivers, so it's easy to see if the sampling's right. The
+    /// A♥ Q♥ has the nut flush, and the kings need to fill up.
+    #[test]
+    fn holdem__turn() {
+        let equity = MonteCarlo::new(20_000)
+            .with_seed(11)
+            .holdem(&hands("A♥ Q♥ K♠ K♦"), &Cards::from_str("K♥ 9♥ 4♣ 2♥").unwrap())
+            .unwrap();
+        let (low, high) = equity.confidence_interval(0);
+
+        // The last king, and three each of the nines, fours and deuces: 10 rivers out of 44.
+        let exact = 34.0 / 44.0;
+        assert!(low < exact && exact < high, "{low} {exact} {high}");
+    }
+
+    #[test]
+    fn holdem__three_way() {
+        let equity = MonteCarlo::new(5_000)
+            .with_seed(5)
+            .holdem(&hands("A♠ A♥ K♠ K♥ 7♦ 2♣"), &Cards::from_str("K♦ 7♣ 2♦").unwrap())
+            .unwrap();
+
+        assert_eq!(3, equity.results.player_count);
+        assert!(equity.equity(1) > equity.equity(0));
+        assert!(equity.equity(1) > equity.equity(2));
+        assert!(((0..3).map(|i| equity.equity(i)).sum::<f64>() - 1.0).abs() < 1e-9);
+    }
+
+    /// With the other two kings dead, the kings can't flop a set.
+    #[test]
+    fn holdem_with_dead() {
+        let hands = hands("A♠ A♥ K♠ K♥");
+        let live = MonteCarlo::new(5_000)
+            .with_seed(2)
+            .holdem(&hands, &Cards::default())
+            .unwrap();
+        let dead = MonteCarlo::new(5_000)
+            .with_seed(2)
+            .holdem_with_dead(&hands, &Cards::default(), &Cards::from_str("K♦ K♣").unwrap())
+            .unwrap();
+
+        assert!(dead.equity(1) < live.equity(1));
+        assert_eq!(
+            PKError::DuplicateCard,
+            MonteCarlo::new(10)
+                .holdem_with_dead(&hands, &Cards::default(), &Cards::from_str("A♠").unwrap())
+                .unwrap_err()
+        );
+    }
+
+    #[test]
+    fn holdem__errors() {
+        let mc = MonteCarlo::new(10);
+
+        assert_eq!(
+            PKError::NotEnoughHands,
+            mc.holdem(&hands("A♠ A♥"), &Cards::default()).unwrap_err()
+        );
+        assert_eq!(
+            PKError::InvalidCardCount,
+            mc.holdem(&hands("A♠ A♥ K♠ K♥"), &Cards::from_str("2♠ 3♠").unwrap())
+                .unwrap_err()
+        );
+        assert_eq!(
+            PKError::DuplicateCard,
+            mc.holdem(&hands("A♠ A♥ K♠ K♥"), &Cards::from_str("A♠ 3♠ 4♠").unwrap())
+                .unwrap_err()
+        );
+    }
+
+    #[test]
+    fn display() {
+        let equity = MonteCarlo::new(1_000)
+            .with_seed(1)
+            .holdem(&hands("A♠ A♥ K♠ K♥"), &Cards::default())
+            .unwrap();
+
+        let display = equity.to_string();
+
+        assert!(display.starts_with("1000 samples, seed 1\n  Player #1 [A♠ A♥] "));
+        assert!(display.contains("Player #2 [K♠ K♥]"));
+    }
+}
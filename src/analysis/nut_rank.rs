@@ -0,0 +1,222 @@
+use crate::analysis::class::HandRankClass;
+use crate::analysis::eval::Eval;
+use crate::analysis::gto::twos::Twos;
+use crate::analysis::holding::Holding;
+use crate::arrays::two::Two;
+use crate::cards::Cards;
+use crate::{PKError, Pile};
+use std::cmp::Ordering;
+use std::collections::BTreeMap;
+use std::fmt::{Display, Formatter};
+
+/// How close to the nuts am I?
+///
+/// `TheNuts` gives us every hand that's possible on a board, best first, but it doesn't know
+/// what we're holding. `NutRank` takes hero's `Two`, and checks it against every other holding
+/// that's still possible once you take out the board and hero's cards. That's card removal: if
+/// you hold the A♠ on a board with three spades, nobody else can have the nut flush.
+///
+/// ```
+/// use pkcore::analysis::class::HandRankClass;
+/// use pkcore::analysis::nut_rank::NutRank;
+/// use pkcore::arrays::three::Three;
+/// use pkcore::arrays::two::Two;
+/// use std::str::FromStr;
+///
+/// // Bottom set on a two-tone flop.
+/// let flop = Three::from_str("K♠ 9♠ 5♥").unwrap();
+/// let rank = NutRank::new(&Two::from_str("5♣ 5♦").unwrap(), &flop).unwrap();
+///
+/// // Only top set and middle set are ahead.
+/// assert_eq!(6, rank.beat);
+/// assert_eq!(2, rank.beaten_by.len());
+/// assert!(rank.beaten_by.contains_key(&HandRankClass::ThreeKings));
+/// assert!(rank.percentile() > 0.99);
+/// ```
+#[derive(Clone, Debug, Default)]
+pub struct NutRank {
+    /// Hero's hand on the board as it is.
+    pub hero: Eval,
+    /// How many holdings beat hero.
+    pub beat: usize,
+    /// How many holdings tie hero.
+    pub tie: usize,
+    /// How many holdings hero beats.
+    pub lose: usize,
+    /// The holdings that beat hero, by what they make, best first.
+    pub beaten_by: BTreeMap<HandRankClass, Twos>,
+}
+
+impl NutRank {
+    /// # Errors
+    ///
+    /// * `PKError::InvalidCardCount` unless the board is three, four or five cards.
+    /// * `PKError::BlankCard` if hero isn't dealt.
+    /// * `PKError::DuplicateCard` if hero shares a card with the board.
+    pub fn new<P: Pile>(hero: &Two, board: &P) -> Result<NutRank, PKError> {
+        let board = board.cards();
+        Holding::validate(hero, &board)?;
+
+        let mut rank = NutRank {
+            hero: Holding::eval(hero, &board)?,
+            ..NutRank::default()
+        };
+
+        let mut dead = board.clone();
+        dead.insert_all(&hero.cards());
+        for v in Cards::deck_minus(&dead).combinations(2) {
+            let villain = Two::from(v);
+            let eval = Holding::eval(&villain, &board)?;
+            match eval.hand_rank.cmp(&rank.hero.hand_rank) {
+                Ordering::Greater => {
+                    rank.beat += 1;
+                    rank.beaten_by
+                        .entry(eval.hand_rank.class)
+                        .or_insert_with(|| Twos::from(Vec::<Two>::new()))
+                        .insert(villain);
+                }
+                Ordering::Equal => rank.tie += 1,
+                Ordering::Less => rank.lose += 1,
+            }
+        }
+
+        Ok(rank)
+    }
+
+    /// Nobody can have a better hand.
+    #[must_use]
+    pub fn is_nuts(&self) -> bool {
+        self.beat == 0
+    }
+
+    /// All the holdings hero was checked against.
+    #[must_use]
+    pub fn total(&self) -> usize {
+        self.beat + self.tie + self.lose
+    }
+
+    /// Where hero sits among all the possible holdings, from 0.0 to 1.0, with ties counting
+    /// for half. The nuts with no way to chop is 1.0.
+    #[must_use]
+    #[allow(clippy::cast_precision_loss)]
+    pub fn percentile(&self) -> f64 {
+        if self.total() == 0 {
+            return 0.0;
+        }
+        (self.lose as f64 + self.tie as f64 / 2.0) / self.total() as f64
+    }
+}
+
+/// ```txt
+/// 5♥ 5♦ 5♣ K♠ 9♠ - 2218-ThreeFives: beaten by 6, ties 0, beats 1075 (99.44%)
+///   ThreeKings: K♥ K♦, K♥ K♣, K♦ K♣
+///   ThreeNines: 9♥ 9♦, 9♥ 9♣, 9♦ 9♣
+/// ```
+impl Display for NutRank {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{}: beaten by {}, ties {}, beats {} ({:.2}%)",
+            self.hero,
+            self.beat,
+            self.tie,
+            self.lose,
+            self.percentile() * 100.0
+        )?;
+        for (class, twos) in &self.beaten_by {
+            write!(f, "\n  {class:?}: {twos}")?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+#[allow(non_snake_case)]
+mod analysis__nut_rank_tests {
+    use super::*;
+    use crate::arrays::three::Three;
+    use crate::play::board::Board;
+    use std::str::FromStr;
+
+    fn rank(hero: &str, board: &str) -> NutRank {
+        NutRank::new(&Two::from_str(hero).unwrap(), &Cards::from_str(board).unwrap()).unwrap()
+    }
+
+    #[test]
+    fn new() {
+        let rank = rank("5♣ 5♦", "K♠ 9♠ 5♥");
+
+        assert_eq!(HandRankClass::ThreeFives, rank.hero.hand_rank.class);
+        assert_eq!(6, rank.beat);
+        assert_eq!(0, rank.tie);
+        assert_eq!(1_081, rank.total());
+        assert_eq!(
+            "9♥ 9♦, 9♥ 9♣, 9♦ 9♣",
+            rank.beaten_by.get(&HandRankClass::ThreeNines).unwrap().to_string()
+        );
+    }
+
+    #[test]
+    fn new__nuts() {
+        let rank = rank("A♠ K♠", "Q♠ J♠ T♠");
+
+        assert!(rank.is_nuts());
+        assert!(rank.beaten_by.is_empty());
+        assert!((1.0 - rank.percentile()).abs() < f64::EPSILON);
+    }
+
+    /// The ace is on the board and hero holds the K♠, so nobody can have a better flush, and
+    /// there's no straight flush or full house possible.
+    #[test]
+    fn new__card_removal() {
+        let rank = rank("K♠ 2♠", "A♠ 9♠ 5♠ 4♦ J♣");
+
+        assert_eq!(HandRankClass::AceHighFlush, rank.hero.hand_rank.class);
+        assert!(rank.is_nuts());
+        assert_eq!(990, rank.total());
+    }
+
+    #[test]
+    fn new__ties() {
+        let rank = rank("2♣ 3♦", "A♠ K♥ Q♦ J♣ T♠");
+
+        assert_eq!(0, rank.beat);
+        assert_eq!(0, rank.lose);
+        assert!(rank.is_nuts());
+        assert!((0.5 - rank.percentile()).abs() < f64::EPSILON);
+    }
+
+    #[test]
+    fn new__piles() {
+        let hero = Two::HAND_AS_KS;
+        let flop = NutRank::new(&hero, &Three::from_str("K♥ 7♦ 2♣").unwrap()).unwrap();
+        let turn = NutRank::new(&hero, &Board::from_str("K♥ 7♦ 2♣ 3♠").unwrap()).unwrap();
+
+        assert_eq!(1_081, flop.total());
+        assert_eq!(1_035, turn.total());
+    }
+
+    #[test]
+    fn new__errors() {
+        let board = Cards::from_str("K♥ 7♦ 2♣").unwrap();
+
+        assert_eq!(
+            PKError::InvalidCardCount,
+            NutRank::new(&Two::HAND_AS_KS, &Cards::from_str("K♥ 7♦").unwrap()).unwrap_err()
+        );
+        assert_eq!(PKError::BlankCard, NutRank::new(&Two::default(), &board).unwrap_err());
+        assert_eq!(
+            PKError::DuplicateCard,
+            NutRank::new(&Two::from_str("K♥ Q♥").unwrap(), &board).unwrap_err()
+        );
+    }
+
+    #[test]
+    fn display() {
+        let display = rank("5♣ 5♦", "K♠ 9♠ 5♥").to_string();
+
+        assert!(display.starts_with("5♥ 5♦ 5♣ K♠ 9♠"));
+        assert!(display.contains("beaten by 6, ties 0, beats 1075 (99.44%)"));
+        assert!(display.ends_with("  ThreeNines: 9♥ 9♦, 9♥ 9♣, 9♦ 9♣"));
+    }
+}
@@ -1,6 +1,8 @@
 use crate::Pile;
 use crate::arrays::five::Five;
 use crate::cards::Cards;
+use std::cmp::Ordering;
+use std::fmt::{Display, Formatter};
 
 #[derive(Debug, Eq, PartialEq)]
 pub enum EightOrBetter {
@@ -120,6 +122,80 @@ impl From<Five> for EightOrBetter {
     }
 }
 
+/// A qualifying eight or better low, for any of the split pot games: Omaha/8, Stud/8, and
+/// whatever else comes along. `bits` is the value from `Pile::to_eight_or_better_bits()`.
+///
+/// The trick here is that those bits already sort the way we want them to. The highest card is
+/// the highest bit, so the smaller the number, the better the low. `8-6-4-2-A` beats
+/// `8-7-3-2-A` because `0b10101011 < 0b11000111`. `Ord` is flipped to match `Eval`, so the
+/// better low is the greater one, and two lows are equal if they have the same ranks, no matter
+/// what suits they're in.
+///
+/// ```
+/// use pkcore::analysis::omaha::EightOrBetterLow;
+/// use pkcore::arrays::five::Five;
+/// use std::str::FromStr;
+///
+/// let low = EightOrBetterLow::new(Five::from_str("8♠ 7♥ 3♦ 2♣ A♠").unwrap()).unwrap();
+///
+/// assert_eq!("8-7-3-2-A", low.to_string());
+/// assert!(EightOrBetterLow::new(Five::from_str("9♠ 7♥ 3♦ 2♣ A♠").unwrap()).is_none());
+/// ```
+#[derive(Clone, Copy, Debug, Default)]
+pub struct EightOrBetterLow {
+    pub bits: u8,
+    pub hand: Five,
+}
+
+impl EightOrBetterLow {
+    /// The low for the five cards, if they qualify.
+    #[must_use]
+    pub fn new(five: Five) -> Option<EightOrBetterLow> {
+        EightOrBetter::filter(five).map(|bits| EightOrBetterLow { bits, hand: five })
+    }
+
+    /// The best qualifying low out of a bunch of different five card hands, if there is one. For
+    /// Stud that's the 21 hands in a player's seven cards, and for Omaha it's every two hole
+    /// card and three board card combination.
+    #[must_use]
+    pub fn best<I: IntoIterator<Item = Five>>(fives: I) -> Option<EightOrBetterLow> {
+        fives.into_iter().filter_map(EightOrBetterLow::new).max()
+    }
+}
+
+impl Display for EightOrBetterLow {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let ranks: Vec<&str> = ["A", "2", "3", "4", "5", "6", "7", "8"]
+            .iter()
+            .enumerate()
+            .rev()
+            .filter(|(i, _)| self.bits & (1 << i) != 0)
+            .map(|(_, r)| *r)
+            .collect();
+        write!(f, "{}", ranks.join("-"))
+    }
+}
+
+impl Ord for EightOrBetterLow {
+    fn cmp(&self, other: &Self) -> Ordering {
+        other.bits.cmp(&self.bits)
+    }
+}
+
+impl PartialOrd for EightOrBetterLow {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl PartialEq for EightOrBetterLow {
+    fn eq(&self, other: &Self) -> bool {
+        self.bits == other.bits
+    }
+}
+
+impl Eq for EightOrBetterLow {}
+
 // impl From<u32> for EightOrBetter {
 //     fn from(collapsed: u32) -> Self {
 //         EightOrBetter::from(collapsed)
@@ -194,4 +270,24 @@ mod lookups__omaha_tests {
 
         assert_eq!(EightOrBetter::from(five), expected);
     }
+
+    #[test]
+    fn eight_or_better_low__best() {
+        let fives = ["9♠ 7♥ 3♦ 2♣ A♠", "8♠ 7♥ 3♦ 2♣ A♠", "8♦ 6♥ 4♦ 2♣ A♠", "K♠ Q♠ J♠ T♠ 9♠"]
+            .iter()
+            .map(|s| Five::from_str(s).unwrap());
+
+        assert_eq!("8-6-4-2-A", EightOrBetterLow::best(fives).unwrap().to_string());
+        assert!(EightOrBetterLow::best(vec![Five::from_str("K♠ Q♠ J♠ T♠ 9♠").unwrap()]).is_none());
+    }
+
+    /// Suits don't matter. The same ranks are the same low.
+    #[test]
+    fn eight_or_better_low__ord() {
+        let low = |s: &str| EightOrBetterLow::new(Five::from_str(s).unwrap()).unwrap();
+
+        assert!(low("5♠ 4♥ 3♦ 2♣ A♠") > low("6♠ 4♥ 3♦ 2♣ A♠"));
+        assert!(low("8♠ 6♥ 4♦ 2♣ A♠") > low("8♥ 7♥ 3♦ 2♣ A♠"));
+        assert_eq!(low("5♠ 4♥ 3♦ 2♣ A♠"), low("5♥ 4♦ 3♣ 2♠ A♥"));
+    }
 }
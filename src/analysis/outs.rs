@@ -1,9 +1,16 @@
 use crate::analysis::case_eval::CaseEval;
 use crate::analysis::case_evals::CaseEvals;
+use crate::analysis::eval::Eval;
+use crate::arrays::HandRanker;
+use crate::arrays::five::Five;
+use crate::arrays::seven::Seven;
+use crate::arrays::six::Six;
+use crate::play::game::Game;
 use crate::util::wincounter::PlayerFlag;
 use crate::util::wincounter::win::Win;
-use crate::{Card, Cards};
+use crate::{Card, Cards, PKError, Pile};
 use indexmap::IndexMap;
+use std::fmt::{Display, Formatter};
 
 /// This is old `Fudd` code.
 #[derive(Clone, Debug)]
@@ -351,6 +358,269 @@ impl From<&CaseEvals> for Outs {
     }
 }
 
+/// One card that wins for a player, and why.
+///
+/// `Outs` only tells us which cards win. When you're deciding whether to chase, that isn't
+/// enough. A flush card that also pairs the board could give your opponent a full house on a
+/// different day, so you want to know about it. Players call that a tainted out. A card that
+/// only gets you a chop isn't worth as much as one that scoops, and on the flop, a card that
+/// only wins if another one comes after it isn't really an out at all. It's runner runner.
+#[derive(Clone, Copy, Debug)]
+pub struct Out {
+    pub card: Card,
+    /// The hand the player makes with the card. For a runner runner out, it's the best hand the
+    /// player makes with it.
+    pub eval: Eval,
+    /// The card also improves an opponent's hand, say from a pair to two pair.
+    pub tainted: bool,
+    /// The card only gets the player a share of the pot.
+    pub split: bool,
+    /// The card doesn't put the player ahead on the turn, but it wins with the right river.
+    pub runner_runner: bool,
+}
+
+impl Out {
+    /// Clean outs win outright and don't help anyone else. Runner runner outs are never clean.
+    #[must_use]
+    pub fn is_clean(&self) -> bool {
+        !self.tainted && !self.split && !self.runner_runner
+    }
+}
+
+impl Display for Out {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}: {}", self.card, self.eval)?;
+        if self.runner_runner {
+            write!(f, " (runner runner)")?;
+        }
+        if self.tainted {
+            write!(f, " (tainted)")?;
+        }
+        if self.split {
+            write!(f, " (split)")?;
+        }
+        Ok(())
+    }
+}
+
+/// All the `Out`s for one player, from their point of view. Where `Outs` has every player at
+/// once, this is for one player at a time, the one who's behind and wants to know what to root
+/// for.
+///
+/// Players are by index, starting at zero, the same as `Game::turn_eval_for_player()`.
+///
+/// ```
+/// use pkcore::analysis::outs::PlayerOuts;
+/// use pkcore::arrays::two::Two;
+/// use pkcore::play::board::Board;
+/// use pkcore::play::game::Game;
+/// use pkcore::play::hole_cards::HoleCards;
+/// use std::str::FromStr;
+///
+/// let game = Game::new(
+///     HoleCards::from_str("9♥ 8♥ A♠ K♠").unwrap(),
+///     Board::from_str("K♥ 7♥ 2♠ 3♦").unwrap(),
+/// );
+///
+/// let outs = PlayerOuts::at_turn(&game, 0).unwrap();
+///
+/// assert_eq!("9 outs, 6 clean", outs.to_string());
+/// ```
+#[derive(Clone, Debug, Default)]
+pub struct PlayerOuts(Vec<Out>);
+
+impl PlayerOuts {
+    /// The outs for the player on the river, from the `CaseEvals` at the turn. Each `CaseEval`
+    /// has the river card and everyone's hand with it. `current` is everyone's hand on the turn,
+    /// which is what we compare to when we check if the card helps an opponent.
+    #[must_use]
+    pub fn from_case_evals(index: usize, current: &[Eval], case_evals: &CaseEvals) -> PlayerOuts {
+        let mut outs = Vec::new();
+        for case_eval in case_evals.iter() {
+            if let Some(out) = PlayerOuts::out(index, current, case_eval) {
+                outs.push(out);
+            }
+        }
+        PlayerOuts(outs)
+    }
+
+    /// # Errors
+    ///
+    /// * `PKError::NotEnoughCards` if the turn hasn't been dealt.
+    /// * `PKError::Fubar` if there's no player at the index, or a hand can't be evaluated.
+    pub fn at_turn(game: &Game, index: usize) -> Result<PlayerOuts, PKError> {
+        if !game.has_dealt_turn() {
+            return Err(PKError::NotEnoughCards);
+        }
+        if game.hands.get(index).is_none() {
+            return Err(PKError::Fubar);
+        }
+        Ok(game.turn_player_outs(&game.turn_case_evals())?.swap_remove(index))
+    }
+
+    /// On the flop, an out is a turn card that puts the player in front. Any other card that
+    /// can still win with the right river is a runner runner out.
+    ///
+    /// # Errors
+    ///
+    /// * `PKError::NotDealt` if the flop hasn't been dealt.
+    /// * `PKError::Fubar` if there's no player at the index.
+    pub fn at_flop(game: &Game, index: usize) -> Result<PlayerOuts, PKError> {
+        if !game.board.flop.is_dealt() {
+            return Err(PKError::NotDealt);
+        }
+        let Some(hero) = game.hands.get(index) else {
+            return Err(PKError::Fubar);
+        };
+
+        let current: Vec<Eval> = game
+            .hands
+            .iter()
+            .map(|two| Five::from_2and3(*two, game.board.flop).eval())
+            .collect();
+        let mut dealt = game.board.flop.cards();
+        dealt.insert_all(&game.hands.cards());
+        let remaining = Cards::deck_minus(&dealt);
+
+        let mut outs = Vec::new();
+        for turn in remaining.iter() {
+            let mut case_eval = CaseEval::new(Cards::from(turn));
+            for two in game.hands.iter() {
+                case_eval.push(Six::from_2and3and1(*two, game.board.flop, *turn).eval());
+            }
+            if let Some(out) = PlayerOuts::out(index, &current, &case_eval) {
+                outs.push(out);
+                continue;
+            }
+
+            // Runner runner: the best hand the player wins with, and whether every win is a chop.
+            let mut best: Option<(Eval, bool)> = None;
+            for river in remaining.iter().filter(|river| *river != turn) {
+                let mut case_eval = CaseEval::new(Cards::from(river));
+                for two in game.hands.iter() {
+                    case_eval.push(Eval::from(Seven::from_case_at_turn(
+                        *two,
+                        game.board.flop,
+                        *turn,
+                        *river,
+                    )));
+                }
+                let flag = case_eval.win_count();
+                let player = Win::from_index(index);
+                if flag & player == player {
+                    let eval = Eval::from(Seven::from_case_at_turn(*hero, game.board.flop, *turn, *river));
+                    let split = flag != player;
+                    best = match best {
+                        Some((b, s)) if b.hand_rank >= eval.hand_rank => Some((b, s && split)),
+                        Some((_, s)) => Some((eval, s && split)),
+                        None => Some((eval, split)),
+                    };
+                }
+            }
+            if let Some((eval, split)) = best {
+                outs.push(Out {
+                    card: *turn,
+                    eval,
+                    tainted: false,
+                    split,
+                    runner_runner: true,
+                });
+            }
+        }
+        Ok(PlayerOuts(outs))
+    }
+
+    /// Cards that win outright, don't help anyone else, and don't need help from the river.
+    #[must_use]
+    pub fn clean(&self) -> Vec<Out> {
+        self.0.iter().filter(|out| out.is_clean()).copied().collect()
+    }
+
+    /// Every card that's an out on the next street, leaving out the runner runners.
+    #[must_use]
+    pub fn cards(&self) -> Cards {
+        Cards::from(self.outs().iter().map(|out| out.card).collect::<Vec<Card>>())
+    }
+
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    pub fn iter(&self) -> std::slice::Iter<'_, Out> {
+        self.0.iter()
+    }
+
+    /// How many outs there are, not counting runner runners.
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.outs().len()
+    }
+
+    #[must_use]
+    pub fn outs(&self) -> Vec<Out> {
+        self.0.iter().filter(|out| !out.runner_runner).copied().collect()
+    }
+
+    #[must_use]
+    pub fn runner_runner(&self) -> Vec<Out> {
+        self.0.iter().filter(|out| out.runner_runner).copied().collect()
+    }
+
+    #[must_use]
+    pub fn tainted(&self) -> Vec<Out> {
+        self.0.iter().filter(|out| out.tainted).copied().collect()
+    }
+
+    #[must_use]
+    pub fn splits(&self) -> Vec<Out> {
+        self.0
+            .iter()
+            .filter(|out| out.split && !out.runner_runner)
+            .copied()
+            .collect()
+    }
+
+    fn out(index: usize, current: &[Eval], case_eval: &CaseEval) -> Option<Out> {
+        let flag = case_eval.win_count();
+        let player = Win::from_index(index);
+        if flag & player != player {
+            return None;
+        }
+        let tainted = case_eval
+            .iter()
+            .zip(current)
+            .enumerate()
+            .any(|(i, (after, before))| i != index && after.hand_rank.name < before.hand_rank.name);
+
+        Some(Out {
+            card: case_eval.card(),
+            eval: *case_eval.get(index)?,
+            tainted,
+            split: flag != player,
+            runner_runner: false,
+        })
+    }
+}
+
+/// `9 outs, 7 clean`, the way you'd say it at the table.
+impl Display for PlayerOuts {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let outs = self.len();
+        write!(
+            f,
+            "{outs} {}, {} clean",
+            if outs == 1 { "out" } else { "outs" },
+            self.clean().len()
+        )?;
+        let runner_runner = self.runner_runner().len();
+        if runner_runner > 0 {
+            write!(f, ", {runner_runner} runner runner")?;
+        }
+        Ok(())
+    }
+}
+
 #[cfg(test)]
 #[allow(non_snake_case)]
 mod analysis__outs_tests {
@@ -519,4 +789,91 @@ mod analysis__outs_tests {
             outs.get(1).unwrap().sort().to_string()
         );
     }
+
+    fn flush_draw() -> Game {
+        Game::new(
+            HoleCards::from_str("9♥ 8♥ A♠ K♠").unwrap(),
+            Board::from_str("K♥ 7♥ 2♠ 3♦").unwrap(),
+        )
+    }
+
+    #[test]
+    fn player_outs__at_turn() {
+        let outs = PlayerOuts::at_turn(&flush_draw(), 0).unwrap();
+
+        assert_eq!(9, outs.len());
+        assert_eq!("A♥ Q♥ J♥ T♥ 6♥ 5♥ 4♥ 3♥ 2♥", outs.cards().sort().to_string());
+        assert_eq!(
+            "A♥ 3♥ 2♥",
+            Cards::from(outs.tainted().iter().map(|out| out.card).collect::<Vec<Card>>())
+                .sort()
+                .to_string()
+        );
+        assert_eq!(6, outs.clean().len());
+        assert!(outs.splits().is_empty());
+        assert!(outs.runner_runner().is_empty());
+        assert_eq!("9 outs, 6 clean", outs.to_string());
+    }
+
+    #[test]
+    fn player_outs__at_turn__split() {
+        let game = Game::new(
+            HoleCards::from_str("A♠ 2♣ A♥ 9♦").unwrap(),
+            Board::from_str("K♦ Q♠ J♣ 4♥").unwrap(),
+        );
+
+        let outs = PlayerOuts::at_turn(&game, 0).unwrap();
+
+        // The tens give both players Broadway, and anything that pairs the board counterfeits the
+        // nine kicker.
+        assert_eq!(
+            "K♠ J♠ T♠ 4♠ K♥ Q♥ J♥ T♥ A♦ Q♦ J♦ T♦ 4♦ A♣ K♣ Q♣ T♣ 4♣",
+            Cards::from(outs.splits().iter().map(|out| out.card).collect::<Vec<Card>>())
+                .sort()
+                .to_string()
+        );
+        assert_eq!(
+            "2♠ 2♥ 2♦",
+            Cards::from(outs.clean().iter().map(|out| out.card).collect::<Vec<Card>>())
+                .sort()
+                .to_string()
+        );
+        assert_eq!("21 outs, 3 clean", outs.to_string());
+    }
+
+    #[test]
+    fn player_outs__at_turn__the_hand() {
+        let outs = PlayerOuts::at_turn(&TestData::the_hand(), 0).unwrap();
+
+        assert_eq!("6♣", outs.cards().to_string());
+        assert_eq!("1 out, 1 clean", outs.to_string());
+    }
+
+    #[test]
+    fn player_outs__at_flop() {
+        let game = Game::new(
+            HoleCards::from_str("9♥ 8♥ A♠ K♠").unwrap(),
+            Board::from_str("K♥ 7♥ 2♠").unwrap(),
+        );
+
+        let outs = PlayerOuts::at_flop(&game, 0).unwrap();
+
+        assert_eq!(9, outs.len());
+        assert!(outs.runner_runner().iter().any(|out| out.card == Card::SIX_CLUBS));
+        assert!(outs.runner_runner().iter().all(|out| !out.is_clean()));
+        assert!(outs.to_string().starts_with("9 outs, 7 clean, "));
+    }
+
+    #[test]
+    fn player_outs__errors() {
+        let game = Game::new(
+            HoleCards::from_str("9♥ 8♥ A♠ K♠").unwrap(),
+            Board::from_str("K♥ 7♥ 2♠").unwrap(),
+        );
+
+        assert_eq!(PKError::NotEnoughCards, PlayerOuts::at_turn(&game, 0).unwrap_err());
+        assert_eq!(PKError::Fubar, PlayerOuts::at_turn(&flush_draw(), 2).unwrap_err());
+        assert_eq!(PKError::Fubar, PlayerOuts::at_flop(&game, 2).unwrap_err());
+        assert_eq!(PKError::NotDealt, PlayerOuts::at_flop(&Game::default(), 0).unwrap_err());
+    }
 }
@@ -0,0 +1,349 @@
+use crate::analysis::gto::twos::DISTINCT_POCKET_PAIRS;
+use crate::analysis::gto::weighted_range::WeightedRange;
+use crate::analysis::hand_rank::HandRankValue;
+use crate::analysis::holding::Holding;
+use crate::analysis::seven_card_evaluator::SevenCardEvaluator;
+use crate::arrays::seven::Seven;
+use crate::arrays::two::Two;
+use crate::card::Card;
+use crate::cards::Cards;
+use crate::{PKError, Pile};
+use std::cmp::Ordering;
+use std::fmt::{Display, Formatter};
+
+/// The hand strength and potential numbers from Billings, Papp, Schaeffer and Szafron's
+/// [Opponent Modeling in Poker](https://webdocs.cs.ualberta.ca/~jonathan/PREVIOUS/Grad/Papers/aaai98.pdf),
+/// the paper that most of the early poker bots were built on.
+///
+/// * `hand_strength` (HS) is how often hero's hand is best right now against the opponent's
+///   range, counting ties as half.
+/// * `positive` (`PPot`) is how often hero is behind now and ends up ahead by the river.
+/// * `negative` (`NPot`) is how often hero is ahead now and ends up behind.
+/// * `ehs()` is effective hand strength: `HS × (1 − NPot) + (1 − HS) × PPot`.
+/// * `ehs2` is the expected hand strength squared over every runout, from Johanson's thesis.
+///   Squaring rewards hands whose strength swings, which is why it's better at telling draws
+///   from medium made hands.
+///
+/// Everything is exact. On the flop we look at every turn and river, which is about a million
+/// showdowns against a random hand, so it goes through the `SevenCardEvaluator`. On the river
+/// there's nothing left to come, so both potentials are zero and `ehs2` is HS squared.
+///
+/// The paper's code counts runouts against the number of opponent hands, which makes the flop
+/// potentials way too big. We weight each runout by one over the number of runouts, so that
+/// `PPot` and `NPot` are real probabilities.
+///
+/// ```
+/// use pkcore::analysis::potential::HandPotential;
+/// use pkcore::arrays::two::Two;
+/// use pkcore::cards::Cards;
+/// use std::str::FromStr;
+///
+/// // A flush draw with two overcards on the turn.
+/// let board = Cards::from_str("T♠ 7♠ 2♥ 3♦").unwrap();
+/// let potential = HandPotential::new(&Two::HAND_AS_KS, &board).unwrap();
+///
+/// assert!(potential.positive > 0.3);
+/// assert!(potential.ehs() > potential.hand_strength);
+/// ```
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct HandPotential {
+    pub hand_strength: f64,
+    pub positive: f64,
+    pub negative: f64,
+    pub ehs2: f64,
+}
+
+impl HandPotential {
+    /// Hero against any two cards.
+    ///
+    /// # Errors
+    ///
+    /// Same as `HandPotential::against()`.
+    pub fn new<P: Pile>(hero: &Two, board: &P) -> Result<HandPotential, PKError> {
+        HandPotential::against(hero, board, &WeightedRange::from(DISTINCT_POCKET_PAIRS.clone()))
+    }
+
+    /// Hero against a range. Hands in the range that use hero's cards or the board's are
+    /// dropped, and the rest count by their weight.
+    ///
+    /// # Errors
+    ///
+    /// * `PKError::InvalidCardCount` unless the board is three, four or five cards.
+    /// * `PKError::BlankCard` if hero isn't dealt.
+    /// * `PKError::DuplicateCard` if hero shares a card with the board.
+    /// * `PKError::Incomplete` if there's nothing left in the range after card removal.
+    pub fn against<P: Pile>(hero: &Two, board: &P, range: &WeightedRange) -> Result<HandPotential, PKError> {
+        let board = board.cards();
+        Holding::validate(hero, &board)?;
+
+        let mut dead = board.clone();
+        dead.insert_all(&hero.cards());
+        let opponents: Vec<(Two, f64, HandRankValue)> = range
+            .explode(&dead)
+            .into_iter()
+            .map(|(two, weight)| Ok((two, weight, HandPotential::rank(two, &board)?)))
+            .collect::<Result<_, PKError>>()?;
+        if opponents.is_empty() {
+            return Err(PKError::Incomplete);
+        }
+
+        let hero_rank = HandPotential::rank(*hero, &board)?;
+        let mut tally = Tally::default();
+        for (_, weight, rank) in &opponents {
+            tally.now[Tally::index(hero_rank, *rank)] += weight;
+        }
+
+        let to_come = 5 - board.len();
+        if to_come == 0 {
+            let hand_strength = tally.hand_strength();
+            return Ok(HandPotential {
+                hand_strength,
+                positive: 0.0,
+                negative: 0.0,
+                ehs2: hand_strength * hand_strength,
+            });
+        }
+
+        // Every opponent hand leaves the same number of cards for the runout, so they all have
+        // the same number of runouts.
+        let runouts = HandPotential::choose(Cards::deck_minus(&dead).len() - 2, to_come);
+        let mut ehs2 = 0.0;
+        let mut boards = 0.0;
+        for runout in Cards::deck_minus(&dead).combinations(to_come) {
+            let river = HandPotential::river(&board, &runout);
+            let hero_final = SevenCardEvaluator::hand_rank_value(&HandPotential::seven(*hero, &river));
+
+            let mut showdown = [0.0; 3];
+            for (two, weight, rank) in &opponents {
+                if runout.iter().any(|card| two.contains_card(*card)) {
+                    continue;
+                }
+                let opponent_final = SevenCardEvaluator::hand_rank_value(&HandPotential::seven(*two, &river));
+                let now = Tally::index(hero_rank, *rank);
+                let then = Tally::index(hero_final, opponent_final);
+                tally.later[now][then] += weight / runouts;
+                showdown[then] += weight;
+            }
+
+            let total: f64 = showdown.iter().sum();
+            if total > 0.0 {
+                let strength = (showdown[Tally::AHEAD] + showdown[Tally::TIED] / 2.0) / total;
+                ehs2 += strength * strength;
+                boards += 1.0;
+            }
+        }
+
+        Ok(HandPotential {
+            hand_strength: tally.hand_strength(),
+            positive: tally.positive(),
+            negative: tally.negative(),
+            ehs2: if boards > 0.0 { ehs2 / boards } else { 0.0 },
+        })
+    }
+
+    /// Effective hand strength: the chance that hero is ahead now and stays there, plus the
+    /// chance that they're behind and get there.
+    #[must_use]
+    pub fn ehs(&self) -> f64 {
+        self.hand_strength * (1.0 - self.negative) + (1.0 - self.hand_strength) * self.positive
+    }
+
+    /// The hand's `HandRankValue` on the board as it is now.
+    fn rank(two: Two, board: &Cards) -> Result<HandRankValue, PKError> {
+        Ok(Holding::eval(&two, board)?.hand_rank.value)
+    }
+
+    fn river(board: &Cards, runout: &[Card]) -> [Card; 5] {
+        let mut river = [Card::BLANK; 5];
+        for (i, card) in board.iter().chain(runout.iter()).enumerate() {
+            river[i] = *card;
+        }
+        river
+    }
+
+    fn seven(two: Two, river: &[Card; 5]) -> Seven {
+        Seven::from([
+            two.first(),
+            two.second(),
+            river[0],
+            river[1],
+            river[2],
+            river[3],
+            river[4],
+        ])
+    }
+
+    #[allow(clippy::cast_precision_loss)]
+    fn choose(n: usize, k: usize) -> f64 {
+        (0..k).fold(1.0, |acc, i| acc * (n - i) as f64 / (i + 1) as f64)
+    }
+}
+
+impl Display for HandPotential {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "HS: {:.2}% PPot: {:.2}% NPot: {:.2}% EHS: {:.2}% EHS²: {:.2}%",
+            self.hand_strength * 100.0,
+            self.positive * 100.0,
+            self.negative * 100.0,
+            self.ehs() * 100.0,
+            self.ehs2 * 100.0
+        )
+    }
+}
+
+/// The paper's `HP` and `HPTotal` arrays: weights by where hero stands now, and by where hero
+/// stands now and on the river.
+#[derive(Default)]
+struct Tally {
+    now: [f64; 3],
+    later: [[f64; 3]; 3],
+}
+
+impl Tally {
+    const AHEAD: usize = 0;
+    const TIED: usize = 1;
+    const BEHIND: usize = 2;
+
+    /// Lower `HandRankValues` are better.
+    fn index(hero: HandRankValue, opponent: HandRankValue) -> usize {
+        match hero.cmp(&opponent) {
+            Ordering::Less => Tally::AHEAD,
+            Ordering::Equal => Tally::TIED,
+            Ordering::Greater => Tally::BEHIND,
+        }
+    }
+
+    fn hand_strength(&self) -> f64 {
+        let total: f64 = self.now.iter().sum();
+        (self.now[Tally::AHEAD] + self.now[Tally::TIED] / 2.0) / total
+    }
+
+    fn positive(&self) -> f64 {
+        let (ahead, tied, behind) = (Tally::AHEAD, Tally::TIED, Tally::BEHIND);
+        let denominator = self.now[behind] + self.now[tied] / 2.0;
+        if denominator > 0.0 {
+            (self.later[behind][ahead] + self.later[behind][tied] / 2.0 + self.later[tied][ahead] / 2.0) / denominator
+        } else {
+            0.0
+        }
+    }
+
+    fn negative(&self) -> f64 {
+        let (ahead, tied, behind) = (Tally::AHEAD, Tally::TIED, Tally::BEHIND);
+        let denominator = self.now[ahead] + self.now[tied] / 2.0;
+        if denominator > 0.0 {
+            (self.later[ahead][behind] + self.later[ahead][tied] / 2.0 + self.later[tied][behind] / 2.0) / denominator
+        } else {
+            0.0
+        }
+    }
+}
+
+#[cfg(test)]
+#[allow(non_snake_case)]
+mod analysis__potential_tests {
+    use super::*;
+    use std::str::FromStr;
+
+    fn potential(hero: &str, board: &str) -> HandPotential {
+        HandPotential::new(&Two::from_str(hero).unwrap(), &Cards::from_str(board).unwrap()).unwrap()
+    }
+
+    #[test]
+    fn new__river() {
+        let potential = potential("A♠ A♥", "A♦ A♣ K♠ 7♥ 2♦");
+
+        assert!((1.0 - potential.hand_strength).abs() < f64::EPSILON);
+        assert!((1.0 - potential.ehs2).abs() < f64::EPSILON);
+        assert!(potential.positive.abs() < f64::EPSILON);
+        assert!(potential.negative.abs() < f64::EPSILON);
+    }
+
+    /// The nuts on the turn can still be outdrawn, but only a little.
+    #[test]
+    fn new__turn__made_hand() {
+        let potential = potential("K♠ K♥", "K♦ 7♥ 2♣ 3♠");
+
+        assert!(potential.hand_strength > 0.99);
+        assert!(potential.negative < 0.02);
+        assert!(potential.ehs() < potential.hand_strength);
+    }
+
+    #[test]
+    fn new__turn__draw() {
+        let draw = potential("A♠ K♠", "T♠ 7♠ 2♥ 3♦");
+        let made = potential("T♥ 9♥", "T♠ 7♠ 2♥ 3♦");
+
+        assert!(draw.positive > made.positive);
+        assert!(made.hand_strength > draw.hand_strength);
+    }
+
+    #[test]
+    fn new__flop() {
+        let potential = potential("9♥ 8♥", "K♥ 7♥ 2♠");
+
+        assert!(potential.positive > potential.negative);
+        assert!(potential.ehs() > potential.hand_strength);
+        assert!(potential.ehs2 > 0.0 && potential.ehs2 < 1.0);
+    }
+
+    /// Against a single hand on the turn, the potentials are just counting outs.
+    #[test]
+    fn against__turn__single_hand() {
+        let range = WeightedRange::from(Two::from_str("A♠ A♥").unwrap());
+        let board = Cards::from_str("K♥ 7♥ 2♠ 3♦").unwrap();
+
+        let potential = HandPotential::against(&Two::from_str("9♥ 8♥").unwrap(), &board, &range).unwrap();
+
+        // Behind to aces, with nine hearts less the A♥ of 44 rivers.
+        assert!(potential.hand_strength.abs() < f64::EPSILON);
+        assert!((potential.positive - 8.0 / 44.0).abs() < 1e-9);
+        assert!((potential.ehs() - 8.0 / 44.0).abs() < 1e-9);
+        assert!((potential.ehs2 - 8.0 / 44.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn against__flop__single_hand() {
+        let range = WeightedRange::from(Two::from_str("A♠ A♥").unwrap());
+        let board = Cards::from_str("A♦ 7♣ 2♠").unwrap();
+
+        let potential = HandPotential::against(&Two::from_str("K♠ K♥").unwrap(), &board, &range).unwrap();
+
+        // Runner runner kings, or running straights and flushes that don't exist, leave one
+        // runout: K♦ K♣ out of 990.
+        assert!((potential.positive - 1.0 / 990.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn against__errors() {
+        let hero = Two::HAND_AS_KS;
+        let range = WeightedRange::from(Two::HAND_AS_KS);
+
+        assert_eq!(
+            PKError::InvalidCardCount,
+            HandPotential::new(&hero, &Cards::from_str("K♥ 7♠").unwrap()).unwrap_err()
+        );
+        assert_eq!(
+            PKError::DuplicateCard,
+            HandPotential::new(&hero, &Cards::from_str("A♠ 7♠ 2♦").unwrap()).unwrap_err()
+        );
+        assert_eq!(
+            PKError::BlankCard,
+            HandPotential::new(&Two::default(), &Cards::from_str("A♥ 7♠ 2♦").unwrap()).unwrap_err()
+        );
+        assert_eq!(
+            PKError::Incomplete,
+            HandPotential::against(&hero, &Cards::from_str("A♥ 7♠ 2♦").unwrap(), &range).unwrap_err()
+        );
+    }
+
+    #[test]
+    fn display() {
+        assert_eq!(
+            "HS: 100.00% PPot: 0.00% NPot: 0.00% EHS: 100.00% EHS²: 100.00%",
+            potential("A♠ A♥", "A♦ A♣ K♠ 7♥ 2♦").to_string()
+        );
+    }
+}
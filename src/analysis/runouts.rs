@@ -0,0 +1,557 @@
+use crate::analysis::case_evals::CaseEvals;
+use crate::arrays::three::Three;
+use crate::card::Card;
+use crate::cards::Cards;
+use crate::play::hole_cards::HoleCards;
+use crate::rank::Rank;
+use crate::suit::Suit;
+use crate::util::wincounter::PlayerFlag;
+use crate::util::wincounter::win::Win;
+use crate::{PKError, Pile};
+use csv::WriterBuilder;
+use std::collections::HashMap;
+use std::fmt::{Display, Formatter};
+use std::io::Write;
+use strum::IntoEnumIterator;
+
+/// What happens with one turn card: everybody's equity once it hits, and who wins with every
+/// river that can follow it.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct TurnRunout {
+    pub card: Card,
+    /// Each player's equity once the card hits, in the same order as the hands. Ties are split.
+    pub equity: Vec<f64>,
+    /// Every river card after this turn, with the players who win with it.
+    pub rivers: Vec<(Card, PlayerFlag)>,
+}
+
+impl TurnRunout {
+    /// The player with the most equity after this turn. `None` if the top is shared.
+    #[must_use]
+    pub fn leader(&self) -> Option<usize> {
+        RunoutMatrix::leader_of(&self.equity)
+    }
+
+    /// The players who win when the river is the card.
+    #[must_use]
+    pub fn river(&self, card: Card) -> Option<PlayerFlag> {
+        self.rivers
+            .iter()
+            .find(|(river, _)| *river == card)
+            .map(|(_, flag)| *flag)
+    }
+}
+
+/// The turn cards that share a rank or a suit, with everybody's average equity across them.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct RunoutSummary {
+    pub cards: Cards,
+    pub equity: Vec<f64>,
+}
+
+/// `TurnEval::case_evals()` gives us every river once the turn is out. This is the same idea
+/// one street earlier, as a report: for a flop all in, what does every turn card do to
+/// everybody's equity, and who wins with every turn and river pair?
+///
+/// The `CaseEvals` that `FlopEval` already has are every two card runout, so we don't need to
+/// evaluate anything new. We just need to turn them on their side. Each runout shows up twice
+/// in the matrix, once with each card as the turn, since it doesn't matter which order they
+/// come in.
+///
+/// A _scare card_ here is a turn card that changes who's ahead. On the flop the leader is the
+/// player with the most equity, and if a turn card hands the most equity to somebody else, it
+/// gets flagged.
+///
+/// ```
+/// use pkcore::analysis::runouts::RunoutMatrix;
+/// use pkcore::arrays::three::Three;
+/// use pkcore::card::Card;
+/// use pkcore::play::hole_cards::HoleCards;
+/// use std::str::FromStr;
+///
+/// // THE HAND: Negreanu's sixes against Hansen's fives.
+/// let matrix = RunoutMatrix::new(
+///     Three::from_str("9♣ 6♦ 5♥").unwrap(),
+///     &HoleCards::from_str("6♠ 6♥ 5♦ 5♣").unwrap(),
+/// )
+/// .unwrap();
+///
+/// assert_eq!(Some(0), matrix.leader());
+/// assert_eq!(45, matrix.turns.len());
+///
+/// // The case five is the only card that puts Gus in front.
+/// assert_eq!("5♠", matrix.scare_cards().to_string());
+/// ```
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct RunoutMatrix {
+    pub flop: Three,
+    pub hands: HoleCards,
+    /// Each player's equity on the flop. Ties are split.
+    pub equity: Vec<f64>,
+    /// Every turn card, in deck order.
+    pub turns: Vec<TurnRunout>,
+}
+
+impl RunoutMatrix {
+    /// # Errors
+    ///
+    /// `PKError::NotDealt` if the flop isn't dealt or there aren't any hands.
+    pub fn new(flop: Three, hands: &HoleCards) -> Result<RunoutMatrix, PKError> {
+        if !flop.is_dealt() || hands.is_empty() {
+            return Err(PKError::NotDealt);
+        }
+        Ok(RunoutMatrix::from_case_evals(
+            flop,
+            hands,
+            &CaseEvals::from_holdem_at_flop_mpsc(flop, hands),
+        ))
+    }
+
+    /// Builds the matrix from `CaseEvals` that have already been worked out at the flop, such
+    /// as the ones in `FlopEval`. Runouts that aren't in the `CaseEvals` are left out.
+    #[must_use]
+    #[allow(clippy::cast_precision_loss)]
+    pub fn from_case_evals(flop: Three, hands: &HoleCards, case_evals: &CaseEvals) -> RunoutMatrix {
+        let mut winners: HashMap<(Card, Card), PlayerFlag> = HashMap::new();
+        for case_eval in case_evals.iter() {
+            if let Some(cards) = case_eval.cards() {
+                if let (Some(a), Some(b)) = (cards.get_index(0), cards.get_index(1)) {
+                    winners.insert((*a, *b), case_eval.win_count());
+                    winners.insert((*b, *a), case_eval.win_count());
+                }
+            }
+        }
+
+        let mut dealt = flop.cards();
+        dealt.insert_all(&hands.cards());
+        let remaining = Cards::deck_minus(&dealt);
+
+        let turns: Vec<TurnRunout> = remaining
+            .iter()
+            .map(|turn| {
+                let rivers: Vec<(Card, PlayerFlag)> = remaining
+                    .iter()
+                    .filter_map(|river| winners.get(&(*turn, *river)).map(|flag| (*river, *flag)))
+                    .collect();
+                let equity = RunoutMatrix::average(
+                    &rivers
+                        .iter()
+                        .map(|(_, flag)| RunoutMatrix::shares(*flag, hands.len()))
+                        .collect::<Vec<Vec<f64>>>(),
+                    hands.len(),
+                );
+                TurnRunout {
+                    card: *turn,
+                    equity,
+                    rivers,
+                }
+            })
+            .filter(|turn| !turn.rivers.is_empty())
+            .collect();
+
+        RunoutMatrix {
+            flop,
+            hands: hands.clone(),
+            equity: RunoutMatrix::average(
+                &turns.iter().map(|turn| turn.equity.clone()).collect::<Vec<Vec<f64>>>(),
+                hands.len(),
+            ),
+            turns,
+        }
+    }
+
+    /// The player with the most equity on the flop. `None` if the top is shared.
+    #[must_use]
+    pub fn leader(&self) -> Option<usize> {
+        RunoutMatrix::leader_of(&self.equity)
+    }
+
+    #[must_use]
+    pub fn turn(&self, card: Card) -> Option<&TurnRunout> {
+        self.turns.iter().find(|turn| turn.card == card)
+    }
+
+    /// The players who win when the turn and river are the two cards, in either order.
+    #[must_use]
+    pub fn winners(&self, turn: Card, river: Card) -> Option<PlayerFlag> {
+        self.turn(turn).and_then(|runout| runout.river(river))
+    }
+
+    /// The turn cards that change who's ahead.
+    #[must_use]
+    pub fn scare_cards(&self) -> Cards {
+        let leader = self.leader();
+        let mut cards = Cards::default();
+        for turn in self.turns.iter().filter(|turn| turn.leader() != leader) {
+            cards.insert(turn.card);
+        }
+        cards
+    }
+
+    /// The turn cards grouped by rank, from aces down. Ranks that can't come are left out.
+    #[must_use]
+    pub fn by_rank(&self) -> Vec<(Rank, RunoutSummary)> {
+        Rank::iter()
+            .filter_map(|rank| Some(rank).zip(self.summary(|card| card.get_rank() == rank)))
+            .collect()
+    }
+
+    /// The turn cards grouped by suit.
+    #[must_use]
+    pub fn by_suit(&self) -> Vec<(Suit, RunoutSummary)> {
+        Suit::iter()
+            .filter_map(|suit| Some(suit).zip(self.summary(|card| card.get_suit() == suit)))
+            .collect()
+    }
+
+    /// Writes a row for every turn card, with a header: `turn,player_1,...,leader,scare`. The
+    /// leader is the player's number, and is empty if it's shared.
+    ///
+    /// # Errors
+    ///
+    /// If the writer fails.
+    pub fn write_csv<W: Write>(&self, writer: W) -> Result<(), Box<dyn std::error::Error>> {
+        let mut wtr = WriterBuilder::new().from_writer(writer);
+        let mut header = vec!["turn".to_string()];
+        header.extend(self.player_columns());
+        header.push("leader".to_string());
+        header.push("scare".to_string());
+        wtr.write_record(&header)?;
+
+        let leader = self.leader();
+        for turn in &self.turns {
+            let mut record = vec![turn.card.to_string()];
+            record.extend(turn.equity.iter().map(ToString::to_string));
+            record.push(turn.leader().map_or_else(String::new, |i| (i + 1).to_string()));
+            record.push((turn.leader() != leader).to_string());
+            wtr.write_record(&record)?;
+        }
+        wtr.flush()?;
+        Ok(())
+    }
+
+    /// Writes every turn and river pair, with a header: `turn,river,winners,player_1,...`. The
+    /// winners are the players' numbers separated by spaces, and each player column is their
+    /// share of the pot.
+    ///
+    /// # Errors
+    ///
+    /// If the writer fails.
+    pub fn write_matrix_csv<W: Write>(&self, writer: W) -> Result<(), Box<dyn std::error::Error>> {
+        let mut wtr = WriterBuilder::new().from_writer(writer);
+        let mut header = vec!["turn".to_string(), "river".to_string(), "winners".to_string()];
+        header.extend(self.player_columns());
+        wtr.write_record(&header)?;
+
+        for turn in &self.turns {
+            for (river, flag) in &turn.rivers {
+                let mut record = vec![
+                    turn.card.to_string(),
+                    river.to_string(),
+                    RunoutMatrix::players(*flag, self.hands.len())
+                        .iter()
+                        .map(|i| (i + 1).to_string())
+                        .collect::<Vec<String>>()
+                        .join(" "),
+                ];
+                record.extend(
+                    RunoutMatrix::shares(*flag, self.hands.len())
+                        .iter()
+                        .map(ToString::to_string),
+                );
+                wtr.write_record(&record)?;
+            }
+        }
+        wtr.flush()?;
+        Ok(())
+    }
+
+    /// # Errors
+    ///
+    /// Unable to create csv file.
+    pub fn generate_csv(&self, path: &str) -> Result<(), Box<dyn std::error::Error>> {
+        self.write_csv(std::fs::File::create(path)?)
+    }
+
+    /// # Errors
+    ///
+    /// Unable to create csv file.
+    pub fn generate_matrix_csv(&self, path: &str) -> Result<(), Box<dyn std::error::Error>> {
+        self.write_matrix_csv(std::fs::File::create(path)?)
+    }
+
+    fn player_columns(&self) -> Vec<String> {
+        (1..=self.hands.len()).map(|i| format!("player_{i}")).collect()
+    }
+
+    fn summary<F: Fn(&Card) -> bool>(&self, f: F) -> Option<RunoutSummary> {
+        let turns: Vec<&TurnRunout> = self.turns.iter().filter(|turn| f(&turn.card)).collect();
+        if turns.is_empty() {
+            return None;
+        }
+        let mut cards = Cards::default();
+        for turn in &turns {
+            cards.insert(turn.card);
+        }
+        Some(RunoutSummary {
+            cards,
+            equity: RunoutMatrix::average(
+                &turns.iter().map(|turn| turn.equity.clone()).collect::<Vec<Vec<f64>>>(),
+                self.hands.len(),
+            ),
+        })
+    }
+
+    fn players(flag: PlayerFlag, player_count: usize) -> Vec<usize> {
+        (0..player_count).filter(|i| flag & Win::from_index(*i) != 0).collect()
+    }
+
+    /// Each player's share of one pot, split evenly between the winners.
+    #[allow(clippy::cast_precision_loss)]
+    fn shares(flag: PlayerFlag, player_count: usize) -> Vec<f64> {
+        let winners = RunoutMatrix::players(flag, player_count);
+        let mut shares = vec![0.0; player_count];
+        for i in &winners {
+            shares[*i] = 1.0 / winners.len() as f64;
+        }
+        shares
+    }
+
+    #[allow(clippy::cast_precision_loss)]
+    fn average(rows: &[Vec<f64>], player_count: usize) -> Vec<f64> {
+        let mut average = vec![0.0; player_count];
+        if rows.is_empty() {
+            return average;
+        }
+        for row in rows {
+            for (total, share) in average.iter_mut().zip(row) {
+                *total += share;
+            }
+        }
+        for total in &mut average {
+            *total /= rows.len() as f64;
+        }
+        average
+    }
+
+    fn leader_of(equity: &[f64]) -> Option<usize> {
+        let best = equity.iter().copied().fold(f64::MIN, f64::max);
+        let mut leaders = equity.iter().enumerate().filter(|(_, e)| (best - **e).abs() < 1e-9);
+        match (leaders.next(), leaders.next()) {
+            (Some((i, _)), None) => Some(i),
+            _ => None,
+        }
+    }
+
+    fn equities(equity: &[f64]) -> String {
+        equity
+            .iter()
+            .map(|e| format!("{:>7.2}%", e * 100.0))
+            .collect::<Vec<String>>()
+            .join(" ")
+    }
+
+    /// The number of the player who wins, or `=` for a split.
+    fn cell(flag: PlayerFlag, player_count: usize) -> String {
+        match RunoutMatrix::players(flag, player_count).as_slice() {
+            [i] => (i + 1).to_string(),
+            [] => "-".to_string(),
+            _ => "=".to_string(),
+        }
+    }
+}
+
+/// Equity for every turn card, then by rank and by suit, and finally the turn and river matrix,
+/// with the number of the player who wins each runout:
+///
+/// ```txt
+/// Runouts from 9♣ 6♦ 5♥:   94.85%    5.15%
+///   A♠    97.73%    2.27%
+///   ...
+///   5♠     2.27%   97.73% scare
+///   ...
+/// By rank:
+///   A     97.73%    2.27% (4)
+///   ...
+/// By suit:
+///   ♠     89.02%   10.98% (12)
+///   ...
+/// Matrix:
+///      A♠ K♠ Q♠ J♠ ...
+///   A♠  ·  1  1  1 ...
+/// ```
+impl Display for RunoutMatrix {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let leader = self.leader();
+        write!(
+            f,
+            "Runouts from {}: {}",
+            self.flop,
+            RunoutMatrix::equities(&self.equity)
+        )?;
+        for turn in &self.turns {
+            write!(f, "\n  {}  {}", turn.card, RunoutMatrix::equities(&turn.equity))?;
+            if turn.leader() != leader {
+                write!(f, " scare")?;
+            }
+        }
+
+        write!(f, "\nBy rank:")?;
+        for (rank, summary) in self.by_rank() {
+            write!(
+                f,
+                "\n  {rank}   {} ({})",
+                RunoutMatrix::equities(&summary.equity),
+                summary.cards.len()
+            )?;
+        }
+        write!(f, "\nBy suit:")?;
+        for (suit, summary) in self.by_suit() {
+            write!(
+                f,
+                "\n  {suit}   {} ({})",
+                RunoutMatrix::equities(&summary.equity),
+                summary.cards.len()
+            )?;
+        }
+
+        write!(f, "\nMatrix:\n    ")?;
+        for turn in &self.turns {
+            write!(f, " {}", turn.card)?;
+        }
+        for turn in &self.turns {
+            write!(f, "\n  {}", turn.card)?;
+            for column in &self.turns {
+                match turn.river(column.card) {
+                    Some(flag) => write!(f, " {:>2}", RunoutMatrix::cell(flag, self.hands.len()))?,
+                    None => write!(f, "  ·")?,
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+#[allow(non_snake_case)]
+mod analysis__runouts_tests {
+    use super::*;
+    use crate::util::data::TestData;
+    use std::str::FromStr;
+
+    fn the_hand() -> RunoutMatrix {
+        let game = TestData::the_hand();
+        RunoutMatrix::new(game.board.flop, &game.hands).unwrap()
+    }
+
+    fn card(s: &str) -> Card {
+        Card::from_str(s).unwrap()
+    }
+
+    /// 990 runouts: Daniel wins 931, Gus wins 43, and they chop 16.
+    #[test]
+    fn new() {
+        let matrix = the_hand();
+
+        assert_eq!(45, matrix.turns.len());
+        assert!(matrix.turns.iter().all(|turn| turn.rivers.len() == 44));
+        assert!((matrix.equity[0] - 939.0 / 990.0).abs() < 1e-9);
+        assert!((matrix.equity[1] - 51.0 / 990.0).abs() < 1e-9);
+        assert_eq!(Some(0), matrix.leader());
+    }
+
+    #[test]
+    fn new__not_dealt() {
+        let game = TestData::the_hand();
+
+        assert_eq!(
+            PKError::NotDealt,
+            RunoutMatrix::new(Three::default(), &game.hands).unwrap_err()
+        );
+        assert_eq!(
+            PKError::NotDealt,
+            RunoutMatrix::new(game.board.flop, &HoleCards::default()).unwrap_err()
+        );
+    }
+
+    /// The case five gives Gus quads, and only the case six saves Daniel.
+    #[test]
+    fn turn() {
+        let matrix = the_hand();
+        let turn = matrix.turn(card("5♠")).unwrap();
+
+        assert!((turn.equity[1] - 43.0 / 44.0).abs() < 1e-9);
+        assert_eq!(Some(1), turn.leader());
+        assert_eq!(Some(Win::FIRST), turn.river(card("6♣")));
+        assert_eq!(Some(Win::SECOND), turn.river(card("2♦")));
+        assert!(matrix.turn(card("6♠")).is_none());
+    }
+
+    #[test]
+    fn winners() {
+        let matrix = the_hand();
+
+        assert_eq!(Some(Win::SECOND), matrix.winners(card("5♠"), card("2♦")));
+        assert_eq!(Some(Win::SECOND), matrix.winners(card("2♦"), card("5♠")));
+        assert_eq!(Some(Win::FIRST | Win::SECOND), matrix.winners(card("7♠"), card("8♦")));
+        assert!(matrix.winners(card("5♠"), card("5♠")).is_none());
+    }
+
+    #[test]
+    fn scare_cards() {
+        assert_eq!("5♠", the_hand().scare_cards().to_string());
+    }
+
+    #[test]
+    fn by_rank() {
+        let by_rank = the_hand().by_rank();
+        let (rank, fives) = by_rank.iter().find(|(rank, _)| *rank == Rank::FIVE).unwrap();
+
+        assert_eq!(13, by_rank.len());
+        assert_eq!(Rank::ACE, by_rank[0].0);
+        assert_eq!(Rank::FIVE, *rank);
+        assert_eq!("5♠", fives.cards.to_string());
+        assert!((fives.equity[1] - 43.0 / 44.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn by_suit() {
+        let by_suit = the_hand().by_suit();
+        let total: usize = by_suit.iter().map(|(_, summary)| summary.cards.len()).sum();
+
+        assert_eq!(4, by_suit.len());
+        assert_eq!(45, total);
+    }
+
+    #[test]
+    fn write_csv() {
+        let mut csv = Vec::new();
+        the_hand().write_csv(&mut csv).unwrap();
+        let csv = String::from_utf8(csv).unwrap();
+
+        assert!(csv.starts_with("turn,player_1,player_2,leader,scare\n"));
+        assert_eq!(46, csv.lines().count());
+        assert!(csv.contains("\n5♠,0.022727272727272728,0.9772727272727273,2,true\n"));
+    }
+
+    #[test]
+    fn write_matrix_csv() {
+        let mut csv = Vec::new();
+        the_hand().write_matrix_csv(&mut csv).unwrap();
+        let csv = String::from_utf8(csv).unwrap();
+
+        assert!(csv.starts_with("turn,river,winners,player_1,player_2\n"));
+        assert_eq!(1_981, csv.lines().count());
+        assert!(csv.contains("\n7♠,8♦,1 2,0.5,0.5\n"));
+    }
+
+    #[test]
+    fn display() {
+        let display = the_hand().to_string();
+
+        assert!(display.starts_with("Runouts from 9♣ 6♦ 5♥:   94.85%    5.15%"));
+        assert!(display.contains("\n  5♠     2.27%   97.73% scare"));
+        assert!(display.contains("\nBy suit:"));
+        assert!(display.contains("\nMatrix:"));
+    }
+}
@@ -0,0 +1,163 @@
+use crate::card::Card;
+use crate::cards::Cards;
+use rand::rngs::StdRng;
+use rand::seq::SliceRandom;
+use rand::{Rng, SeedableRng};
+
+/// Omaha, Razz and Stud/8 all have the same problem. Late in the hand there are only a few
+/// hundred ways to finish it, so we deal them all. Early on there are millions, so we punt and
+/// deal a random sample. `Sampling` is where the line between the two lives, how many to deal
+/// when we're on the wrong side of it, and what seed to deal them with.
+///
+/// Leave the seed out and the sample is different every time, which is what you want at the
+/// table. Give it one and the same sample comes out every time, which is what you want in a
+/// test.
+///
+/// ```
+/// use pkcore::analysis::sampling::Sampling;
+/// use pkcore::cards::Cards;
+/// use std::str::FromStr;
+///
+/// let sampling = Sampling::new(10).with_seed(42);
+///
+/// // Eight cards, two at a time, is only 28 ways to go, so we deal them all.
+/// let (cases, exhaustive) = sampling.runouts(&Cards::from_str("A♠ K♠ Q♠ J♠ T♠ 9♠ 8♠ 7♠").unwrap(), 2);
+/// assert_eq!(28, cases.len());
+/// assert!(exhaustive);
+///
+/// // A whole board out of a whole deck is over two million, so we take our ten.
+/// let (cases, exhaustive) = sampling.runouts(&Cards::deck(), 5);
+/// assert_eq!(10, cases.len());
+/// assert!(!exhaustive);
+/// assert_eq!(cases, sampling.runouts(&Cards::deck(), 5).0);
+/// ```
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct Sampling {
+    pub samples: usize,
+    pub seed: Option<u64>,
+}
+
+impl Sampling {
+    /// Anything with more ways to finish than this gets sampled instead of played out.
+    pub const EXHAUSTIVE_LIMIT: usize = 100_000;
+
+    /// The number of random runouts we deal when we can't do them all.
+    pub const SAMPLES: usize = 50_000;
+
+    #[must_use]
+    pub fn new(samples: usize) -> Sampling {
+        Sampling { samples, seed: None }
+    }
+
+    #[must_use]
+    pub fn with_seed(self, seed: u64) -> Sampling {
+        Sampling {
+            seed: Some(seed),
+            ..self
+        }
+    }
+
+    /// n choose k, capped at `usize::MAX`.
+    #[must_use]
+    pub fn count(n: usize, k: usize) -> usize {
+        if k > n {
+            return 0;
+        }
+        let k = k.min(n - k);
+        let mut count: usize = 1;
+        for i in 0..k {
+            count = match count.checked_mul(n - i) {
+                Some(c) => c / (i + 1),
+                None => return usize::MAX,
+            };
+        }
+        count
+    }
+
+    /// `true` if `count` ways to finish are few enough to deal them all.
+    #[must_use]
+    pub fn is_exhaustive(count: usize) -> bool {
+        count <= Sampling::EXHAUSTIVE_LIMIT
+    }
+
+    /// An RNG from the seed, or a random one if there isn't a seed.
+    #[must_use]
+    pub fn rng(&self) -> StdRng {
+        StdRng::seed_from_u64(self.seed.unwrap_or_else(|| rand::rng().random()))
+    }
+
+    /// Returns every way to deal the `needed` cards out of what's `remaining`, or, if there are
+    /// more than `EXHAUSTIVE_LIMIT` of them, `samples` random ones. The flag is `true` if the
+    /// runouts are the real deal.
+    #[must_use]
+    pub fn runouts(&self, remaining: &Cards, needed: usize) -> (Vec<Cards>, bool) {
+        if Sampling::is_exhaustive(Sampling::count(remaining.len(), needed)) {
+            (remaining.combinations(needed).map(Cards::from).collect(), true)
+        } else {
+            let mut rng = self.rng();
+            let cases = (0..self.samples)
+                .filter_map(|_| Sampling::shuffle(remaining, &mut rng).draw(needed).ok())
+                .collect();
+            (cases, false)
+        }
+    }
+
+    /// Same as `Cards::shuffle()`, only with the RNG that you hand it.
+    #[must_use]
+    pub fn shuffle<R: Rng>(cards: &Cards, rng: &mut R) -> Cards {
+        let mut vec: Vec<Card> = cards.iter().copied().collect();
+        vec.shuffle(rng);
+        Cards::from(vec)
+    }
+}
+
+impl Default for Sampling {
+    fn default() -> Self {
+        Sampling::new(Sampling::SAMPLES)
+    }
+}
+
+#[cfg(test)]
+#[allow(non_snake_case)]
+mod analysis__sampling_tests {
+    use super::*;
+    use std::str::FromStr;
+
+    #[test]
+    fn count() {
+        assert_eq!(1, Sampling::count(44, 0));
+        assert_eq!(44, Sampling::count(44, 1));
+        assert_eq!(946, Sampling::count(44, 2));
+        assert_eq!(1_086_008, Sampling::count(44, 5));
+        assert_eq!(0, Sampling::count(4, 5));
+    }
+
+    #[test]
+    fn default() {
+        assert_eq!(Sampling::SAMPLES, Sampling::default().samples);
+        assert!(Sampling::default().seed.is_none());
+    }
+
+    #[test]
+    fn runouts__sampled() {
+        let remaining = Cards::deck();
+        let sampling = Sampling::new(100).with_seed(7);
+
+        let (cases, exhaustive) = sampling.runouts(&remaining, 5);
+
+        assert!(!exhaustive);
+        assert_eq!(100, cases.len());
+        assert_eq!(cases, sampling.runouts(&remaining, 5).0);
+        assert_ne!(cases, sampling.with_seed(8).runouts(&remaining, 5).0);
+    }
+
+    #[test]
+    fn shuffle() {
+        let cards = Cards::from_str("A♠ K♠ Q♠ J♠ T♠").unwrap();
+
+        let shuffled = Sampling::shuffle(&cards, &mut Sampling::new(1).with_seed(1).rng());
+
+        assert_eq!(5, shuffled.len());
+        assert_eq!(cards.clone().sort(), shuffled.sort());
+    }
+}
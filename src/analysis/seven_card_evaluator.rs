@@ -0,0 +1,421 @@
+use crate::analysis::eval::Eval;
+use crate::analysis::hand_rank::{HandRank, HandRankValue, NO_HAND_RANK_VALUE};
+use crate::arrays::HandRanker;
+use crate::arrays::five::Five;
+use crate::arrays::seven::Seven;
+use crate::bard::Bard;
+use crate::card::Card;
+use crate::rank::Rank;
+use crate::suit::Suit;
+use crate::{PKError, Pile};
+use std::collections::HashMap;
+
+/// Built on first use. Building it runs the classic 21 permutation scan once for every one of the
+/// 49,205 possible rank multisets of a seven card hand, so that we never have to do it again.
+pub static SEVEN_CARD_LOOKUP: std::sync::LazyLock<SevenCardLookup> = std::sync::LazyLock::new(SevenCardLookup::new);
+
+/// # Direct Seven Card Evaluation
+///
+/// Every equity calculation we do bottoms out in `HandRanker::hand_rank_value_and_hand()` for a
+/// `Seven`, which breaks the hand into all 21 possible `Five` card hands and scores each one
+/// against the Cactus Kev lookup tables. That's 21 evaluations to get one answer.
+///
+/// The `SevenCardEvaluator` gets the same answer in one step. The trick is that, as long as there
+/// isn't a flush, the only thing that matters about a seven card hand is how many of each `Rank`
+/// it holds. There are only 49,205 of those rank multisets, so we can score each one once and
+/// store the result keyed by the count of each rank. If a `Suit` shows up five or more times, the
+/// hand is a flush, and since there's no way to hold quads or a full house alongside a seven card
+/// flush, all we need is the best flush for the bits of those ranks, which is a 8,192 entry table.
+///
+/// The `Five` returned is exactly the one the permutation scan would have landed on, so this can
+/// be swapped in anywhere without changing results. `tests/heavy_tests.rs` has the exhaustive
+/// test over all 133,784,560 seven card hands proving it.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct SevenCardEvaluator;
+
+impl SevenCardEvaluator {
+    /// # Errors
+    ///
+    /// Returns `PKError::NotEnoughCards` or `PKError::TooManyCards` if the `Bard` doesn't have
+    /// exactly seven cards flagged.
+    pub fn from_bard(bard: Bard) -> Result<(HandRankValue, Five), PKError> {
+        Ok(SevenCardEvaluator::hand_rank_value_and_hand(&Seven::try_from(bard)?))
+    }
+
+    #[must_use]
+    pub fn eval(seven: &Seven) -> Eval {
+        let (hrv, five) = SevenCardEvaluator::hand_rank_value_and_hand(seven);
+        Eval::new(HandRank::from(hrv), five)
+    }
+
+    #[must_use]
+    pub fn hand_rank_value(seven: &Seven) -> HandRankValue {
+        if !seven.is_dealt() {
+            return NO_HAND_RANK_VALUE;
+        }
+        match SevenCardEvaluator::flush_suit(seven) {
+            Some(suit) => {
+                SEVEN_CARD_LOOKUP
+                    .flush(SevenCardEvaluator::suit_rank_bits(seven, suit))
+                    .0
+            }
+            None => SEVEN_CARD_LOOKUP.rank(SevenCardEvaluator::rank_key(seven)).0,
+        }
+    }
+
+    /// Returns the same `HandRankValue` and best `Five` as `Seven::hand_rank_value_and_hand()`.
+    ///
+    /// The permutation scan keeps the first of the 21 `Five` card hands with the best value. When
+    /// more than one set of cards ties for best, such as holding two aces for an ace high straight,
+    /// the first one it finds is the one that takes the lowest position for each rank, so that's
+    /// the one we pick here.
+    #[must_use]
+    pub fn hand_rank_value_and_hand(seven: &Seven) -> (HandRankValue, Five) {
+        if !seven.is_dealt() {
+            return (NO_HAND_RANK_VALUE, Five::default());
+        }
+
+        let cards = seven.to_arr();
+        let mut best = [Card::BLANK; 5];
+        let mut i = 0;
+
+        let hrv = if let Some(suit) = SevenCardEvaluator::flush_suit(seven) {
+            let (hrv, bits) = SEVEN_CARD_LOOKUP.flush(SevenCardEvaluator::suit_rank_bits(seven, suit));
+            for card in cards {
+                if SevenCardEvaluator::suit_bit(card) == suit && bits & SevenCardEvaluator::rank_bit(card) != 0 {
+                    best[i] = card;
+                    i += 1;
+                }
+            }
+            hrv
+        } else {
+            let (hrv, mut counts) = SEVEN_CARD_LOOKUP.rank(SevenCardEvaluator::rank_key(seven));
+            for card in cards {
+                let rank = SevenCardEvaluator::rank_index(card);
+                if counts[rank] > 0 {
+                    counts[rank] -= 1;
+                    best[i] = card;
+                    i += 1;
+                }
+            }
+            hrv
+        };
+
+        (hrv, Five::from(best).sort().clean())
+    }
+
+    /// Powers of five, one for each rank index. A rank can appear at most four times, so the sum
+    /// of these for each `Card` is a unique key for the hand's rank multiset.
+    const RANK_KEYS: [u32; 13] = [
+        1,
+        5,
+        25,
+        125,
+        625,
+        3_125,
+        15_625,
+        78_125,
+        390_625,
+        1_953_125,
+        9_765_625,
+        48_828_125,
+        244_140_625,
+    ];
+
+    fn flush_suit(seven: &Seven) -> Option<u32> {
+        let mut counts = [0u8; 16];
+        for card in seven.to_arr() {
+            let suit = SevenCardEvaluator::suit_bit(card);
+            counts[suit as usize] += 1;
+            if counts[suit as usize] == 5 {
+                return Some(suit);
+            }
+        }
+        None
+    }
+
+    fn rank_bit(card: Card) -> u16 {
+        1 << SevenCardEvaluator::rank_index(card)
+    }
+
+    /// The `rrrr` nibble of the Cactus Kev `Card`, deuce=0 through ace=12.
+    fn rank_index(card: Card) -> usize {
+        ((card.as_u32() >> 8) & 0xF) as usize
+    }
+
+    fn rank_key(seven: &Seven) -> u32 {
+        seven
+            .to_arr()
+            .iter()
+            .map(|card| SevenCardEvaluator::RANK_KEYS[SevenCardEvaluator::rank_index(*card)])
+            .sum()
+    }
+
+    fn suit_bit(card: Card) -> u32 {
+        (card.as_u32() & Card::SUIT_FLAG_FILTER) >> Card::SUIT_FLAG_SHIFT
+    }
+
+    fn suit_rank_bits(seven: &Seven, suit: u32) -> u16 {
+        seven
+            .to_arr()
+            .iter()
+            .filter(|card| SevenCardEvaluator::suit_bit(**card) == suit)
+            .fold(0, |acc, card| acc | SevenCardEvaluator::rank_bit(*card))
+    }
+}
+
+/// The two tables behind the `SevenCardEvaluator`.
+///
+/// - `flushes` is indexed by the rank bits of the flush suit, and holds the best value along with
+///   the rank bits of the five cards that make it.
+/// - `ranks` is keyed by the rank multiset of the hand, and holds the best value along with how
+///   many of each rank make up the best `Five`.
+#[derive(Clone, Debug, Default)]
+pub struct SevenCardLookup {
+    flushes: Vec<(HandRankValue, u16)>,
+    ranks: HashMap<u32, (HandRankValue, [u8; 13])>,
+}
+
+impl SevenCardLookup {
+    #[must_use]
+    pub fn new() -> SevenCardLookup {
+        SevenCardLookup {
+            flushes: SevenCardLookup::generate_flushes(),
+            ranks: SevenCardLookup::generate_ranks(),
+        }
+    }
+
+    #[must_use]
+    pub fn flush(&self, rank_bits: u16) -> (HandRankValue, u16) {
+        self.flushes
+            .get(rank_bits as usize)
+            .copied()
+            .unwrap_or((NO_HAND_RANK_VALUE, 0))
+    }
+
+    #[must_use]
+    pub fn rank(&self, key: u32) -> (HandRankValue, [u8; 13]) {
+        self.ranks.get(&key).copied().unwrap_or((NO_HAND_RANK_VALUE, [0; 13]))
+    }
+
+    #[must_use]
+    pub fn ranks_len(&self) -> usize {
+        self.ranks.len()
+    }
+
+    /// For every combination of five or more ranks, the best flush is the best of the ones left
+    /// after dropping one rank at a time, until we're down to five ranks, where the Cactus Kev
+    /// `FLUSHES` table has the answer.
+    fn generate_flushes() -> Vec<(HandRankValue, u16)> {
+        let mut flushes = vec![(NO_HAND_RANK_VALUE, 0u16); 8192];
+
+        for bits in 0..8192u16 {
+            flushes[bits as usize] = match bits.count_ones() {
+                5 => (crate::lookups::flushes::FLUSHES[bits as usize], bits),
+                6 | 7 => {
+                    let mut best = (NO_HAND_RANK_VALUE, 0);
+                    for i in 0..13 {
+                        if bits & (1 << i) != 0 {
+                            let sub = flushes[(bits & !(1 << i)) as usize];
+                            if best.0 == NO_HAND_RANK_VALUE || sub.0 < best.0 {
+                                best = sub;
+                            }
+                        }
+                    }
+                    best
+                }
+                _ => (NO_HAND_RANK_VALUE, 0),
+            };
+        }
+
+        flushes
+    }
+
+    /// Walks every way of holding seven cards' worth of ranks, no more than four of any one.
+    /// Each multiset is dealt out so that no more than two cards share a `Suit`, which keeps
+    /// flushes out of the picture, and then scored with the same 21 permutations as the scan.
+    fn generate_ranks() -> HashMap<u32, (HandRankValue, [u8; 13])> {
+        let mut ranks = HashMap::new();
+        let mut counts = [0u8; 13];
+        SevenCardLookup::walk_ranks(0, 7, &mut counts, &mut ranks);
+        ranks
+    }
+
+    fn walk_ranks(
+        rank: usize,
+        remaining: u8,
+        counts: &mut [u8; 13],
+        ranks: &mut HashMap<u32, (HandRankValue, [u8; 13])>,
+    ) {
+        if remaining == 0 {
+            if let Some(entry) = SevenCardLookup::score_ranks(counts) {
+                ranks.insert(entry.0, entry.1);
+            }
+            return;
+        }
+        if rank == 13 {
+            return;
+        }
+        for count in 0..=remaining.min(4) {
+            counts[rank] = count;
+            SevenCardLookup::walk_ranks(rank + 1, remaining - count, counts, ranks);
+        }
+        counts[rank] = 0;
+    }
+
+    fn score_ranks(counts: &[u8; 13]) -> Option<(u32, (HandRankValue, [u8; 13]))> {
+        let mut cards = Vec::with_capacity(7);
+        let mut key = 0u32;
+
+        for (rank, count) in counts.iter().enumerate() {
+            for _ in 0..*count {
+                let suit = SevenCardLookup::SUITS[cards.len() % 4];
+                cards.push(Card::new(SevenCardLookup::RANKS[rank], suit));
+                key += SevenCardEvaluator::RANK_KEYS[rank];
+            }
+        }
+
+        let seven = Seven::try_from(cards).ok()?;
+        let mut hrv = NO_HAND_RANK_VALUE;
+        let mut best = [0u8; 13];
+
+        for perm in Seven::FIVE_CARD_PERMUTATIONS {
+            let five = seven.five_from_permutation(perm);
+            let value = SevenCardLookup::score_unsuited(&five);
+            if hrv == NO_HAND_RANK_VALUE || value < hrv {
+                hrv = value;
+                best = [0u8; 13];
+                for card in five.iter() {
+                    best[SevenCardEvaluator::rank_index(*card)] += 1;
+                }
+            }
+        }
+
+        Some((key, (hrv, best)))
+    }
+
+    /// The non flush half of `Five::hand_rank_value_and_hand()`, without the sorting, which is
+    /// most of the cost when building the table.
+    fn score_unsuited(five: &Five) -> HandRankValue {
+        match Five::unique_rank(five.or_rank_bits() as usize) {
+            0 => five.not_unique(),
+            unique => unique,
+        }
+    }
+
+    const RANKS: [Rank; 13] = [
+        Rank::DEUCE,
+        Rank::TREY,
+        Rank::FOUR,
+        Rank::FIVE,
+        Rank::SIX,
+        Rank::SEVEN,
+        Rank::EIGHT,
+        Rank::NINE,
+        Rank::TEN,
+        Rank::JACK,
+        Rank::QUEEN,
+        Rank::KING,
+        Rank::ACE,
+    ];
+
+    const SUITS: [Suit; 4] = [Suit::SPADES, Suit::HEARTS, Suit::DIAMONDS, Suit::CLUBS];
+}
+
+#[cfg(test)]
+#[allow(non_snake_case)]
+mod analysis__seven_card_evaluator_tests {
+    use super::*;
+    use crate::analysis::class::HandRankClass;
+    use crate::cards::Cards;
+    use rstest::rstest;
+    use std::str::FromStr;
+
+    #[test]
+    fn lookup__ranks_len() {
+        assert_eq!(49_205, SEVEN_CARD_LOOKUP.ranks_len());
+    }
+
+    #[test]
+    fn lookup__flush() {
+        // A♠ K♠ Q♠ J♠ T♠ 9♠ 8♠
+        let (hrv, bits) = SEVEN_CARD_LOOKUP.flush(0b1_1111_1100_0000);
+
+        assert_eq!(1, hrv);
+        assert_eq!(0b1_1111_0000_0000, bits);
+        assert_eq!((NO_HAND_RANK_VALUE, 0), SEVEN_CARD_LOOKUP.flush(0b1111));
+    }
+
+    #[rstest]
+    #[case("6♠ 6♥ 9♣ 6♦ 5♥ 5♠ 8♠", 271, "6♠ 6♥ 6♦ 5♠ 5♥")]
+    #[case("A♦ 6♠ 4♠ A♠ 5♦ 3♣ 2♠", 1608, "6♠ 5♦ 4♠ 3♣ 2♠")]
+    #[case("A♠ K♠ Q♠ J♠ T♠ 9♠ 8♠", 1, "A♠ K♠ Q♠ J♠ T♠")]
+    #[case("A♥ 2♠ 3♠ 4♠ 5♦ 9♠ K♦", 1609, "5♦ 4♠ 3♠ 2♠ A♥")]
+    #[case("A♠ A♥ K♠ Q♦ J♣ T♥ 2♣", 1600, "A♠ K♠ Q♦ J♣ T♥")]
+    #[case("A♥ A♠ K♠ Q♦ J♣ T♥ 2♣", 1600, "A♥ K♠ Q♦ J♣ T♥")]
+    #[case("K♠ K♥ K♦ K♣ Q♠ Q♥ Q♦", 24, "K♠ K♥ K♦ K♣ Q♠")]
+    fn hand_rank_value_and_hand(#[case] index: &str, #[case] hrv: HandRankValue, #[case] five: &str) {
+        let seven = Seven::from_str(index).unwrap();
+
+        let actual = SevenCardEvaluator::hand_rank_value_and_hand(&seven);
+
+        assert_eq!(hrv, actual.0);
+        assert_eq!(five, actual.1.to_string());
+        assert_eq!(seven.hand_rank_value_and_hand(), actual);
+        assert_eq!(hrv, SevenCardEvaluator::hand_rank_value(&seven));
+    }
+
+    #[test]
+    fn hand_rank_value_and_hand__blank() {
+        assert_eq!(
+            (NO_HAND_RANK_VALUE, Five::default()),
+            SevenCardEvaluator::hand_rank_value_and_hand(&Seven::default())
+        );
+        assert_eq!(
+            NO_HAND_RANK_VALUE,
+            SevenCardEvaluator::hand_rank_value(&Seven::default())
+        );
+    }
+
+    #[test]
+    fn eval() {
+        let seven = Seven::from_str("6♠ 6♥ 9♣ 6♦ 5♥ 5♠ 8♠").unwrap();
+
+        let eval = SevenCardEvaluator::eval(&seven);
+
+        assert_eq!(eval, Eval::from(seven));
+        assert_eq!(eval.hand, Eval::from(seven).hand);
+        assert_eq!(HandRankClass::SixesOverFives, eval.hand_rank.class);
+    }
+
+    #[test]
+    fn from_bard() {
+        let seven = Seven::from_str("6♠ 6♥ 9♣ 6♦ 5♥ 5♠ 8♠").unwrap();
+
+        let (hrv, five) = SevenCardEvaluator::from_bard(seven.bard()).unwrap();
+
+        assert_eq!(271, hrv);
+        assert_eq!("6♠ 6♥ 6♦ 5♠ 5♥", five.to_string());
+        assert_eq!(
+            PKError::NotEnoughCards,
+            SevenCardEvaluator::from_bard(Bard::ACE_SPADES).unwrap_err()
+        );
+    }
+
+    /// The exhaustive version of this is in `tests/heavy_tests.rs`. This one walks every seven
+    /// card hand out of a slice of the deck picked to hit flushes, straight flushes, wheels,
+    /// quads, and boats.
+    #[test]
+    fn matches_permutation_scan() {
+        let cards = Cards::from_str("A♠ K♠ Q♠ J♠ T♠ 5♠ 4♠ 3♠ 2♠ A♥ A♦ A♣ 5♥ 4♦ 3♣ T♥").unwrap();
+
+        for v in cards.combinations(7) {
+            let seven = Seven::try_from(v).unwrap();
+            assert_eq!(
+                seven.hand_rank_value_and_hand(),
+                SevenCardEvaluator::hand_rank_value_and_hand(&seven),
+                "{seven}"
+            );
+        }
+    }
+}
@@ -68,6 +68,18 @@ impl HUPResult {
         }
     }
 
+    /// `hero`'s share of the pot, with a draw counting as half. The table stores each matchup
+    /// once, from the point of view of whichever hand sorts higher, so if `hero` is the lower
+    /// one we have to flip it first.
+    #[must_use]
+    pub fn equity_for(&self, hero: &Two) -> Option<f64> {
+        if Bard::from(*hero) == self.higher {
+            self.odds.equity()
+        } else {
+            self.flip_mode().odds.equity()
+        }
+    }
+
     /// # THIS IS WRONG
     ///
     /// I'm doing it in the inverse order. I need to do this from `SortedHeadsUp` and pass in
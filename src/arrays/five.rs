@@ -209,19 +209,27 @@ impl HandRanker for Five {
         (CaliforniaHandRank::from(*self), *self)
     }
 
-    fn hand_rank_value_and_hand(&self) -> (HandRankValue, Five) {
+    /// Skips the sort that comes with `hand_rank_value_and_hand()`. When you're comparing the
+    /// sixty possible hands in an Omaha showdown you only want the number.
+    fn hand_rank_value(&self) -> HandRankValue {
         if self.is_dealt() {
             let i = self.or_rank_bits() as usize;
-            let rank: u16 = if self.is_flush() {
+            if self.is_flush() {
                 crate::lookups::flushes::FLUSHES[i]
             } else {
-                let unique = Five::unique_rank(i);
-                match unique {
+                match Five::unique_rank(i) {
                     0 => self.not_unique(),
-                    _ => unique,
+                    unique => unique,
                 }
-            };
-            (rank, self.sort().clean())
+            }
+        } else {
+            NO_HAND_RANK_VALUE
+        }
+    }
+
+    fn hand_rank_value_and_hand(&self) -> (HandRankValue, Five) {
+        if self.is_dealt() {
+            (self.hand_rank_value(), self.sort().clean())
         } else {
             (NO_HAND_RANK_VALUE, Five::default())
         }
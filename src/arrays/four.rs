@@ -1,9 +1,8 @@
 use crate::analysis::eval::Eval;
-use crate::arrays::HandRanker;
-use crate::arrays::seven::Seven;
 use crate::arrays::three::Three;
 use crate::arrays::two::Two;
 use crate::cards::Cards;
+use crate::games::omaha::OmahaHigh;
 use crate::play::board::Board;
 use crate::{Card, PKError, Pile, TheNuts};
 use std::fmt;
@@ -58,25 +57,14 @@ impl Four {
     }
     //endregion
 
-    /// There's a serious flaw in this logic. Omaha requires that you use exactly two of cards
-    /// from the four in your hand, unlike NLHE where you can play with board. The valid, tested
-    /// logic is over in `OmahaHigh::eval()`. This is here for historical reasons and should be
+    /// There used to be a serious flaw in this logic. Omaha requires that you use exactly two of
+    /// cards from the four in your hand, unlike NLHE where you can play with board. It now hands
+    /// off to the valid, tested logic over in `OmahaHigh::eval()`. This is here for historical
+    /// reasons and should be removed.
     #[must_use]
     #[deprecated]
     pub fn omaha_high(&self, board: &Board) -> Eval {
-        let mut best_eval = Eval::default();
-
-        for perm in &Self::OMAHA_PERMUTATIONS {
-            let two = Two::from([self.0[perm[0]], self.0[perm[1]]]);
-            let seven = Seven::from_case_and_board(&two, board);
-
-            let eval = seven.eval();
-            if eval > best_eval {
-                best_eval = eval;
-            }
-        }
-
-        best_eval
+        OmahaHigh::from(*self).eval(board)
     }
 
     #[must_use]
@@ -195,6 +183,7 @@ impl TryFrom<Cards> for Four {
 #[allow(non_snake_case)]
 mod arrays__four_tests {
     use super::*;
+    use crate::analysis::class::HandRankClass;
 
     #[test]
     fn from_twos() {
@@ -211,28 +200,38 @@ mod arrays__four_tests {
 
         assert_eq!(expected, actual);
     }
-    // Test for flawed method
-    // #[test]
-    // fn omaha_high() {
-    //     let four = Four::from([
-    //         Card::ACE_DIAMONDS,
-    //         Card::ACE_CLUBS,
-    //         Card::KING_DIAMONDS,
-    //         Card::KING_CLUBS,
-    //     ]);
-    //     let board = Board::from([
-    //         Card::QUEEN_DIAMONDS,
-    //         Card::QUEEN_HEARTS,
-    //         Card::JACK_DIAMONDS,
-    //         Card::TEN_CLUBS,
-    //         Card::TEN_DIAMONDS,
-    //     ]);
-    //     let expected = Class::RoyalFlush;
-    //
-    //     let actual = four.omaha_high(&board).hand_rank.class;
-    //
-    //     assert_eq!(expected, actual);
-    // }
+    #[test]
+    #[allow(deprecated)]
+    fn omaha_high() {
+        let four = Four::from([
+            Card::ACE_DIAMONDS,
+            Card::ACE_CLUBS,
+            Card::KING_DIAMONDS,
+            Card::KING_CLUBS,
+        ]);
+        let board = Board::from([
+            Card::QUEEN_DIAMONDS,
+            Card::QUEEN_HEARTS,
+            Card::JACK_DIAMONDS,
+            Card::TEN_CLUBS,
+            Card::TEN_DIAMONDS,
+        ]);
+
+        let actual = four.omaha_high(&board).hand_rank.class;
+
+        assert_eq!(HandRankClass::RoyalFlush, actual);
+    }
+
+    #[test]
+    #[allow(deprecated)]
+    fn omaha_high__four_flush_board() {
+        let four = Four::from_str("A♥ Q♠ 7♦ 7♣").unwrap();
+        let board = Board::from_str("K♠ J♠ 8♠ 2♠ 7♥").unwrap();
+
+        let actual = four.omaha_high(&board).hand_rank.class;
+
+        assert_eq!(HandRankClass::ThreeSevens, actual);
+    }
 
     #[test]
     fn from__array() {
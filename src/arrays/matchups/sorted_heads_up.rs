@@ -1,4 +1,4 @@
-use crate::analysis::store::bcm::binary_card_map::BC_RANK_HASHMAP;
+use crate::analysis::seven_card_evaluator::SevenCardEvaluator;
 use crate::analysis::store::db::hup::HUPResult;
 use crate::analysis::the_nuts::TheNuts;
 use crate::arrays::five::Five;
@@ -704,6 +704,10 @@ impl SortedHeadsUp {
     ///
     /// Test moved to `tests/heavy_tests.rs`.
     ///
+    /// This used to look up every `Seven` in the `BC_RANK_HASHMAP`, which meant loading the 4GB
+    /// binary card map before we could do anything. The `SevenCardEvaluator` gets us the same
+    /// `HandRankValue` without the file.
+    ///
     /// # Errors
     ///
     /// Throws `PKError` when unable to cast cards correctly.
@@ -713,10 +717,10 @@ impl SortedHeadsUp {
         for combo in self.remaining().combinations(5) {
             let (high7, low7) = self.sevens(Five::try_from(combo)?)?;
 
-            let high_rank = BC_RANK_HASHMAP.get(&high7.bard()).ok_or(PKError::InvalidHand)?;
-            let low_rank = BC_RANK_HASHMAP.get(&low7.bard()).ok_or(PKError::InvalidHand)?;
+            let high_rank = SevenCardEvaluator::hand_rank_value(&high7);
+            let low_rank = SevenCardEvaluator::hand_rank_value(&low7);
 
-            match high_rank.rank.cmp(&low_rank.rank) {
+            match high_rank.cmp(&low_rank) {
                 Ordering::Less => wins.add(Win::FIRST),
                 Ordering::Greater => wins.add(Win::SECOND),
                 Ordering::Equal => wins.add(Win::FIRST | Win::SECOND),
@@ -3,6 +3,7 @@ use crate::arrays::HandRanker;
 use crate::arrays::five::Five;
 use crate::arrays::three::Three;
 use crate::arrays::two::Two;
+use crate::bard::Bard;
 use crate::card::Card;
 use crate::cards::Cards;
 use crate::games::razz::california::{CaliforniaHandRank, CaliforniaHandRankValue, NO_RAZZ_HAND_RANK_VALUE};
@@ -152,20 +153,25 @@ impl FromStr for Seven {
 
 impl HandRanker for Seven {
     fn razz_hand_rank_and_hand(&self) -> (CaliforniaHandRank, Five) {
+        let mut best_hr = CaliforniaHandRank::Unknown;
         let mut best_hrv: CaliforniaHandRankValue = NO_RAZZ_HAND_RANK_VALUE;
         let mut best_hand = Five::default();
 
         for perm in Seven::FIVE_CARD_PERMUTATIONS {
             let hand = self.five_from_permutation(perm);
-            let hrv = CaliforniaHandRank::from(hand).get_hand_rank_value();
+            let hr = CaliforniaHandRank::from(hand);
+            let hrv = hr.get_hand_rank_value();
 
             if (best_hrv == 0) || hrv != 0 && hrv < best_hrv {
+                best_hr = hr;
                 best_hrv = hrv;
                 best_hand = hand;
             }
         }
 
-        (CaliforniaHandRank::from(best_hrv), best_hand.sort())
+        // Holding on to the `CaliforniaHandRank` itself, rather than rebuilding it from the
+        // value at the end, saves walking the enum's iterator on every call.
+        (best_hr, best_hand.sort())
     }
 
     fn hand_rank_value_and_hand(&self) -> (HandRankValue, Five) {
@@ -233,6 +239,14 @@ impl Pile for Seven {
     }
 }
 
+impl TryFrom<Bard> for Seven {
+    type Error = PKError;
+
+    fn try_from(bard: Bard) -> Result<Self, Self::Error> {
+        Seven::try_from(Cards::from(bard))
+    }
+}
+
 impl TryFrom<Cards> for Seven {
     type Error = PKError;
 
@@ -153,6 +153,30 @@ impl BoxedCards {
         &self.0
     }
 
+    /// Swaps out a card for a new one, in the same slot. This is what happens when you draw.
+    ///
+    /// ```
+    /// use pkcore::prelude::*;
+    ///
+    /// let mut boxed_cards = BoxedCards::from_str("T♠ 2♠ 8♣").unwrap();
+    /// boxed_cards.replace(Card::DEUCE_SPADES, Card::ACE_DIAMONDS).unwrap();
+    ///
+    /// assert_eq!("T♠ A♦ 8♣", boxed_cards.to_string());
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// `PKError::CardNotFound` if the card being replaced isn't there.
+    pub fn replace(&mut self, old: Card, new: Card) -> Result<(), PKError> {
+        match self.0.iter_mut().find(|slot| **slot == old) {
+            Some(slot) => {
+                *slot = new;
+                Ok(())
+            }
+            None => Err(PKError::CardNotFound),
+        }
+    }
+
     pub fn take(&mut self) -> Box<[Card]> {
         let cards = std::mem::take(&mut self.0);
         self.0 = vec![Card::BLANK; cards.len()].into_boxed_slice();
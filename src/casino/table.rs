@@ -58,6 +58,20 @@ impl Table {
         Seats::new(seats)
     }
 
+    /// Sets up empty seats with the right number of blank card slots for the `GameType`, so
+    /// that `deal_cards_to_seats()` deals four cards a player for PLO, five for PLO5 and Big O,
+    /// and six for PLO6.
+    ///
+    /// # Errors
+    ///
+    /// `PKError::TooManyHands` if there aren't enough cards in the deck to deal everyone in.
+    pub fn generate_seats_for(game: GameType, count: u8) -> Result<Seats, PKError> {
+        if count > game.max_players() {
+            return Err(PKError::TooManyHands);
+        }
+        Ok(Table::generate_seats(count, game.cards_per_player()))
+    }
+
     #[must_use]
     pub fn nlh_primed(seats: Seats, dealt: &CardsCell, forced_bets: ForcedBets) -> Self {
         let table = Table::nlh_from_seats(seats, forced_bets);
@@ -65,11 +79,18 @@ impl Table {
         table
     }
 
+    #[must_use]
+    pub fn nlh_from_seats(seats: Seats, forced: ForcedBets) -> Self {
+        Table::from_seats(GameType::NoLimitHoldem, seats, forced)
+    }
+
+    /// Same as `nlh_from_seats()` but for any `GameType`.
+    ///
     /// # Panics
     ///
     /// This will panic if the number of seats exceeds `u8::MAX`, which shouldn't be possible.
     #[must_use]
-    pub fn nlh_from_seats(seats: Seats, forced: ForcedBets) -> Self {
+    pub fn from_seats(game: GameType, seats: Seats, forced: ForcedBets) -> Self {
         log::info!("Generating table with {} seats passed in", seats.size());
 
         let event_log = TableLog::default();
@@ -77,7 +98,7 @@ impl Table {
         let uuid = Uuid::new_v4();
         event_log.log(TableAction::TableOpen(uuid));
 
-        let mut deck = deck_cell!();
+        let mut deck = game.get_deck();
 
         for seat in seats.borrow_all() {
             if !seat.borrow().is_empty() {
@@ -105,8 +126,8 @@ impl Table {
 
         Table {
             id: uuid,
-            name: "No Limit Hold'em Table".to_string(),
-            game: GameType::NoLimitHoldem,
+            name: format!("{game} Table"),
+            game,
             forced,
             phase: GamePhase::NewHand.into(),
             seats,
@@ -873,7 +894,7 @@ impl Default for Table {
             forced: ForcedBets::new(50, 100),
             seats,
             button: BintCell::new(player_count),
-            deck: deck_cell!(),
+            deck: GameType::NoLimitHoldem.get_deck(),
             board: CardsCell::default(),
             muck: CardsCell::default(),
             pot: Stack::default(),
@@ -905,6 +926,7 @@ mod casino__table_tests {
     use super::*;
     use crate::cards::Cards;
     use crate::casino::table::event::TableAction;
+    use crate::games::omaha::{FiveCardOmaha, SixCardOmaha};
     use crate::prelude::*;
     use crate::util::data::TestData;
     use std::borrow::Borrow;
@@ -963,6 +985,60 @@ mod casino__table_tests {
         assert!(table.pot.is_empty());
     }
 
+    #[test]
+    fn from_seats__plo6() {
+        let seats = Table::generate_seats_for(GameType::PLO6, 6).unwrap();
+        let table = Table::from_seats(GameType::PLO6, seats, ForcedBets::new(50, 100));
+
+        table.deal_cards_to_seats().unwrap();
+
+        assert_eq!("Six Card Pot Limit Omaha Table", table.name);
+        assert_eq!(16, table.deck.len());
+        assert_eq!(41, table.cards_in_play());
+        for i in 0..6 {
+            let seat = table.get_seat(i).unwrap();
+            assert!(seat.cards.is_dealt());
+            assert!(SixCardOmaha::try_from(seat.cards.cards()).is_ok());
+        }
+    }
+
+    #[test]
+    fn from_seats__short_deck() {
+        let seats = Table::generate_seats_for(GameType::ShortDeck, 6).unwrap();
+        let table = Table::from_seats(GameType::ShortDeck, seats, ForcedBets::new(50, 100));
+
+        assert_eq!(36, table.deck.len());
+        assert!(table.deck.cards().iter().all(|card| card.get_rank() >= Rank::SIX));
+
+        table.deal_cards_to_seats().unwrap();
+        table.reset();
+
+        assert_eq!(36, table.deck.len());
+        assert_eq!(table.event_log.entries().last(), Some(&TableAction::DeckPassesAudit));
+    }
+
+    #[test]
+    fn from_seats__big_o() {
+        let seats = Table::generate_seats_for(GameType::BigO, 9).unwrap();
+        let table = Table::from_seats(GameType::BigO, seats, ForcedBets::new(50, 100));
+
+        table.deal_cards_to_seats().unwrap();
+
+        assert_eq!(7, table.deck.len());
+        for i in 0..9 {
+            assert!(FiveCardOmaha::try_from(table.get_seat(i).unwrap().cards.cards()).is_ok());
+        }
+    }
+
+    #[test]
+    fn generate_seats_for() {
+        assert_eq!(5, Table::generate_seats_for(GameType::PLO5, 5).unwrap().size());
+        assert_eq!(
+            PKError::TooManyHands,
+            Table::generate_seats_for(GameType::PLO6, 8).unwrap_err()
+        );
+    }
+
     #[test]
     fn default() {
         let table = Table::default();
@@ -20,13 +20,19 @@ pub enum TableAction {
     ForcedBet(u8, usize),
     ForcedBetSmallBlind(u8, usize),
     ForcedBetBigBlind(u8, usize),
+    ForcedBetBringIn(u8, usize),
     BetAnteForced(u8, usize),
     DealingXCards(u8),
     Dealt(u8, Bard),
     DealtFlop(Bard),
     DealtTurn(Bard),
     DealtRiver(Bard),
+    DealtCommonCard(Bard),
     DealtPlayers,
+    DrawRound(u8),
+    Draws(u8, usize),
+    StandsPat(u8),
+    ReshuffleDiscards(usize),
     ForceDealt(u8, Bard),
     BringItIn(usize),
     ActionTo(u8),
@@ -54,6 +60,7 @@ impl TableAction {
         match self {
             TableAction::ForcedBetSmallBlind(_, amount) => format!("{name} posts {amount} small blind"),
             TableAction::ForcedBetBigBlind(_, amount) => format!("{name} posts {amount} big blind"),
+            TableAction::ForcedBetBringIn(_, amount) => format!("{name} brings it in for {amount}"),
             TableAction::BringItIn(amount) => format!("Brings in {amount}"),
             TableAction::Bet(_, amount) => format!("{name} bets {amount}"),
             TableAction::Call(_, amount) => format!("{name} calls {amount}"),
@@ -63,6 +70,8 @@ impl TableAction {
             TableAction::Check(_) => format!("{name} checks"),
             TableAction::Dealt(_, bard) => format!("{name} dealt {}", Cards::from(*bard)),
             TableAction::DealtFlop(bard) => format!("Flop is {}", Cards::from(*bard)),
+            TableAction::Draws(_, count) => format!("{name} draws {count}"),
+            TableAction::StandsPat(_) => format!("{name} stands pat"),
             _ => self.to_string(),
         }
     }
@@ -81,9 +90,12 @@ impl TableAction {
             | TableAction::MoveButton(seat)
             | TableAction::ForcedBetSmallBlind(seat, _)
             | TableAction::ForcedBetBigBlind(seat, _)
+            | TableAction::ForcedBetBringIn(seat, _)
             | TableAction::BetAnteForced(seat, _)
             | TableAction::Dealt(seat, _)
             | TableAction::ForceDealt(seat, _)
+            | TableAction::Draws(seat, _)
+            | TableAction::StandsPat(seat)
             | TableAction::ActionTo(seat)
             | TableAction::Check(seat)
             | TableAction::Bet(seat, _)
@@ -145,6 +157,9 @@ impl Display for TableAction {
             TableAction::ForcedBetBigBlind(seat, amount) => {
                 write!(f, "Seat {seat} puts in Big Blind of {amount}")
             }
+            TableAction::ForcedBetBringIn(seat, amount) => {
+                write!(f, "Seat {seat} brings it in for {amount}")
+            }
             TableAction::BetAnteForced(seat, amount) => {
                 write!(f, "Seat {seat} Antes {amount}")
             }
@@ -153,7 +168,12 @@ impl Display for TableAction {
             TableAction::DealtFlop(cards) => write!(f, "Flop is {}", Cards::from(*cards)),
             TableAction::DealtTurn(cards) => write!(f, "Turn is {}", Cards::from(*cards)),
             TableAction::DealtRiver(cards) => write!(f, "River is {}", Cards::from(*cards)),
+            TableAction::DealtCommonCard(cards) => write!(f, "Common card is {}", Cards::from(*cards)),
             TableAction::DealtPlayers => write!(f, "Dealt Players"),
+            TableAction::DrawRound(round) => write!(f, "Draw #{round}"),
+            TableAction::Draws(seat, count) => write!(f, "Seat {seat} draws {count}"),
+            TableAction::StandsPat(seat) => write!(f, "Seat {seat} stands pat"),
+            TableAction::ReshuffleDiscards(count) => write!(f, "Reshuffle {count} discards into the deck"),
             TableAction::ForceDealt(seat, cards) => {
                 write!(f, "Seat {seat} is force-dealt {}", Cards::from(*cards))
             }
@@ -0,0 +1,292 @@
+use crate::arrays::four::Four;
+use crate::card::Card;
+use crate::cards::Cards;
+use crate::rank::Rank;
+use crate::{PKError, Pile};
+use std::cmp::Ordering;
+use std::fmt::{Display, Formatter};
+
+/// Like `LowballHandRankValue`, lower is better and the numbers aren't consecutive. The number of
+/// cards in the hand goes in the top bits, so every four card badugi sorts ahead of every three
+/// card hand, which sorts ahead of every two card hand, and so on. Under that come the ranks, high
+/// to low, with aces counting as one.
+pub type BadugiHandRankValue = u32;
+
+/// The hand isn't four cards.
+pub const NO_BADUGI_HAND_RANK_VALUE: BadugiHandRankValue = 0;
+
+/// Badugi is a four card lowball draw game where you want four cards of different ranks and
+/// different suits. That's a badugi. If you can't make one, your hand is the biggest collection
+/// of your cards that doesn't repeat a rank or suit, so `K♠ 7♠ 4♥ 2♦` plays as the three card
+/// `7-4-2`, and the `K♠` gets ignored. Fewer cards is always worse: the worst badugi, `K-Q-J-T`,
+/// beats the best three card hand, `3-2-A`.
+///
+/// Aces are low, and straights don't count, so the nuts is `4-3-2-A` of four different suits.
+///
+/// When there's more than one way to make the biggest hand, we take the lowest of them. `A♠ 2♠ 3♥ 4♦`
+/// could be `A-3-4` or `2-3-4`, and it plays as `4-3-A`.
+///
+/// ```
+/// use pkcore::arrays::four::Four;
+/// use pkcore::games::badugi::BadugiRanker;
+/// use std::str::FromStr;
+///
+/// let badugi = Four::from_str("K♠ Q♥ J♦ T♣").unwrap().badugi_eval();
+/// let three_card = Four::from_str("A♠ 2♥ 3♦ 3♣").unwrap().badugi_eval();
+///
+/// assert!(badugi > three_card);
+/// assert_eq!("K♠ Q♥ J♦ T♣ (4 card badugi)", badugi.to_string());
+/// assert_eq!("3♦ 2♥ A♠ (3 card)", three_card.to_string());
+/// ```
+#[derive(Clone, Debug, Default)]
+pub struct BadugiEval {
+    pub value: BadugiHandRankValue,
+    pub hand: Cards,
+}
+
+impl BadugiEval {
+    /// Returns the `BadugiHandRankValue` for cards that are already known to have no repeated rank
+    /// or suit.
+    fn value(cards: &[Card]) -> BadugiHandRankValue {
+        let mut ranks: Vec<u32> = cards
+            .iter()
+            .map(|card| BadugiEval::rank_value(card.get_rank()))
+            .collect();
+        ranks.sort_unstable_by(|a, b| b.cmp(a));
+
+        let size = u32::try_from(cards.len()).unwrap_or(4);
+        let ranks = ranks.iter().fold(0, |acc, rank| (acc << 4) | rank);
+
+        ((4 - size) << 16) | ranks
+    }
+
+    fn rank_value(rank: Rank) -> u32 {
+        match rank {
+            Rank::ACE => 1,
+            _ => rank as u32,
+        }
+    }
+
+    fn no_repeats(cards: &[Card]) -> bool {
+        cards.iter().enumerate().all(|(i, a)| {
+            cards[i + 1..]
+                .iter()
+                .all(|b| a.get_rank() != b.get_rank() && a.get_suit() != b.get_suit())
+        })
+    }
+
+    /// Tries every one of the fifteen subsets of the four cards and keeps the best.
+    fn best(four: &Four) -> BadugiEval {
+        if !four.is_dealt() {
+            return BadugiEval::default();
+        }
+
+        let cards = four.to_arr();
+        let mut best: Option<(BadugiHandRankValue, Vec<Card>)> = None;
+        for mask in 1..16_usize {
+            let subset: Vec<Card> = (0..4).filter(|i| mask & (1 << i) != 0).map(|i| cards[i]).collect();
+            if !BadugiEval::no_repeats(&subset) {
+                continue;
+            }
+            let value = BadugiEval::value(&subset);
+            if best.as_ref().is_none_or(|(best_value, _)| value < *best_value) {
+                best = Some((value, subset));
+            }
+        }
+
+        match best {
+            Some((value, mut subset)) => {
+                subset.sort_by(|a, b| BadugiEval::rank_value(b.get_rank()).cmp(&BadugiEval::rank_value(a.get_rank())));
+                BadugiEval {
+                    value,
+                    hand: Cards::from(subset),
+                }
+            }
+            None => BadugiEval::default(),
+        }
+    }
+
+    /// How many cards play. Four means it's a badugi.
+    #[must_use]
+    pub fn size(&self) -> usize {
+        self.hand.len()
+    }
+
+    #[must_use]
+    pub fn is_badugi(&self) -> bool {
+        self.size() == 4
+    }
+}
+
+impl Display for BadugiEval {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        if self.is_badugi() {
+            write!(f, "{} (4 card badugi)", self.hand)
+        } else {
+            write!(f, "{} ({} card)", self.hand, self.size())
+        }
+    }
+}
+
+/// Like `Eval`, the better hand is the greater one, which for Badugi means the lower value.
+/// Anything without a value is worse than everything else.
+impl Ord for BadugiEval {
+    fn cmp(&self, other: &Self) -> Ordering {
+        match (self.value, other.value) {
+            (NO_BADUGI_HAND_RANK_VALUE, NO_BADUGI_HAND_RANK_VALUE) => Ordering::Equal,
+            (NO_BADUGI_HAND_RANK_VALUE, _) => Ordering::Less,
+            (_, NO_BADUGI_HAND_RANK_VALUE) => Ordering::Greater,
+            (mine, theirs) => theirs.cmp(&mine),
+        }
+    }
+}
+
+impl PartialEq for BadugiEval {
+    fn eq(&self, other: &Self) -> bool {
+        self.value == other.value
+    }
+}
+
+impl Eq for BadugiEval {}
+
+impl PartialOrd for BadugiEval {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+/// The Badugi version of `HandRanker`.
+pub trait BadugiRanker {
+    fn badugi_eval(&self) -> BadugiEval;
+
+    fn badugi_hand_rank_value(&self) -> BadugiHandRankValue {
+        self.badugi_eval().value
+    }
+}
+
+impl BadugiRanker for Four {
+    fn badugi_eval(&self) -> BadugiEval {
+        BadugiEval::best(self)
+    }
+}
+
+/// Has to be exactly four cards. Anything else gets you `NO_BADUGI_HAND_RANK_VALUE`.
+impl BadugiRanker for Cards {
+    fn badugi_eval(&self) -> BadugiEval {
+        match Four::try_from(self.clone()) {
+            Ok(four) => four.badugi_eval(),
+            Err(_) => BadugiEval::default(),
+        }
+    }
+}
+
+impl TryFrom<&Cards> for BadugiEval {
+    type Error = PKError;
+
+    fn try_from(cards: &Cards) -> Result<Self, Self::Error> {
+        Ok(Four::try_from(cards.clone())?.badugi_eval())
+    }
+}
+
+#[cfg(test)]
+#[allow(non_snake_case)]
+mod games__badugi_tests {
+    use super::*;
+    use rstest::rstest;
+    use std::collections::HashSet;
+    use std::str::FromStr;
+
+    fn eval(s: &str) -> BadugiEval {
+        Four::from_str(s).unwrap().badugi_eval()
+    }
+
+    #[rstest]
+    #[case("4♠ 3♥ 2♦ A♣", "4♠ 3♥ 2♦ A♣", 0x4321)]
+    #[case("K♠ Q♥ J♦ T♣", "K♠ Q♥ J♦ T♣", 0xDCBA)]
+    #[case("K♠ 7♠ 4♥ 2♦", "7♠ 4♥ 2♦", 0x1_0742)]
+    #[case("A♠ 2♠ 3♥ 4♦", "4♦ 3♥ A♠", 0x1_0431)]
+    #[case("A♠ A♥ 2♠ 2♥", "2♠ A♥", 0x2_0021)]
+    #[case("2♠ 2♥ 2♦ 2♣", "2♠", 0x3_0002)]
+    #[case("K♠ 5♠ 3♠ A♠", "A♠", 0x3_0001)]
+    fn badugi_eval(#[case] four: &str, #[case] hand: &str, #[case] value: BadugiHandRankValue) {
+        let eval = eval(four);
+
+        assert_eq!(hand, eval.hand.to_string());
+        assert_eq!(value, eval.value);
+    }
+
+    #[rstest]
+    #[case("4♠ 3♥ 2♦ A♣", "5♠ 3♥ 2♦ A♣")]
+    #[case("8♠ 6♥ 5♦ 4♣", "8♠ 7♥ 2♦ A♣")]
+    #[case("K♠ Q♥ J♦ T♣", "3♠ 2♥ A♦ A♣")]
+    #[case("K♠ Q♥ J♦ J♣", "2♠ A♥ A♦ A♣")]
+    #[case("A♠ A♥ 2♠ 2♥", "A♠ 2♠ 3♠ 4♠")]
+    fn ordering(#[case] higher: &str, #[case] lower: &str) {
+        assert!(eval(higher) > eval(lower), "{higher} should beat {lower}");
+    }
+
+    #[test]
+    fn ordering__ties_across_suits() {
+        assert_eq!(Ordering::Equal, eval("4♠ 3♥ 2♦ A♣").cmp(&eval("4♣ 3♦ 2♥ A♠")));
+    }
+
+    #[test]
+    fn badugi_eval__not_dealt() {
+        assert_eq!(BadugiEval::default(), Four::default().badugi_eval());
+        assert!(eval("K♠ Q♥ J♦ T♣") > BadugiEval::default());
+    }
+
+    #[test]
+    fn badugi_eval__cards() {
+        assert_eq!(0x7543, Cards::from_str("7♠ 5♥ 4♦ 3♣").unwrap().badugi_hand_rank_value());
+        assert_eq!(
+            NO_BADUGI_HAND_RANK_VALUE,
+            Cards::from_str("7♠ 5♥ 4♦").unwrap().badugi_hand_rank_value()
+        );
+        assert_eq!(
+            PKError::TooManyCards,
+            BadugiEval::try_from(&Cards::from_str("7♠ 5♥ 4♦ 3♣ 2♠").unwrap()).unwrap_err()
+        );
+    }
+
+    #[test]
+    fn display() {
+        assert_eq!("2♠ A♥ (2 card)", eval("A♠ A♥ 2♠ 2♥").to_string());
+        assert_eq!("4♠ 3♥ 2♦ A♣ (4 card badugi)", eval("4♠ 3♥ 2♦ A♣").to_string());
+    }
+
+    /// Every four card hand out of the deck. Each one plays at least one card, never repeats a rank
+    /// or suit, and the number of cards matches the top bits of the value.
+    ///
+    /// | Cards | Hands   | Distinct |
+    /// |-------|---------|----------|
+    /// | 4     | 17,160  | 715      |
+    /// | 3     | 154,440 | 286      |
+    /// | 2     | 96,252  | 78       |
+    /// | 1     | 2,873   | 13       |
+    #[test]
+    fn badugi_eval__every_hand() {
+        let mut counts = [0_usize; 5];
+        let mut distinct = HashSet::new();
+        let mut hands = 0;
+
+        for cards in Cards::deck().combinations(4) {
+            let four = Four::from(cards);
+            let eval = four.badugi_eval();
+            let hand: Vec<Card> = eval.hand.iter().copied().collect();
+
+            assert!(BadugiEval::no_repeats(&hand), "{four}");
+            assert!(hand.iter().all(|card| four.contains(card)), "{four}");
+            assert_eq!(4 - eval.size(), (eval.value >> 16) as usize, "{four}");
+
+            counts[eval.size()] += 1;
+            distinct.insert(eval.value);
+            hands += 1;
+        }
+
+        assert_eq!(270_725, hands);
+        assert_eq!([0, 2_873, 96_252, 154_440, 17_160], counts);
+        assert_eq!(1_092, distinct.len());
+        assert!(!distinct.contains(&NO_BADUGI_HAND_RANK_VALUE));
+    }
+}
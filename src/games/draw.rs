@@ -0,0 +1,516 @@
+use crate::arrays::HandRanker;
+use crate::arrays::five::Five;
+use crate::arrays::four::Four;
+use crate::arrays::sliced::BoxedCards;
+use crate::cards::Cards;
+use crate::cards_cell::CardsCell;
+use crate::casino::game::ForcedBets;
+use crate::casino::player::Player;
+use crate::casino::table::Table;
+use crate::casino::table::event::TableAction;
+use crate::casino::table::seat::Seat;
+use crate::casino::table::seats::Seats;
+use crate::games::badugi::BadugiRanker;
+use crate::games::lowball::{Lowball, LowballRanker};
+use crate::games::{GamePhase, GameType};
+use crate::{PKError, Pile};
+use std::cell::{Cell, RefCell};
+
+/// The draw games on top of our `Table`: 2-7 Triple Draw, Badugi and good old Five Card Draw.
+///
+/// Unlike hold'em and stud, nobody gets any new cards unless they ask for them. Between the
+/// betting rounds each player, starting to the left of the button, throws away as many cards as
+/// they want and gets the same number back from the deck. Triple Draw and Badugi have three
+/// draws, Five Card Draw only one.
+///
+/// Six players in Triple Draw can easily go through more than 52 cards. When the deck can't cover
+/// a player's draw, the discards in the muck are shuffled up and put under what's left. The
+/// player's own discards from this draw don't go in, since they haven't hit the muck yet.
+///
+/// Each draw is logged on the `Table` as a `TableAction::Draws` with how many cards the player
+/// took, or `TableAction::StandsPat` if they kept what they had.
+///
+/// ```
+/// use pkcore::games::GameType;
+/// use pkcore::games::draw::DrawPoker;
+/// use pkcore::prelude::*;
+///
+/// let players = vec![
+///     Player::new_with_chips("Billy".to_string(), 1_000),
+///     Player::new_with_chips("Stu".to_string(), 1_000),
+/// ];
+/// let draw = DrawPoker::new(GameType::TripleDraw, players, ForcedBets::new(10, 20)).unwrap();
+///
+/// draw.act_blinds().unwrap();
+/// draw.deal().unwrap();
+/// assert_eq!(1, draw.start_draw().unwrap());
+///
+/// let hand = draw.hand(1);
+/// let discards = Cards::from(vec![*hand.get_index(0).unwrap(), *hand.get_index(1).unwrap()]);
+/// let replacements = draw.act_draw(1, &discards).unwrap();
+///
+/// assert_eq!(2, replacements.len());
+/// assert_eq!(2, draw.table.muck.len());
+/// ```
+#[derive(Clone, Debug)]
+pub struct DrawPoker {
+    pub table: Table,
+    draw_round: Cell<u8>,
+    drawn: RefCell<Vec<u8>>,
+}
+
+impl DrawPoker {
+    /// # Errors
+    ///
+    /// * `PKError::InvalidGameType` if the `GameType` isn't a draw game.
+    /// * `PKError::NotEnoughHands` if there are fewer than two players.
+    /// * `PKError::TooManyHands` if there are more than the game allows.
+    pub fn new(game: GameType, players: Vec<Player>, forced: ForcedBets) -> Result<Self, PKError> {
+        if !game.is_draw() {
+            return Err(PKError::InvalidGameType);
+        }
+        if players.len() < 2 {
+            return Err(PKError::NotEnoughHands);
+        }
+        if players.len() > game.max_players() as usize {
+            return Err(PKError::TooManyHands);
+        }
+
+        let seats = players
+            .into_iter()
+            .map(|player| Seat::new_with_cards(player, BoxedCards::blanks(game.cards_per_player() as usize)))
+            .collect();
+
+        Ok(DrawPoker {
+            table: Table::from_seats(game, Seats::new(seats), forced),
+            draw_round: Cell::new(0),
+            drawn: RefCell::new(Vec::new()),
+        })
+    }
+
+    /// Same as `DrawPoker::new()` but with the deck stacked ahead of time, like
+    /// `Table::nlh_primed()`.
+    ///
+    /// # Errors
+    ///
+    /// See `DrawPoker::new()`.
+    pub fn primed(
+        game: GameType,
+        players: Vec<Player>,
+        forced: ForcedBets,
+        dealt: &CardsCell,
+    ) -> Result<Self, PKError> {
+        let draw = DrawPoker::new(game, players, forced)?;
+        draw.table.deck.0.swap(&dealt.0);
+        Ok(draw)
+    }
+
+    // region actions
+
+    /// # Errors
+    ///
+    /// See `Table::act_forced_bets()`.
+    pub fn act_blinds(&self) -> Result<(), PKError> {
+        self.table.act_forced_bets()
+    }
+
+    /// Throws away the `discards` and replaces them with cards off the top of the deck, in the
+    /// same spots. Returns the replacements. Drawing no cards is standing pat.
+    ///
+    /// # Errors
+    ///
+    /// * `PKError::InvalidTableAction` if no draw has been started.
+    /// * `PKError::PlayerOutOfHand` if the seat isn't in the hand.
+    /// * `PKError::DuplicateAction` if the player has already drawn this round.
+    /// * `PKError::CardNotFound` if they're trying to throw away a card they don't have.
+    /// * `PKError::NotEnoughCards` if even with the discards there aren't enough cards left.
+    pub fn act_draw(&self, seat_number: u8, discards: &Cards) -> Result<Cards, PKError> {
+        if self.draw_round() == 0 {
+            return Err(PKError::InvalidTableAction);
+        }
+        if !self.table.seats.is_seat_in_hand(seat_number) {
+            return Err(PKError::PlayerOutOfHand);
+        }
+        if self.drawn.borrow().contains(&seat_number) {
+            return Err(PKError::DuplicateAction);
+        }
+        let hand = self.hand(seat_number);
+        if discards.iter().any(|card| !hand.contains(card)) {
+            return Err(PKError::CardNotFound);
+        }
+
+        if self.table.deck.len() < discards.len() {
+            self.reshuffle_discards();
+        }
+        if self.table.deck.len() < discards.len() {
+            return Err(PKError::NotEnoughCards);
+        }
+
+        let mut replacements = Cards::default();
+        if let Some(mut seat) = self.table.get_seat_mut(seat_number) {
+            for discard in discards.iter() {
+                let card = self.table.deck.draw_one()?;
+                seat.cards.replace(*discard, card)?;
+                replacements.insert(card);
+            }
+        }
+        self.table.muck.insert_all(discards.clone());
+        self.drawn.borrow_mut().push(seat_number);
+
+        if discards.is_empty() {
+            self.table.event_log.log(TableAction::StandsPat(seat_number));
+        } else {
+            self.table
+                .event_log
+                .log(TableAction::Draws(seat_number, discards.len()));
+            self.table
+                .event_log
+                .log(TableAction::Dealt(seat_number, replacements.bard()));
+        }
+        Ok(replacements)
+    }
+
+    /// # Errors
+    ///
+    /// See `DrawPoker::act_draw()`.
+    pub fn act_stand_pat(&self, seat_number: u8) -> Result<Cards, PKError> {
+        self.act_draw(seat_number, &Cards::default())
+    }
+
+    // endregion
+
+    /// The size of a bet in the fixed limit games, Triple Draw and Badugi. It's the big blind
+    /// before the second draw, and double that after. Five Card Draw is played no limit, so
+    /// it's the smallest bet you can make.
+    #[must_use]
+    pub fn bet_size(&self) -> usize {
+        let big_blind = self.table.forced.big_blind;
+        if self.table.game != GameType::FiveCardDraw && self.draw_round() >= 2 {
+            big_blind * 2
+        } else {
+            big_blind
+        }
+    }
+
+    /// Deals everyone their starting hand, one card at a time, starting to the left of the
+    /// button.
+    ///
+    /// # Errors
+    ///
+    /// * `PKError::NotEnoughCards` if the deck runs out.
+    /// * `PKError::NoBlankSlots` if the hands have already been dealt.
+    pub fn deal(&self) -> Result<(), PKError> {
+        let in_hand = self.seats_in_hand();
+        for _ in 0..self.table.game.cards_per_player() {
+            for seat_number in &in_hand {
+                self.table.deal_card_to_seat(*seat_number)?;
+            }
+        }
+        self.table.set_phase(GamePhase::DealHoleCards);
+        self.table.event_log.log(TableAction::DealtPlayers);
+        Ok(())
+    }
+
+    /// How many draws have been started. Zero means we're still on the first betting round.
+    #[must_use]
+    pub fn draw_round(&self) -> u8 {
+        self.draw_round.get()
+    }
+
+    /// How many draws the game has left.
+    #[must_use]
+    pub fn draws_remaining(&self) -> u8 {
+        self.table.game.draws().saturating_sub(self.draw_round())
+    }
+
+    /// The player's cards.
+    #[must_use]
+    pub fn hand(&self, seat_number: u8) -> Cards {
+        match self.table.get_seat(seat_number) {
+            Some(seat) => seat.cards.cards(),
+            None => Cards::default(),
+        }
+    }
+
+    /// `true` if the player has already had their turn to draw this round.
+    #[must_use]
+    pub fn has_drawn(&self, seat_number: u8) -> bool {
+        self.drawn.borrow().contains(&seat_number)
+    }
+
+    /// Shuffles the muck and puts it under the deck. Returns how many cards that was.
+    pub fn reshuffle_discards(&self) -> usize {
+        let discards = self.table.muck.take();
+        let count = discards.len();
+        if count > 0 {
+            self.table.deck.insert_all(discards.shuffle());
+            self.table.event_log.log(TableAction::ReshuffleDiscards(count));
+        }
+        count
+    }
+
+    /// The seats in the hand, in drawing order, starting to the left of the button.
+    #[must_use]
+    pub fn seats_in_hand(&self) -> Vec<u8> {
+        let start = self.table.button.static_up_x(1).value;
+        self.table
+            .seats
+            .indices_from(start)
+            .filter_map(|i| u8::try_from(i).ok())
+            .filter(|seat_number| self.table.seats.is_seat_in_hand(*seat_number))
+            .collect()
+    }
+
+    /// Moves on to the next draw. Returns which draw it is.
+    ///
+    /// # Errors
+    ///
+    /// `PKError::AlreadyDealt` if the players have already had all of their draws.
+    pub fn start_draw(&self) -> Result<u8, PKError> {
+        if self.draws_remaining() == 0 {
+            return Err(PKError::AlreadyDealt);
+        }
+        let round = self.draw_round() + 1;
+        self.draw_round.set(round);
+        self.drawn.borrow_mut().clear();
+        self.table.event_log.log(TableAction::DrawRound(round));
+        Ok(round)
+    }
+
+    /// The seats with the best hand at showdown. More than one means a split pot.
+    ///
+    /// Triple Draw goes by `Lowball::DeuceToSeven`, Badugi by its `BadugiHandRankValue`, and Five
+    /// Card Draw by the regular Cactus Kev `HandRankValue`. Lower is better for all three.
+    ///
+    /// # Errors
+    ///
+    /// * `PKError::NotDealt` if the hands haven't been dealt.
+    /// * `PKError::InvalidGameType` if the `GameType` isn't a draw game. `new()` won't let that
+    ///   happen, but the table is `pub`, so somebody could swap it out from under us.
+    pub fn winners(&self) -> Result<Vec<u8>, PKError> {
+        let mut values = Vec::new();
+        for seat_number in self.seats_in_hand() {
+            let hand = self.hand(seat_number);
+            let value = match self.table.game {
+                GameType::Badugi => Four::try_from(hand)
+                    .map_err(|_| PKError::NotDealt)?
+                    .badugi_hand_rank_value(),
+                GameType::TripleDraw => Five::try_from(hand)
+                    .map_err(|_| PKError::NotDealt)?
+                    .lowball_hand_rank_value(Lowball::DeuceToSeven),
+                GameType::FiveCardDraw => {
+                    u32::from(Five::try_from(hand).map_err(|_| PKError::NotDealt)?.hand_rank_value())
+                }
+                _ => return Err(PKError::InvalidGameType),
+            };
+            values.push((seat_number, value));
+        }
+        let best = values.iter().map(|(_, value)| *value).min().unwrap_or_default();
+        Ok(values
+            .into_iter()
+            .filter(|(_, value)| *value == best)
+            .map(|(seat_number, _)| seat_number)
+            .collect())
+    }
+}
+
+#[cfg(test)]
+#[allow(non_snake_case)]
+mod games__draw_tests {
+    use super::*;
+    use std::str::FromStr;
+
+    /// The button starts on seat 0, so the cards go out to seats 1, 2, 0 and around again.
+    ///
+    /// | Seat | Dealt          | Draws             |
+    /// |------|----------------|-------------------|
+    /// | 1    | 7♠ 5♥ 4♦ 3♣ K♠ | K♠ for 2♦         |
+    /// | 2    | 8♠ 6♥ 4♣ 3♦ 2♠ | stands pat        |
+    /// | 0    | A♠ A♥ K♥ Q♦ J♣ | everything        |
+    const DEAL: &str = "7♠ 8♠ A♠ 5♥ 6♥ A♥ 4♦ 4♣ K♥ 3♣ 3♦ Q♦ K♠ 2♠ J♣ 2♦ 9♥ 9♦ 9♣ T♠ T♥";
+
+    fn players(count: usize) -> Vec<Player> {
+        (0..count)
+            .map(|i| Player::new_with_chips(format!("Player {i}"), 1_000))
+            .collect()
+    }
+
+    fn draw_poker(game: GameType) -> DrawPoker {
+        let deck = CardsCell::from(Cards::deck_primed(&Cards::from_str(DEAL).unwrap()));
+        let draw = DrawPoker::primed(game, players(3), ForcedBets::new(10, 20), &deck).unwrap();
+        draw.deal().unwrap();
+        draw
+    }
+
+    fn cards(s: &str) -> Cards {
+        Cards::from_str(s).unwrap()
+    }
+
+    #[test]
+    fn new__errors() {
+        let forced = ForcedBets::new(10, 20);
+
+        assert_eq!(
+            PKError::InvalidGameType,
+            DrawPoker::new(GameType::Razz, players(2), forced).unwrap_err()
+        );
+        assert_eq!(
+            PKError::NotEnoughHands,
+            DrawPoker::new(GameType::TripleDraw, players(1), forced).unwrap_err()
+        );
+        assert_eq!(
+            PKError::TooManyHands,
+            DrawPoker::new(GameType::Badugi, players(7), forced).unwrap_err()
+        );
+    }
+
+    #[test]
+    fn deal() {
+        let draw = draw_poker(GameType::TripleDraw);
+
+        assert_eq!("7♠ 5♥ 4♦ 3♣ K♠", draw.hand(1).to_string());
+        assert_eq!("8♠ 6♥ 4♣ 3♦ 2♠", draw.hand(2).to_string());
+        assert_eq!("A♠ A♥ K♥ Q♦ J♣", draw.hand(0).to_string());
+    }
+
+    #[test]
+    fn deal__badugi() {
+        let draw = draw_poker(GameType::Badugi);
+
+        assert_eq!("7♠ 5♥ 4♦ 3♣", draw.hand(1).to_string());
+    }
+
+    #[test]
+    fn act_draw() {
+        let draw = draw_poker(GameType::TripleDraw);
+        draw.start_draw().unwrap();
+
+        let replacements = draw.act_draw(1, &cards("K♠")).unwrap();
+
+        assert_eq!("2♦", replacements.to_string());
+        assert_eq!("7♠ 5♥ 4♦ 3♣ 2♦", draw.hand(1).to_string());
+        assert_eq!("K♠", draw.table.muck.to_string());
+        assert!(draw.has_drawn(1));
+        assert_eq!(1, draw.table.event_count(&TableAction::Draws(1, 1)));
+    }
+
+    #[test]
+    fn act_draw__errors() {
+        let draw = draw_poker(GameType::TripleDraw);
+        assert_eq!(PKError::InvalidTableAction, draw.act_draw(1, &cards("K♠")).unwrap_err());
+
+        draw.start_draw().unwrap();
+
+        assert_eq!(PKError::CardNotFound, draw.act_draw(1, &cards("A♠")).unwrap_err());
+        draw.act_draw(1, &cards("K♠")).unwrap();
+        assert_eq!(PKError::DuplicateAction, draw.act_draw(1, &cards("7♠")).unwrap_err());
+    }
+
+    #[test]
+    fn act_stand_pat() {
+        let draw = draw_poker(GameType::TripleDraw);
+        draw.start_draw().unwrap();
+
+        assert!(draw.act_stand_pat(2).unwrap().is_empty());
+        assert_eq!("8♠ 6♥ 4♣ 3♦ 2♠", draw.hand(2).to_string());
+        assert_eq!(1, draw.table.event_count(&TableAction::StandsPat(2)));
+    }
+
+    /// Three draws of five cards each from a three handed table is 15 + 15 + 15 + 15 = 60 cards.
+    /// Something has to give.
+    #[test]
+    fn act_draw__reshuffles_discards() {
+        let draw = draw_poker(GameType::TripleDraw);
+
+        for _ in 0..3 {
+            draw.start_draw().unwrap();
+            for seat_number in draw.seats_in_hand() {
+                draw.act_draw(seat_number, &draw.hand(seat_number)).unwrap();
+            }
+        }
+
+        assert!(draw.table.event_log.to_string().contains("Reshuffle"));
+        let mut in_play = draw.table.deck.cards();
+        in_play.insert_all(&draw.table.muck.cards());
+        for seat_number in 0..3 {
+            assert_eq!(5, draw.hand(seat_number).len());
+            in_play.insert_all(&draw.hand(seat_number));
+        }
+        assert_eq!(52, in_play.len());
+    }
+
+    #[test]
+    fn bet_size() {
+        let draw = draw_poker(GameType::TripleDraw);
+        assert_eq!(20, draw.bet_size());
+
+        draw.start_draw().unwrap();
+        assert_eq!(20, draw.bet_size());
+
+        draw.start_draw().unwrap();
+        assert_eq!(40, draw.bet_size());
+    }
+
+    #[test]
+    fn start_draw() {
+        let draw = draw_poker(GameType::FiveCardDraw);
+        assert_eq!(1, draw.draws_remaining());
+
+        assert_eq!(1, draw.start_draw().unwrap());
+        assert_eq!(0, draw.draws_remaining());
+        assert_eq!(PKError::AlreadyDealt, draw.start_draw().unwrap_err());
+    }
+
+    #[test]
+    fn winners__triple_draw() {
+        let draw = draw_poker(GameType::TripleDraw);
+        assert_eq!(vec![2], draw.winners().unwrap());
+
+        draw.start_draw().unwrap();
+        draw.act_draw(1, &cards("K♠")).unwrap();
+
+        assert_eq!(vec![1], draw.winners().unwrap());
+    }
+
+    #[test]
+    fn winners__badugi() {
+        let draw = draw_poker(GameType::Badugi);
+        assert_eq!(vec![1], draw.winners().unwrap());
+
+        // Seat 0 has the three card K-Q-A. Swapping the A♥ for the K♠ off the top doesn't help.
+        draw.start_draw().unwrap();
+        draw.act_draw(0, &cards("A♥")).unwrap();
+
+        assert_eq!(vec![1], draw.winners().unwrap());
+    }
+
+    #[test]
+    fn winners__five_card_draw() {
+        let draw = draw_poker(GameType::FiveCardDraw);
+
+        assert_eq!(vec![0], draw.winners().unwrap());
+    }
+
+    #[test]
+    fn winners__invalid_game_type() {
+        let mut draw = draw_poker(GameType::FiveCardDraw);
+        draw.table.game = GameType::Razz;
+
+        assert_eq!(PKError::InvalidGameType, draw.winners().unwrap_err());
+    }
+
+    #[test]
+    fn commentary() {
+        let draw = draw_poker(GameType::TripleDraw);
+        draw.start_draw().unwrap();
+        draw.act_draw(1, &cards("K♠")).unwrap();
+        draw.act_stand_pat(2).unwrap();
+
+        let log = draw.table.event_log.to_string();
+
+        assert!(log.contains("Draw #1"));
+        assert!(log.contains("Seat 1 draws 1"));
+        assert!(log.contains("Seat 2 stands pat"));
+        assert_eq!("Player 1 draws 1", TableAction::Draws(1, 1).commentary("Player 1"));
+    }
+}
@@ -0,0 +1,416 @@
+use crate::Pile;
+use crate::arrays::HandRanker;
+use crate::arrays::five::Five;
+use crate::arrays::seven::Seven;
+use crate::card::Card;
+use crate::cards::Cards;
+use crate::rank::Rank;
+use std::fmt::{Display, Formatter};
+
+/// Like `HandRankValue`, lower is better, but since there isn't one big lookup table behind it
+/// the numbers aren't consecutive. All that matters is that they sort right, and that two hands
+/// that are worth the same get the same number.
+pub type LowballHandRankValue = u32;
+
+/// The hand doesn't have five cards, or doesn't qualify for the low.
+pub const NO_LOWBALL_HAND_RANK_VALUE: LowballHandRankValue = 0;
+
+/// The different ways of figuring out who has the worst hand, which in lowball is the best hand.
+///
+/// * `DeuceToSeven` - Kansas City lowball, as played in 2-7 Triple Draw. Aces are always high,
+///   and straights and flushes count against you, so the nuts is 7-5-4-3-2 of at least two
+///   suits. A-2-3-4-5 isn't a straight, it's just ace high.
+/// * `AceToFive` - California lowball, as played in Razz. Aces are low and straights and flushes
+///   don't count, so the nuts is the wheel.
+/// * `AceToFiveOrBetter` - Ace to five with a qualifier. The hand has to be five unpaired cards
+///   that are all the rank or lower. `AceToFiveOrBetter(Rank::EIGHT)` is the low half of Stud/8
+///   and Omaha/8.
+/// * `AceToSix` - London lowball. Aces are low, but straights and flushes count against you, so
+///   the wheel is a straight and the nuts is 6-4-3-2-A.
+#[derive(Clone, Copy, Debug, Default, Eq, Hash, PartialEq)]
+pub enum Lowball {
+    #[default]
+    DeuceToSeven,
+    AceToFive,
+    AceToFiveOrBetter(Rank),
+    AceToSix,
+}
+
+impl Lowball {
+    const HIGH_CARD: u32 = 0;
+    const PAIR: u32 = 1;
+    const TWO_PAIR: u32 = 2;
+    const TRIPS: u32 = 3;
+    const STRAIGHT: u32 = 4;
+    const FLUSH: u32 = 5;
+    const FULL_HOUSE: u32 = 6;
+    const QUADS: u32 = 7;
+    const STRAIGHT_FLUSH: u32 = 8;
+
+    /// Returns the `LowballHandRankValue` for the `Five` under this system.
+    ///
+    /// The value is the category of the hand, shifted up past the ranks, followed by a nibble
+    /// for each card, ordered by how many of that rank there are and then by how high it is.
+    /// Since lower is better in both, a straight comparison of the numbers does the job.
+    ///
+    /// ```
+    /// use pkcore::arrays::five::Five;
+    /// use pkcore::games::lowball::Lowball;
+    /// use std::str::FromStr;
+    ///
+    /// let wheel = Five::from_str("5♠ 4♥ 3♦ 2♣ A♠").unwrap();
+    /// let seventy_five = Five::from_str("7♠ 5♥ 4♦ 3♣ 2♠").unwrap();
+    ///
+    /// assert!(Lowball::AceToFive.value(&wheel) < Lowball::AceToFive.value(&seventy_five));
+    /// assert!(Lowball::DeuceToSeven.value(&wheel) > Lowball::DeuceToSeven.value(&seventy_five));
+    /// ```
+    #[must_use]
+    pub fn value(&self, five: &Five) -> LowballHandRankValue {
+        if !five.is_dealt() {
+            return NO_LOWBALL_HAND_RANK_VALUE;
+        }
+
+        let mut counts: Vec<(u32, u32)> = Vec::with_capacity(5);
+        for card in five.iter() {
+            let rank = self.rank_value(card.get_rank());
+            match counts.iter_mut().find(|(_, r)| *r == rank) {
+                Some((count, _)) => *count += 1,
+                None => counts.push((1, rank)),
+            }
+        }
+        counts.sort_by(|a, b| b.cmp(a));
+
+        let category = match counts.as_slice() {
+            [(4, _), _] => Lowball::QUADS,
+            [(3, _), (2, _)] => Lowball::FULL_HOUSE,
+            [(3, _), _, _] => Lowball::TRIPS,
+            [(2, _), (2, _), _] => Lowball::TWO_PAIR,
+            [(2, _), _, _, _] => Lowball::PAIR,
+            _ => self.unpaired_category(five, &counts),
+        };
+
+        if let Lowball::AceToFiveOrBetter(qualifier) = self {
+            let top = counts.first().map_or(0, |(_, rank)| *rank);
+            if category != Lowball::HIGH_CARD || top > self.rank_value(*qualifier) {
+                return NO_LOWBALL_HAND_RANK_VALUE;
+            }
+        }
+
+        let ranks = counts
+            .iter()
+            .flat_map(|(count, rank)| std::iter::repeat_n(*rank, *count as usize))
+            .fold(0, |acc, rank| (acc << 4) | rank);
+
+        ((category << 20) | ranks) + 1
+    }
+
+    /// Aces are high in 2-7 and low everywhere else.
+    fn rank_value(self, rank: Rank) -> u32 {
+        match (self, rank) {
+            (Lowball::DeuceToSeven, _) => rank as u32,
+            (_, Rank::ACE) => 1,
+            _ => rank as u32,
+        }
+    }
+
+    /// Five different ranks: high card, unless this is a system where straights and flushes
+    /// count, in which case it might be worse.
+    fn unpaired_category(self, five: &Five, counts: &[(u32, u32)]) -> u32 {
+        if self.is_ace_to_five() {
+            return Lowball::HIGH_CARD;
+        }
+
+        let high = counts.first().map_or(0, |(_, rank)| *rank);
+        let low = counts.last().map_or(0, |(_, rank)| *rank);
+        let straight = high - low == 4;
+        match (straight, five.is_flush()) {
+            (true, true) => Lowball::STRAIGHT_FLUSH,
+            (false, true) => Lowball::FLUSH,
+            (true, false) => Lowball::STRAIGHT,
+            (false, false) => Lowball::HIGH_CARD,
+        }
+    }
+
+    fn is_ace_to_five(self) -> bool {
+        matches!(self, Lowball::AceToFive | Lowball::AceToFiveOrBetter(_))
+    }
+
+    /// Returns the best value and hand out of every five card combination of the cards.
+    fn best<I: Iterator<Item = Five>>(self, fives: I) -> (LowballHandRankValue, Five) {
+        let mut best = (NO_LOWBALL_HAND_RANK_VALUE, Five::default());
+        for five in fives {
+            let value = self.value(&five);
+            if value != NO_LOWBALL_HAND_RANK_VALUE && (best.0 == NO_LOWBALL_HAND_RANK_VALUE || value < best.0) {
+                best = (value, five);
+            }
+        }
+        (best.0, best.1.sort())
+    }
+}
+
+impl Display for Lowball {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Lowball::DeuceToSeven => write!(f, "Deuce to Seven"),
+            Lowball::AceToFive => write!(f, "Ace to Five"),
+            Lowball::AceToFiveOrBetter(rank) => write!(f, "Ace to Five, {} or Better", rank.to_char()),
+            Lowball::AceToSix => write!(f, "Ace to Six"),
+        }
+    }
+}
+
+/// The lowball version of `HandRanker`. Give it a `Lowball` system and it returns the best low
+/// hand along with its `LowballHandRankValue`.
+pub trait LowballRanker {
+    fn lowball_hand_rank_value_and_hand(&self, lowball: Lowball) -> (LowballHandRankValue, Five);
+
+    fn lowball_hand_rank_value(&self, lowball: Lowball) -> LowballHandRankValue {
+        let (value, _) = self.lowball_hand_rank_value_and_hand(lowball);
+        value
+    }
+}
+
+impl LowballRanker for Five {
+    fn lowball_hand_rank_value_and_hand(&self, lowball: Lowball) -> (LowballHandRankValue, Five) {
+        lowball.best(std::iter::once(*self))
+    }
+}
+
+impl LowballRanker for Seven {
+    fn lowball_hand_rank_value_and_hand(&self, lowball: Lowball) -> (LowballHandRankValue, Five) {
+        lowball.best(
+            Seven::FIVE_CARD_PERMUTATIONS
+                .iter()
+                .map(|perm| self.five_from_permutation(*perm)),
+        )
+    }
+}
+
+/// Anything from five cards on up. Fewer than five and you get `NO_LOWBALL_HAND_RANK_VALUE`.
+impl LowballRanker for Cards {
+    fn lowball_hand_rank_value_and_hand(&self, lowball: Lowball) -> (LowballHandRankValue, Five) {
+        lowball.best(
+            self.combinations(5)
+                .filter_map(|cards: Vec<Card>| Five::try_from(cards).ok()),
+        )
+    }
+}
+
+#[cfg(test)]
+#[allow(non_snake_case)]
+mod games__lowball_tests {
+    use super::*;
+    use crate::games::razz::california::CaliforniaHandRank;
+    use crate::suit::Suit;
+    use itertools::Itertools;
+    use rayon::prelude::*;
+    use rstest::rstest;
+    use std::collections::HashSet;
+    use std::str::FromStr;
+    use strum::IntoEnumIterator;
+
+    fn value(lowball: Lowball, s: &str) -> LowballHandRankValue {
+        Lowball::value(&lowball, &Five::from_str(s).unwrap())
+    }
+
+    /// Every way to pick five ranks, with no more than four of any one, as a `Five`. The suits
+    /// go spade, heart, diamond, club for each rank, so five different ranks come out as a
+    /// spade flush. For those we add a version with the first card a heart, so the 7,462
+    /// hands cover every lowball value there is without dealing out all 2,598,960.
+    fn every_rank_class() -> Vec<Five> {
+        let suits = [Suit::SPADES, Suit::HEARTS, Suit::DIAMONDS, Suit::CLUBS];
+        let mut fives = Vec::new();
+        for ranks in Rank::iter()
+            .filter(|rank| *rank != Rank::BLANK)
+            .combinations_with_replacement(5)
+        {
+            let mut cards: Vec<Card> = Vec::with_capacity(5);
+            for rank in ranks {
+                let seen = cards.iter().filter(|card| card.get_rank() == rank).count();
+                if let Some(suit) = suits.get(seen) {
+                    cards.push(Card::new(rank, *suit));
+                }
+            }
+            if let Ok(five) = Five::try_from(cards.clone()) {
+                if five.is_flush() {
+                    cards[0] = Card::new(cards[0].get_rank(), Suit::HEARTS);
+                    fives.push(Five::try_from(cards).unwrap());
+                }
+                fives.push(five);
+            }
+        }
+        fives
+    }
+
+    fn distinct(lowball: Lowball, fives: &[Five]) -> usize {
+        let values: HashSet<LowballHandRankValue> = fives
+            .par_iter()
+            .map(|five| lowball.value(five))
+            .filter(|value| *value != NO_LOWBALL_HAND_RANK_VALUE)
+            .collect();
+        values.len()
+    }
+
+    #[test]
+    fn every_rank_class__count() {
+        assert_eq!(crate::DISTINCT_5_CARD_HANDS, every_rank_class().len());
+    }
+
+    /// 2-7 and A-6 both end up with the same 7,462 as Cactus Kev, since each only moves one
+    /// straight, and the matching straight flush, into the unpaired hands.
+    #[test]
+    fn distinct__deuce_to_seven() {
+        assert_eq!(
+            crate::DISTINCT_5_CARD_HANDS,
+            distinct(Lowball::DeuceToSeven, &every_rank_class())
+        );
+    }
+
+    #[test]
+    fn distinct__ace_to_six() {
+        assert_eq!(
+            crate::DISTINCT_5_CARD_HANDS,
+            distinct(Lowball::AceToSix, &every_rank_class())
+        );
+    }
+
+    /// No straights or flushes: 1,287 unpaired + 2,860 pairs + 858 two pair + 858 trips + 156
+    /// full houses + 156 quads.
+    #[test]
+    fn distinct__ace_to_five() {
+        assert_eq!(6_175, distinct(Lowball::AceToFive, &every_rank_class()));
+    }
+
+    /// Five different ranks out of the eight from ace to eight is 8 choose 5.
+    #[test]
+    fn distinct__ace_to_five__eight_or_better() {
+        assert_eq!(
+            56,
+            distinct(Lowball::AceToFiveOrBetter(Rank::EIGHT), &every_rank_class())
+        );
+    }
+
+    /// The same thing the hard way, over every five card hand in the deck. Slow in debug.
+    #[test]
+    #[ignore]
+    fn distinct__every_hand() {
+        let fives: Vec<Five> = Cards::deck()
+            .combinations(5)
+            .map(|cards| Five::try_from(cards).unwrap())
+            .collect();
+
+        assert_eq!(crate::DISTINCT_5_CARD_HANDS, distinct(Lowball::DeuceToSeven, &fives));
+        assert_eq!(crate::DISTINCT_5_CARD_HANDS, distinct(Lowball::AceToSix, &fives));
+        assert_eq!(6_175, distinct(Lowball::AceToFive, &fives));
+        assert_eq!(56, distinct(Lowball::AceToFiveOrBetter(Rank::EIGHT), &fives));
+    }
+
+    #[rstest]
+    #[case("7♠ 5♥ 4♦ 3♣ 2♠", "7♠ 6♥ 4♦ 3♣ 2♠")]
+    #[case("8♠ 6♥ 5♦ 4♣ 2♠", "8♠ 6♥ 5♦ 4♣ 3♠")]
+    #[case("K♠ Q♥ J♦ T♣ 8♠", "A♠ 5♥ 4♦ 3♣ 2♠")]
+    #[case("K♠ Q♥ J♦ T♣ 8♠", "2♠ 2♥ 7♦ 5♣ 3♠")]
+    #[case("A♠ A♥ 7♦ 5♣ 3♠", "2♠ 2♥ 3♦ 3♣ 4♠")]
+    #[case("A♠ K♥ Q♦ J♣ 9♠", "6♠ 5♥ 4♦ 3♣ 2♠")]
+    #[case("6♠ 5♥ 4♦ 3♣ 2♠", "7♠ 5♠ 4♠ 3♠ 2♠")]
+    #[case("7♠ 5♠ 4♠ 3♠ 2♠", "2♠ 2♥ 2♦ 3♣ 3♠")]
+    #[case("A♠ K♠ Q♠ J♠ 9♠", "A♠ K♠ Q♠ J♠ T♠")]
+    fn value__deuce_to_seven(#[case] better: &str, #[case] worse: &str) {
+        assert!(value(Lowball::DeuceToSeven, better) < value(Lowball::DeuceToSeven, worse));
+    }
+
+    #[rstest]
+    #[case("6♠ 4♥ 3♦ 2♣ A♠", "6♠ 5♥ 3♦ 2♣ A♠")]
+    #[case("K♠ Q♥ J♦ T♣ 8♠", "5♠ 4♥ 3♦ 2♣ A♠")]
+    #[case("A♠ K♥ Q♦ J♣ T♠", "5♠ 4♥ 3♦ 2♣ A♠")]
+    #[case("A♠ A♥ 7♦ 5♣ 3♠", "2♠ 2♥ 7♦ 5♣ 3♠")]
+    #[case("6♠ 4♥ 3♦ 2♣ A♠", "6♠ 4♠ 3♠ 2♠ A♠")]
+    fn value__ace_to_six(#[case] better: &str, #[case] worse: &str) {
+        assert!(value(Lowball::AceToSix, better) < value(Lowball::AceToSix, worse));
+    }
+
+    #[rstest]
+    #[case("5♠ 4♠ 3♠ 2♠ A♠", "6♠ 4♥ 3♦ 2♣ A♠")]
+    #[case("6♠ 4♥ 3♦ 2♣ A♠", "6♠ 5♥ 3♦ 2♣ A♠")]
+    #[case("K♠ Q♥ J♦ T♣ 9♠", "A♠ A♥ 3♦ 2♣ 4♠")]
+    #[case("A♠ A♥ 3♦ 2♣ 4♠", "2♠ 2♥ 3♦ A♣ 4♠")]
+    fn value__ace_to_five(#[case] better: &str, #[case] worse: &str) {
+        assert!(value(Lowball::AceToFive, better) < value(Lowball::AceToFive, worse));
+    }
+
+    /// Lines up with the order of the `CaliforniaHandRank` table.
+    #[rstest]
+    #[case("5♠ 4♥ 3♦ 2♣ A♠", "6♠ 4♥ 3♦ 2♣ A♠")]
+    #[case("K♠ Q♥ J♦ T♣ 9♠", "A♠ A♥ 3♦ 2♣ 4♠")]
+    #[case("Q♠ Q♥ J♦ J♣ T♠", "K♠ K♥ 3♦ 3♣ 2♠")]
+    fn value__ace_to_five__matches_california(#[case] better: &str, #[case] worse: &str) {
+        let better = Five::from_str(better).unwrap();
+        let worse = Five::from_str(worse).unwrap();
+
+        assert!(CaliforniaHandRank::from(better) < CaliforniaHandRank::from(worse));
+        assert!(Lowball::AceToFive.value(&better) < Lowball::AceToFive.value(&worse));
+    }
+
+    #[test]
+    fn value__ace_to_five_or_better() {
+        let eight = Lowball::AceToFiveOrBetter(Rank::EIGHT);
+        let six = Lowball::AceToFiveOrBetter(Rank::SIX);
+
+        assert_ne!(NO_LOWBALL_HAND_RANK_VALUE, value(eight, "8♠ 7♥ 6♦ 5♣ 4♠"));
+        assert_eq!(NO_LOWBALL_HAND_RANK_VALUE, value(eight, "9♠ 4♥ 3♦ 2♣ A♠"));
+        assert_eq!(NO_LOWBALL_HAND_RANK_VALUE, value(eight, "8♠ 8♥ 3♦ 2♣ A♠"));
+        assert_ne!(NO_LOWBALL_HAND_RANK_VALUE, value(six, "6♠ 5♥ 3♦ 2♣ A♠"));
+        assert_eq!(NO_LOWBALL_HAND_RANK_VALUE, value(six, "7♠ 4♥ 3♦ 2♣ A♠"));
+        assert_eq!(
+            value(Lowball::AceToFive, "6♠ 5♥ 3♦ 2♣ A♠"),
+            value(six, "6♠ 5♥ 3♦ 2♣ A♠")
+        );
+    }
+
+    #[test]
+    fn value__not_dealt() {
+        assert_eq!(
+            NO_LOWBALL_HAND_RANK_VALUE,
+            Lowball::DeuceToSeven.value(&Five::default())
+        );
+    }
+
+    #[test]
+    fn lowball_hand_rank_value_and_hand__seven() {
+        let seven = Seven::from_str("A♠ 2♥ 3♦ 4♣ 5♠ 7♥ K♦").unwrap();
+
+        let (_, deuce_to_seven) = seven.lowball_hand_rank_value_and_hand(Lowball::DeuceToSeven);
+        let (_, ace_to_five) = seven.lowball_hand_rank_value_and_hand(Lowball::AceToFive);
+        let (_, ace_to_six) = seven.lowball_hand_rank_value_and_hand(Lowball::AceToSix);
+
+        assert_eq!("7♥ 5♠ 4♣ 3♦ 2♥", deuce_to_seven.to_string());
+        assert_eq!("5♠ 4♣ 3♦ 2♥ A♠", ace_to_five.to_string());
+        assert_eq!("A♠ 7♥ 4♣ 3♦ 2♥", ace_to_six.to_string());
+    }
+
+    #[test]
+    fn lowball_hand_rank_value_and_hand__cards() {
+        let cards = Cards::from_str("9♠ 8♥ 6♦ 4♣ 3♠ 2♥").unwrap();
+
+        let (value, hand) = cards.lowball_hand_rank_value_and_hand(Lowball::AceToFiveOrBetter(Rank::EIGHT));
+
+        assert_eq!("8♥ 6♦ 4♣ 3♠ 2♥", hand.to_string());
+        assert_eq!(value, Lowball::AceToFive.value(&hand));
+        assert_eq!(
+            NO_LOWBALL_HAND_RANK_VALUE,
+            Cards::from_str("9♠ 8♥ 6♦ 4♣")
+                .unwrap()
+                .lowball_hand_rank_value(Lowball::AceToFive)
+        );
+    }
+
+    #[test]
+    fn display() {
+        assert_eq!("Deuce to Seven", Lowball::DeuceToSeven.to_string());
+        assert_eq!(
+            "Ace to Five, 8 or Better",
+            Lowball::AceToFiveOrBetter(Rank::EIGHT).to_string()
+        );
+        assert_eq!("Ace to Six", Lowball::AceToSix.to_string());
+    }
+}
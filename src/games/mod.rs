@@ -1,7 +1,12 @@
 use crate::cards_cell::CardsCell;
+use crate::games::short_deck::ShortDeck;
 
+pub mod badugi;
+pub mod draw;
+pub mod lowball;
 pub mod omaha;
 pub mod razz;
+pub mod short_deck;
 pub mod stud;
 
 #[derive(Clone, Copy, Debug, Default, Ord, PartialOrd, Eq, Hash, PartialEq)]
@@ -9,35 +14,95 @@ pub enum GameType {
     #[default]
     NoLimitHoldem,
     PLO,
+    PLO5,
+    PLO6,
+    BigO,
     Razz,
+    SevenCardStud,
+    TripleDraw,
+    Badugi,
+    FiveCardDraw,
+    ShortDeck,
 }
 
 impl GameType {
     #[must_use]
     pub fn cards_per_player(&self) -> u8 {
         match self {
-            GameType::NoLimitHoldem => 2,
-            GameType::PLO => 4,
-            GameType::Razz => 7,
+            GameType::NoLimitHoldem | GameType::ShortDeck => 2,
+            GameType::PLO | GameType::Badugi => 4,
+            GameType::PLO5 | GameType::BigO | GameType::TripleDraw | GameType::FiveCardDraw => 5,
+            GameType::PLO6 => 6,
+            GameType::Razz | GameType::SevenCardStud => 7,
         }
     }
 
     #[must_use]
     pub fn cards_on_board(&self) -> u8 {
         match self {
-            GameType::NoLimitHoldem => 5,
+            GameType::NoLimitHoldem
+            | GameType::PLO
+            | GameType::PLO5
+            | GameType::PLO6
+            | GameType::BigO
+            | GameType::ShortDeck => 5,
+            GameType::Razz
+            | GameType::SevenCardStud
+            | GameType::TripleDraw
+            | GameType::Badugi
+            | GameType::FiveCardDraw => 0,
+        }
+    }
+
+    /// How many times the players get to draw. Zero for everything that isn't a draw game.
+    #[must_use]
+    pub fn draws(&self) -> u8 {
+        match self {
+            GameType::TripleDraw | GameType::Badugi => 3,
+            GameType::FiveCardDraw => 1,
             _ => 0,
         }
     }
 
+    #[must_use]
+    pub fn is_draw(&self) -> bool {
+        self.draws() > 0
+    }
+
+    /// The most players that can be dealt in from a single deck, leaving enough cards for the
+    /// board. That's 23 for hold'em, 15 for short deck, 11 for PLO, 9 for PLO5 and Big O, and only 7 for PLO6.
+    ///
+    /// The stud games are the exception. Eight players would need 56 cards, but if they all
+    /// make it to seventh street the dealer turns up a single common card that everyone shares.
+    ///
+    /// So are the draw games, where what matters is how many cards are left over to draw from.
+    /// They're spread six handed, and when the deck runs out the discards get reshuffled.
+    #[must_use]
+    pub fn max_players(&self) -> u8 {
+        if matches!(self, GameType::Razz | GameType::SevenCardStud) {
+            return 8;
+        }
+        if self.is_draw() {
+            return 6;
+        }
+        let available = self.get_deck_size() - self.cards_on_board() as usize;
+        u8::try_from(available / self.cards_per_player() as usize).unwrap_or(u8::MAX)
+    }
+
     #[must_use]
     pub fn get_deck(&self) -> CardsCell {
-        CardsCell::deck()
+        match self {
+            GameType::ShortDeck => CardsCell::from(ShortDeck::deck()),
+            _ => CardsCell::deck(),
+        }
     }
 
     #[must_use]
     pub fn get_deck_size(&self) -> usize {
-        52
+        match self {
+            GameType::ShortDeck => ShortDeck::DECK_SIZE,
+            _ => 52,
+        }
     }
 }
 
@@ -46,7 +111,15 @@ impl std::fmt::Display for GameType {
         match self {
             GameType::NoLimitHoldem => write!(f, "No Limit Hold'em"),
             GameType::PLO => write!(f, "Pot Limit Omaha"),
+            GameType::PLO5 => write!(f, "Five Card Pot Limit Omaha"),
+            GameType::PLO6 => write!(f, "Six Card Pot Limit Omaha"),
+            GameType::BigO => write!(f, "Big O"),
             GameType::Razz => write!(f, "Razz"),
+            GameType::SevenCardStud => write!(f, "Seven Card Stud"),
+            GameType::TripleDraw => write!(f, "2-7 Triple Draw"),
+            GameType::Badugi => write!(f, "Badugi"),
+            GameType::FiveCardDraw => write!(f, "Five Card Draw"),
+            GameType::ShortDeck => write!(f, "Short Deck Hold'em"),
         }
     }
 }
@@ -190,14 +263,63 @@ mod games_tests {
     fn cards_per_player() {
         assert_eq!(2, GameType::NoLimitHoldem.cards_per_player());
         assert_eq!(4, GameType::PLO.cards_per_player());
+        assert_eq!(5, GameType::PLO5.cards_per_player());
+        assert_eq!(6, GameType::PLO6.cards_per_player());
+        assert_eq!(5, GameType::BigO.cards_per_player());
         assert_eq!(7, GameType::Razz.cards_per_player());
+        assert_eq!(7, GameType::SevenCardStud.cards_per_player());
+        assert_eq!(5, GameType::TripleDraw.cards_per_player());
+        assert_eq!(4, GameType::Badugi.cards_per_player());
+        assert_eq!(5, GameType::FiveCardDraw.cards_per_player());
+        assert_eq!(2, GameType::ShortDeck.cards_per_player());
     }
 
     #[test]
     fn cards_on_board() {
         assert_eq!(5, GameType::NoLimitHoldem.cards_on_board());
-        assert_eq!(0, GameType::PLO.cards_on_board());
+        assert_eq!(5, GameType::PLO.cards_on_board());
+        assert_eq!(5, GameType::PLO5.cards_on_board());
+        assert_eq!(5, GameType::PLO6.cards_on_board());
+        assert_eq!(5, GameType::BigO.cards_on_board());
         assert_eq!(0, GameType::Razz.cards_on_board());
+        assert_eq!(0, GameType::SevenCardStud.cards_on_board());
+        assert_eq!(0, GameType::TripleDraw.cards_on_board());
+        assert_eq!(5, GameType::ShortDeck.cards_on_board());
+    }
+
+    #[test]
+    fn max_players() {
+        assert_eq!(23, GameType::NoLimitHoldem.max_players());
+        assert_eq!(11, GameType::PLO.max_players());
+        assert_eq!(9, GameType::PLO5.max_players());
+        assert_eq!(7, GameType::PLO6.max_players());
+        assert_eq!(9, GameType::BigO.max_players());
+        assert_eq!(8, GameType::Razz.max_players());
+        assert_eq!(8, GameType::SevenCardStud.max_players());
+        assert_eq!(6, GameType::TripleDraw.max_players());
+        assert_eq!(6, GameType::Badugi.max_players());
+        assert_eq!(15, GameType::ShortDeck.max_players());
+    }
+
+    #[test]
+    fn draws() {
+        assert_eq!(0, GameType::NoLimitHoldem.draws());
+        assert_eq!(3, GameType::TripleDraw.draws());
+        assert_eq!(3, GameType::Badugi.draws());
+        assert_eq!(1, GameType::FiveCardDraw.draws());
+        assert!(GameType::Badugi.is_draw());
+        assert!(!GameType::Razz.is_draw());
+    }
+
+    #[test]
+    fn display() {
+        assert_eq!("Pot Limit Omaha", GameType::PLO.to_string());
+        assert_eq!("Five Card Pot Limit Omaha", GameType::PLO5.to_string());
+        assert_eq!("Six Card Pot Limit Omaha", GameType::PLO6.to_string());
+        assert_eq!("Big O", GameType::BigO.to_string());
+        assert_eq!("Seven Card Stud", GameType::SevenCardStud.to_string());
+        assert_eq!("2-7 Triple Draw", GameType::TripleDraw.to_string());
+        assert_eq!("Short Deck Hold'em", GameType::ShortDeck.to_string());
     }
 
     #[test]
@@ -205,5 +327,8 @@ mod games_tests {
         assert_eq!(CardsCell::deck(), GameType::NoLimitHoldem.get_deck());
         assert_eq!(CardsCell::deck(), GameType::PLO.get_deck());
         assert_eq!(CardsCell::deck(), GameType::Razz.get_deck());
+        assert_eq!(CardsCell::from(ShortDeck::deck()), GameType::ShortDeck.get_deck());
+        assert_eq!(36, GameType::ShortDeck.get_deck().len());
+        assert_eq!(36, GameType::ShortDeck.get_deck_size());
     }
 }
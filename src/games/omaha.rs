@@ -1,18 +1,25 @@
 use crate::analysis::eval::Eval;
+use crate::analysis::hand_rank::{HandRankValue, NO_HAND_RANK_VALUE};
+use crate::analysis::omaha::EightOrBetterLow;
 use crate::analysis::the_nuts::TheNuts;
 use crate::arrays::HandRanker;
 use crate::arrays::five::Five;
 use crate::arrays::four::Four;
-use crate::arrays::seven::Seven;
+use crate::arrays::three::Three;
 use crate::arrays::two::Two;
 use crate::card::Card;
 use crate::cards::Cards;
 use crate::play::board::Board;
 use crate::{PKError, Pile};
+use itertools::Itertools;
+use std::cmp::Ordering;
 use std::fmt;
 use std::fmt::{Display, Formatter};
 use std::str::FromStr;
 
+pub mod equity;
+pub mod hi_lo;
+
 pub const OMAHA_HAND_PERMUTATIONS: [[usize; 2]; 6] = [[0, 1], [0, 2], [0, 3], [1, 2], [1, 3], [2, 3]];
 pub const OMAHA_BOARD_PERMUTATIONS: [[usize; 3]; 10] = [
     [0, 1, 2],
@@ -27,28 +34,164 @@ pub const OMAHA_BOARD_PERMUTATIONS: [[usize; 3]; 10] = [
     [2, 3, 4],
 ];
 
+/// The one rule that makes Omaha Omaha: you play exactly two of your hole cards and exactly three
+/// cards from the board. It doesn't matter if you're holding four cards for PLO, five for PLO5
+/// and Big O, or six for PLO6. The only thing that changes is how many pairs of hole cards you
+/// have to try: C(4,2) = 6, C(5,2) = 10, and C(6,2) = 15, each against the same ten board triples.
+///
+/// So, rather than hard wiring everything to `Four`, anything that can hand over its hole
+/// cards gets the evaluators for free.
+pub trait OmahaHand: Display {
+    /// All the player's hole cards.
+    fn hole_cards(&self) -> Vec<Card>;
+
+    /// Every pair of hole cards that the player could play, in the same order as
+    /// `OMAHA_HAND_PERMUTATIONS` for a four card hand.
+    fn hole_pairs(&self) -> Vec<Two> {
+        self.hole_cards().into_iter().combinations(2).map(Two::from).collect()
+    }
+
+    /// Returns the best hand that can be made using exactly two of the hole cards and exactly
+    /// three cards from the board.
+    ///
+    /// My original version of this built a `Seven` out of each pair of hole cards and the full
+    /// board, which is fine for hold'em but lets you play zero or one of your hole cards. In
+    /// Omaha that gets you into trouble on four flush boards and when the board has quads or
+    /// a full house on it.
+    #[must_use]
+    fn eval(&self, board: &Board) -> Eval {
+        self.best(board).eval
+    }
+
+    /// Walks through all the legal Omaha combinations, 60 of them for a four card hand, and
+    /// returns the winning `Eval` along with the hole and board cards that made it.
+    ///
+    /// ```
+    /// use pkcore::games::omaha::{OmahaHand, OmahaHigh};
+    /// use pkcore::play::board::Board;
+    /// use std::str::FromStr;
+    ///
+    /// let hand = OmahaHigh::from_str("A♠ A♥ 2♦ 3♣").unwrap();
+    /// let board = Board::from_str("9♠ 9♥ 9♦ 9♣ K♠").unwrap();
+    ///
+    /// let best = hand.best(&board);
+    ///
+    /// // No quads for us. We have to play the aces.
+    /// assert_eq!("9♠ 9♥ 9♦ A♠ A♥", best.eval.hand.to_string());
+    /// assert_eq!("A♠ A♥", best.hole.to_string());
+    /// ```
+    #[must_use]
+    fn best(&self, board: &Board) -> OmahaEval {
+        let board = Five::from(*board);
+        let mut best: Option<(HandRankValue, Two, Three)> = None;
+
+        for hole in self.hole_pairs() {
+            for board_perm in &OMAHA_BOARD_PERMUTATIONS {
+                let three = Three::from([board.0[board_perm[0]], board.0[board_perm[1]], board.0[board_perm[2]]]);
+                let value = Five::from_2and3(hole, three).hand_rank_value();
+                if value == NO_HAND_RANK_VALUE {
+                    continue;
+                }
+                match best {
+                    Some((best_value, _, _)) if best_value <= value => {}
+                    _ => best = Some((value, hole, three)),
+                }
+            }
+        }
+
+        match best {
+            Some((_, hole, three)) => OmahaEval {
+                eval: Five::from_2and3(hole, three).eval(),
+                hole,
+                board: three,
+            },
+            None => OmahaEval::default(),
+        }
+    }
+
+    /// Returns the best eight or better low that can be made with exactly two hole cards and
+    /// three from the board, if there is one. See `EightOrBetterLow` for how they compare.
+    ///
+    /// ```
+    /// use pkcore::games::omaha::{OmahaHand, OmahaHigh};
+    /// use pkcore::play::board::Board;
+    /// use std::str::FromStr;
+    ///
+    /// let hand = OmahaHigh::from_str("A♠ 2♥ K♦ K♣").unwrap();
+    /// let board = Board::from_str("3♠ 7♥ 8♦ Q♣ Q♦").unwrap();
+    ///
+    /// assert_eq!("8-7-3-2-A", hand.best_low(&board).unwrap().to_string());
+    /// ```
+    #[must_use]
+    fn best_low(&self, board: &Board) -> Option<EightOrBetterLow> {
+        let board = Five::from(*board);
+
+        EightOrBetterLow::best(self.hole_pairs().into_iter().flat_map(|hole| {
+            OMAHA_BOARD_PERMUTATIONS.iter().map(move |board_perm| {
+                let three = Three::from([board.0[board_perm[0]], board.0[board_perm[1]], board.0[board_perm[2]]]);
+                Five::from_2and3(hole, three)
+            })
+        }))
+    }
+
+    /// Omaha Hi-Lo, aka Omaha/8. The best high and, separately, the best qualifying low. They
+    /// don't have to use the same hole cards.
+    #[must_use]
+    fn best_hi_lo(&self, board: &Board) -> OmahaHiLoEval {
+        OmahaHiLoEval {
+            high: self.best(board),
+            low: self.best_low(board),
+        }
+    }
+}
+
 #[derive(Clone, Copy, Debug, Default, Eq, Hash, Ord, PartialEq, PartialOrd)]
 #[allow(clippy::pedantic)]
 pub struct OmahaHigh {
     pub hand: Four,
 }
 
+impl OmahaHand for OmahaHigh {
+    fn hole_cards(&self) -> Vec<Card> {
+        self.hand.to_vec()
+    }
+}
+
 impl OmahaHigh {
+    /// `eval()` and friends moved over to `OmahaHand` so that PLO5 and PLO6 could use them, but
+    /// plenty of code out there calls them on `OmahaHigh` without pulling in the trait. These
+    /// just forward along.
+    ///
+    /// ```
+    /// use pkcore::games::omaha::OmahaHigh;
+    /// use pkcore::play::board::Board;
+    /// use std::str::FromStr;
+    ///
+    /// let hand = OmahaHigh::from_str("A♠ 2♥ K♦ K♣").unwrap();
+    /// let board = Board::from_str("3♠ 7♥ 8♦ K♠ Q♦").unwrap();
+    ///
+    /// assert_eq!(hand.best(&board).eval, hand.eval(&board));
+    /// assert_eq!("K♠ K♦ K♣ Q♦ 8♦", hand.eval(&board).hand.to_string());
+    /// assert_eq!("8-7-3-2-A", hand.best_hi_lo(&board).low.unwrap().to_string());
+    /// ```
     #[must_use]
     pub fn eval(&self, board: &Board) -> Eval {
-        let mut best_eval = Eval::default();
+        OmahaHand::eval(self, board)
+    }
 
-        for perm in &OMAHA_HAND_PERMUTATIONS {
-            let two = Two::from([self.hand.0[perm[0]], self.hand.0[perm[1]]]);
-            let seven = Seven::from_case_and_board(&two, board);
+    #[must_use]
+    pub fn best(&self, board: &Board) -> OmahaEval {
+        OmahaHand::best(self, board)
+    }
 
-            let eval = seven.eval();
-            if eval > best_eval {
-                best_eval = eval;
-            }
-        }
+    #[must_use]
+    pub fn best_low(&self, board: &Board) -> Option<EightOrBetterLow> {
+        OmahaHand::best_low(self, board)
+    }
 
-        best_eval
+    #[must_use]
+    pub fn best_hi_lo(&self, board: &Board) -> OmahaHiLoEval {
+        OmahaHand::best_hi_lo(self, board)
     }
 
     #[allow(dead_code)]
@@ -91,6 +234,37 @@ impl Display for OmahaHigh {
     }
 }
 
+/// The winning hand for an Omaha player along with the two hole cards and three board cards that
+/// were used to make it.
+#[derive(Clone, Copy, Debug, Default, Eq, Hash, PartialEq)]
+pub struct OmahaEval {
+    pub eval: Eval,
+    pub hole: Two,
+    pub board: Three,
+}
+
+impl Display for OmahaEval {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(f, "{} ({} + {})", self.eval, self.hole, self.board)
+    }
+}
+
+/// What an Omaha/8 hand is worth at showdown: its best high and its best low, if it has one.
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub struct OmahaHiLoEval {
+    pub high: OmahaEval,
+    pub low: Option<EightOrBetterLow>,
+}
+
+impl Display for OmahaHiLoEval {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self.low {
+            Some(low) => write!(f, "{} / {low}", self.high.eval),
+            None => write!(f, "{} / No Low", self.high.eval),
+        }
+    }
+}
+
 impl From<Four> for OmahaHigh {
     fn from(four: Four) -> Self {
         OmahaHigh { hand: four }
@@ -144,10 +318,85 @@ impl TryFrom<Cards> for OmahaHigh {
     }
 }
 
+/// Omaha with more than four hole cards. `Omaha<5>` is five card PLO, which is also what you're
+/// holding in Big O, the hi-lo version. `Omaha<6>` is six card PLO. The cards are sorted high to
+/// low, the same as `Four`.
+///
+/// ```
+/// use pkcore::games::omaha::{FiveCardOmaha, OmahaHand};
+/// use pkcore::play::board::Board;
+/// use std::str::FromStr;
+///
+/// let hand = FiveCardOmaha::from_str("A♠ K♠ 9♥ 8♥ 2♦").unwrap();
+/// let board = Board::from_str("Q♠ J♠ T♥ 3♣ 2♣").unwrap();
+///
+/// assert_eq!(10, hand.hole_pairs().len());
+/// assert_eq!("A♠ K♠ Q♠ J♠ T♥", hand.best(&board).eval.hand.to_string());
+/// ```
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub struct Omaha<const N: usize>(pub [Card; N]);
+
+pub type FiveCardOmaha = Omaha<5>;
+pub type SixCardOmaha = Omaha<6>;
+
+impl<const N: usize> Default for Omaha<N> {
+    fn default() -> Self {
+        Omaha([Card::BLANK; N])
+    }
+}
+
+impl<const N: usize> Display for Omaha<N> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.0.iter().map(Card::to_string).join(" "))
+    }
+}
+
+impl<const N: usize> From<[Card; N]> for Omaha<N> {
+    fn from(array: [Card; N]) -> Self {
+        let mut array = array;
+        array.sort();
+        array.reverse();
+        Omaha(array)
+    }
+}
+
+impl<const N: usize> FromStr for Omaha<N> {
+    type Err = PKError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Omaha::try_from(Cards::from_str(s)?)
+    }
+}
+
+impl<const N: usize> OmahaHand for Omaha<N> {
+    fn hole_cards(&self) -> Vec<Card> {
+        self.0.to_vec()
+    }
+}
+
+impl<const N: usize> TryFrom<Cards> for Omaha<N> {
+    type Error = PKError;
+
+    fn try_from(cards: Cards) -> Result<Self, Self::Error> {
+        match cards.len().cmp(&N) {
+            Ordering::Less => Err(PKError::NotEnoughCards),
+            Ordering::Greater => Err(PKError::TooManyCards),
+            Ordering::Equal => {
+                let mut array = [Card::BLANK; N];
+                for (slot, card) in array.iter_mut().zip(cards.iter()) {
+                    *slot = *card;
+                }
+                Ok(Omaha::from(array))
+            }
+        }
+    }
+}
+
 #[cfg(test)]
 #[allow(non_snake_case)]
 mod games__omaha_high_tests {
     use super::*;
+    use crate::analysis::class::HandRankClass;
 
     /// The hand:
     /// Robl: AS QS QD JC
@@ -176,6 +425,199 @@ mod games__omaha_high_tests {
         Card::ACE_CLUBS,
     ];
 
+    #[test]
+    fn eval() {
+        let robl = OmahaHigh::from(ROBL_HAND);
+        let antonius = OmahaHigh::from(ANTONIUS_HAND);
+        let board = Board::from(BOARD);
+
+        let robl_eval = robl.eval(&board);
+        let antonius_eval = antonius.eval(&board);
+
+        assert_eq!(HandRankClass::AcesOverJacks, robl_eval.hand_rank.class);
+        assert_eq!(HandRankClass::AceHighFlush, antonius_eval.hand_rank.class);
+        assert!(robl_eval > antonius_eval);
+    }
+
+    #[test]
+    fn best() {
+        let hand = OmahaHigh::from(ANTONIUS_HAND);
+        let board = Board::from(BOARD);
+
+        let actual = hand.best(&board);
+
+        assert_eq!(Two::from([Card::SIX_DIAMONDS, Card::EIGHT_DIAMONDS]), actual.hole);
+        assert_eq!(
+            Three::from([Card::FOUR_DIAMONDS, Card::ACE_DIAMONDS, Card::JACK_DIAMONDS]),
+            actual.board
+        );
+        assert_eq!(Five::from_str("A♦ J♦ 8♦ 6♦ 4♦").unwrap().eval(), actual.eval);
+        assert!(hand.is_valid(&Five::from(BOARD), &actual.eval.hand));
+    }
+
+    /// With one spade in hand on a four flush board hold'em would give us a queen high flush.
+    /// In Omaha we're stuck with our set.
+    #[test]
+    fn best__four_flush_board() {
+        let hand = OmahaHigh::from_str("A♥ Q♠ 7♦ 7♣").unwrap();
+        let board = Board::from_str("K♠ J♠ 8♠ 2♠ 7♥").unwrap();
+
+        let actual = hand.best(&board);
+
+        assert_eq!(HandRankClass::ThreeSevens, actual.eval.hand_rank.class);
+        assert_eq!(Two::from_str("7♦ 7♣").unwrap(), actual.hole);
+        assert_eq!(3, actual.eval.hand.how_many(&board.cards()));
+    }
+
+    #[test]
+    fn best__four_flush_board__needs_two_of_the_suit() {
+        let hand = OmahaHigh::from_str("A♠ T♠ 7♦ 7♣").unwrap();
+        let board = Board::from_str("K♠ J♠ 8♠ 2♠ 7♥").unwrap();
+
+        let actual = hand.best(&board);
+
+        assert_eq!(HandRankClass::AceHighFlush, actual.eval.hand_rank.class);
+        assert_eq!(Two::from_str("A♠ T♠").unwrap(), actual.hole);
+        assert_eq!(Five::from_str("A♠ K♠ J♠ T♠ 8♠").unwrap().eval(), actual.eval);
+    }
+
+    /// Hold'em says four nines with an ace kicker. Omaha says nines full of aces.
+    #[test]
+    fn best__quads_on_board() {
+        let hand = OmahaHigh::from_str("A♠ A♥ 2♦ 3♣").unwrap();
+        let board = Board::from_str("9♠ 9♥ 9♦ 9♣ K♠").unwrap();
+
+        let actual = hand.best(&board);
+
+        assert_eq!(HandRankClass::NinesOverAces, actual.eval.hand_rank.class);
+        assert_eq!(Two::from_str("A♠ A♥").unwrap(), actual.hole);
+    }
+
+    /// A full house on the board plays for everyone in hold'em. Here it's only trips.
+    #[test]
+    fn best__full_house_on_board() {
+        let hand = OmahaHigh::from_str("A♠ Q♥ J♦ 2♣").unwrap();
+        let board = Board::from_str("K♠ K♥ K♦ 7♣ 7♦").unwrap();
+
+        let actual = hand.best(&board);
+
+        assert_eq!(HandRankClass::ThreeKings, actual.eval.hand_rank.class);
+        assert_eq!(Five::from_str("K♠ K♥ K♦ A♠ Q♥").unwrap().eval(), actual.eval);
+    }
+
+    #[test]
+    fn best__matches_permutations() {
+        let hand = OmahaHigh::from_str("A♥ Q♠ 7♦ 7♣").unwrap();
+        let board = Board::from_str("K♠ J♠ 8♠ 2♠ 7♥").unwrap();
+
+        let expected = hand
+            .permutations(&Five::from(board))
+            .iter()
+            .map(Eval::from)
+            .max()
+            .unwrap();
+
+        assert_eq!(expected, hand.best(&board).eval);
+    }
+
+    #[test]
+    fn hole_pairs() {
+        assert_eq!(6, OmahaHigh::from(ROBL_HAND).hole_pairs().len());
+        assert_eq!(10, FiveCardOmaha::default().hole_pairs().len());
+        assert_eq!(15, SixCardOmaha::default().hole_pairs().len());
+    }
+
+    #[test]
+    fn hole_pairs__four_matches_permutations() {
+        let hand = OmahaHigh::from(ROBL_HAND);
+
+        let expected: Vec<Two> = OMAHA_HAND_PERMUTATIONS
+            .iter()
+            .map(|p| Two::from([hand.hand.0[p[0]], hand.hand.0[p[1]]]))
+            .collect();
+
+        assert_eq!(expected, hand.hole_pairs());
+    }
+
+    /// PLO5 showdown:
+    /// Player 1: A♠ A♥ K♦ Q♦ 7♣ - kings and queens, K♦ Q♦ with K♠ Q♠ 7♦
+    /// Player 2: J♠ T♠ 9♣ 8♣ 2♦ - king high flush, J♠ T♠ with K♠ Q♠ 2♠
+    /// Board: K♠ Q♠ 7♦ 3♥ 2♠
+    #[test]
+    fn five_card__showdown() {
+        let board = Board::from_str("K♠ Q♠ 7♦ 3♥ 2♠").unwrap();
+        let player1 = FiveCardOmaha::from_str("A♠ A♥ K♦ Q♦ 7♣").unwrap();
+        let player2 = FiveCardOmaha::from_str("J♠ T♠ 9♣ 8♣ 2♦").unwrap();
+
+        let best1 = player1.best(&board);
+        let best2 = player2.best(&board);
+
+        assert_eq!(HandRankClass::KingsAndQueens, best1.eval.hand_rank.class);
+        assert_eq!("K♦ Q♦", best1.hole.to_string());
+        assert_eq!(HandRankClass::KingHighFlush, best2.eval.hand_rank.class);
+        assert_eq!("K♠ Q♠ J♠ T♠ 2♠", best2.eval.hand.to_string());
+        assert!(best2.eval > best1.eval);
+    }
+
+    /// PLO6: Four hearts in the hand, but you can only play two of them. The Q♥ J♥ make a queen
+    /// high flush with T♥ 9♥ 3♥ from the board. The 9♦ and 2♣ pair the board, but that's it.
+    #[test]
+    fn six_card__best() {
+        let hand = SixCardOmaha::from_str("A♠ K♠ Q♥ J♥ 9♦ 2♣").unwrap();
+        let board = Board::from_str("T♥ 9♥ 8♣ 3♥ 2♥").unwrap();
+
+        let actual = hand.best(&board);
+
+        assert_eq!("Q♥ J♥", actual.hole.to_string());
+        assert_eq!(Five::from_str("Q♥ J♥ T♥ 9♥ 3♥").unwrap().eval(), actual.eval);
+        assert_eq!(2, actual.eval.hand.how_many(&Cards::from(hand.hole_cards())));
+    }
+
+    /// Big O scoop:
+    /// Player 1: A♦ 2♦ 3♣ K♥ K♠ - set of kings and an 8-6-5-2-A low
+    /// Player 2: Q♣ Q♦ J♣ T♦ 4♥ - set of queens, and only one low card so no low
+    /// Board: 5♠ 6♥ 8♣ K♦ Q♥
+    #[test]
+    fn five_card__big_o() {
+        let board = Board::from_str("5♠ 6♥ 8♣ K♦ Q♥").unwrap();
+        let player1 = FiveCardOmaha::from_str("A♦ 2♦ 3♣ K♥ K♠").unwrap();
+        let player2 = FiveCardOmaha::from_str("Q♣ Q♦ J♣ T♦ 4♥").unwrap();
+
+        let hi_lo1 = player1.best_hi_lo(&board);
+        let hi_lo2 = player2.best_hi_lo(&board);
+
+        assert_eq!(HandRankClass::ThreeKings, hi_lo1.high.eval.hand_rank.class);
+        assert_eq!("K♠ K♥", hi_lo1.high.hole.to_string());
+        assert_eq!("8-6-5-2-A", hi_lo1.low.unwrap().to_string());
+        assert_eq!(HandRankClass::ThreeQueens, hi_lo2.high.eval.hand_rank.class);
+        assert!(hi_lo2.low.is_none());
+        assert!(hi_lo1.high.eval > hi_lo2.high.eval);
+    }
+
+    #[test]
+    fn omaha__display() {
+        let hand = SixCardOmaha::from_str("2♣ 9♦ J♥ Q♥ K♠ A♠").unwrap();
+
+        assert_eq!("A♠ K♠ Q♥ J♥ 9♦ 2♣", hand.to_string());
+    }
+
+    #[test]
+    fn omaha__try_from__cards() {
+        assert_eq!(
+            PKError::NotEnoughCards,
+            FiveCardOmaha::try_from(Cards::from_str("AS KS QC JC").unwrap()).unwrap_err()
+        );
+        assert_eq!(
+            PKError::TooManyCards,
+            FiveCardOmaha::try_from(Cards::from_str("AS KS QC JC TC 9C").unwrap()).unwrap_err()
+        );
+        assert_eq!(
+            PKError::TooManyCards,
+            SixCardOmaha::try_from(Cards::from_str("AS KS QC JC TC 9C 8C").unwrap()).unwrap_err()
+        );
+        assert!(SixCardOmaha::try_from(Cards::from_str("AS KS QC JC TC 9C").unwrap()).is_ok());
+    }
+
     #[test]
     fn perm_keys() {
         let expected = ([0, 1], [0, 2, 3]);
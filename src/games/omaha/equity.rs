@@ -0,0 +1,427 @@
+use crate::analysis::case_eval::CaseEval;
+use crate::analysis::case_evals::CaseEvals;
+use crate::analysis::outs::Outs;
+use crate::analysis::sampling::Sampling;
+use crate::arrays::five::Five;
+use crate::cards::Cards;
+use crate::games::omaha::{OmahaHand, OmahaHigh};
+use crate::play::board::Board;
+use crate::util::wincounter::results::Results;
+use crate::util::wincounter::win::Win;
+use crate::util::wincounter::wins::Wins;
+use crate::{PKError, Pile};
+use rayon::prelude::*;
+use std::fmt::{Display, Formatter};
+
+/// `OmahaEquity` is the Omaha version of what `Game::turn_calculations()` gives us for hold'em:
+/// the `CaseEvals` for every remaining runout, the `Wins` and `Results` that come out of them,
+/// and, when there is only the river left to come, each player's `Outs`.
+///
+/// It takes any number of hands that implement `OmahaHand`, so four card `OmahaHigh` for PLO,
+/// `FiveCardOmaha` for PLO5, or `SixCardOmaha` for PLO6, a board that is either empty, a flop,
+/// or a flop and a turn, and any dead cards that have been exposed and are out of play.
+///
+/// From the flop on there are never more than a thousand or so runouts to walk through, so we do
+/// them all. Before the flop, a heads up match has over a million possible boards, each of which
+/// needs sixty evals per player. That's where we punt and take a random sample of runouts
+/// instead. _Don't let the perfect be the enemy of the good._
+///
+/// ```
+/// use pkcore::cards::Cards;
+/// use pkcore::games::omaha::OmahaHigh;
+/// use pkcore::games::omaha::equity::OmahaEquity;
+/// use std::str::FromStr;
+///
+/// let hands = vec![
+///     OmahaHigh::from_str("A♠ K♠ Q♦ J♦").unwrap(),
+///     OmahaHigh::from_str("9♥ 9♣ 8♥ 7♣").unwrap(),
+/// ];
+/// let board = Cards::from_str("9♠ 6♦ 2♥ K♦").unwrap();
+///
+/// let equity = OmahaEquity::new(hands, board, Cards::default()).unwrap();
+///
+/// assert!(equity.is_exhaustive());
+/// assert_eq!(40, equity.case_evals.len());
+/// assert_eq!(10, equity.outs.len_for_player(1));
+/// ```
+#[derive(Clone, Debug, Default)]
+pub struct OmahaEquity<H = OmahaHigh> {
+    pub hands: Vec<H>,
+    pub board: Cards,
+    pub dead: Cards,
+    pub case_evals: CaseEvals,
+    pub wins: Wins,
+    pub results: Results,
+    pub outs: Outs,
+    exhaustive: bool,
+}
+
+impl<H: OmahaHand + Sync> OmahaEquity<H> {
+    /// # Errors
+    ///
+    /// See `OmahaEquity::with_samples()`.
+    pub fn new(hands: Vec<H>, board: Cards, dead: Cards) -> Result<Self, PKError> {
+        OmahaEquity::with_sampling(hands, board, dead, Sampling::default())
+    }
+
+    /// Same as `OmahaEquity::new()` but lets you decide how many runouts to sample if the
+    /// street is too big to walk through completely.
+    ///
+    /// # Errors
+    ///
+    /// * `PKError::NotEnoughHands` if there are fewer than two hands.
+    /// * `PKError::TooManyHands` if there are more hands than `Win::MAX_PLAYERS`.
+    /// * `PKError::NotEnoughCards` if there isn't enough deck left to deal everyone and the board.
+    /// * `PKError::BlankCard` if any of the hands aren't fully dealt.
+    /// * `PKError::InvalidCardCount` if the board isn't empty, a flop, or a flop and a turn.
+    /// * `PKError::DuplicateCard` if the same card shows up in more than one place.
+    pub fn with_samples(hands: Vec<H>, board: Cards, dead: Cards, samples: usize) -> Result<Self, PKError> {
+        OmahaEquity::with_sampling(hands, board, dead, Sampling::new(samples))
+    }
+
+    /// Same as `OmahaEquity::with_samples()` but with a `Sampling`, so that you can seed it and
+    /// get the same sample back every time.
+    ///
+    /// # Errors
+    ///
+    /// See `OmahaEquity::with_samples()`.
+    pub fn with_sampling(hands: Vec<H>, board: Cards, dead: Cards, sampling: Sampling) -> Result<Self, PKError> {
+        let remaining = OmahaEquity::validate(&hands, &board, &dead)?;
+        let needed = 5 - board.len();
+
+        let (cases, exhaustive) = sampling.runouts(&remaining, needed);
+        let case_evals: Vec<CaseEval> = cases
+            .into_par_iter()
+            .filter_map(|case| Self::case_eval(&hands, &board, case).ok())
+            .collect();
+        let case_evals = CaseEvals::from(case_evals);
+
+        let wins = case_evals.wins();
+        let results = Results::from_wins(&wins, hands.len());
+        let outs = if needed == 1 {
+            Outs::from(&case_evals)
+        } else {
+            Outs::default()
+        };
+
+        Ok(Self {
+            hands,
+            board,
+            dead,
+            case_evals,
+            wins,
+            results,
+            outs,
+            exhaustive,
+        })
+    }
+
+    /// Returns the `CaseEval` for a specific runout. The case is whatever cards are needed to
+    /// fill out the board.
+    ///
+    /// # Errors
+    ///
+    /// Throws `PKError::InvalidCardCount` if the board and the case don't add up to five cards.
+    pub fn case_eval(hands: &[H], board: &Cards, case: Cards) -> Result<CaseEval, PKError> {
+        let mut cards = board.clone();
+        cards.insert_all(&case);
+        if cards.len() != 5 {
+            return Err(PKError::InvalidCardCount);
+        }
+        let board = Board::from(Five::try_from(cards)?);
+
+        let mut case_eval = CaseEval::new(case);
+        for hand in hands {
+            case_eval.push(hand.eval(&board));
+        }
+        Ok(case_eval)
+    }
+
+    /// `true` if every possible runout was dealt, `false` if we sampled.
+    #[must_use]
+    pub fn is_exhaustive(&self) -> bool {
+        self.exhaustive
+    }
+}
+
+impl OmahaEquity {
+    /// Makes sure that everything lines up, and returns the `Cards` left in the deck if it does.
+    pub(crate) fn validate<H: OmahaHand>(hands: &[H], board: &Cards, dead: &Cards) -> Result<Cards, PKError> {
+        if hands.len() < 2 {
+            return Err(PKError::NotEnoughHands);
+        }
+        if hands.len() > Win::MAX_PLAYERS {
+            return Err(PKError::TooManyHands);
+        }
+        if !matches!(board.len(), 0 | 3 | 4) {
+            return Err(PKError::InvalidCardCount);
+        }
+
+        let mut used = board.clone();
+        used.insert_all(dead);
+        let mut expected = board.len() + dead.len();
+        for hand in hands {
+            let hole = hand.hole_cards();
+            if hole.iter().any(|card| !card.is_dealt()) {
+                return Err(PKError::BlankCard);
+            }
+            expected += hole.len();
+            used.insert_all(&Cards::from(hole));
+        }
+        if used.len() != expected {
+            return Err(PKError::DuplicateCard);
+        }
+
+        let remaining = Cards::deck_minus(&used);
+        if remaining.len() < 5 - board.len() {
+            return Err(PKError::NotEnoughCards);
+        }
+        Ok(remaining)
+    }
+}
+
+impl<H: OmahaHand> Display for OmahaEquity<H> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let winning_player = self.outs.longest_player();
+
+        writeln!(f, "Board: {}", self.board)?;
+        if !self.dead.is_empty() {
+            writeln!(f, "Dead: {}", self.dead)?;
+        }
+
+        for (i, hand) in self.hands.iter().enumerate() {
+            let player_id = i + 1;
+            writeln!(
+                f,
+                "  Player #{} [{}] {}",
+                player_id,
+                hand,
+                self.results.player_to_string(i)
+            )?;
+
+            if player_id != winning_player {
+                if let Some(cards) = self.outs.get(player_id) {
+                    writeln!(f, "    OUTS: {cards}")?;
+                }
+            }
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+#[allow(non_snake_case)]
+mod games__omaha__equity_tests {
+    use super::*;
+    use crate::card::Card;
+    use crate::games::omaha::{FiveCardOmaha, SixCardOmaha};
+    use std::str::FromStr;
+
+    fn hands(v: &[&str]) -> Vec<OmahaHigh> {
+        v.iter().map(|s| OmahaHigh::from_str(s).unwrap()).collect()
+    }
+
+    /// PLO5 on the turn. Player one's kings and queens are ahead, but player two has a flush
+    /// draw and a wrap. Of the 38 rivers, two wins with seven of the eight spades, since the 7♠
+    /// fills up player one, plus two aces, three jacks, three tens and two nines for the straight.
+    #[test]
+    fn new__turn__five_card() {
+        let hands = vec![
+            FiveCardOmaha::from_str("A♠ A♥ K♦ Q♦ 7♣").unwrap(),
+            FiveCardOmaha::from_str("J♠ T♠ 9♣ 8♣ 2♦").unwrap(),
+        ];
+
+        let equity = OmahaEquity::new(hands, Cards::from_str("K♠ Q♠ 7♦ 3♥").unwrap(), Cards::default()).unwrap();
+
+        assert!(equity.is_exhaustive());
+        assert_eq!(38, equity.case_evals.len());
+        assert_eq!(17, equity.outs.len_for_player(2));
+    }
+
+    /// Eight players at PLO6 need 48 cards, which only leaves four for the board.
+    #[test]
+    fn new__six_card__not_enough_cards() {
+        let hands: Vec<SixCardOmaha> = Cards::deck()
+            .to_vec()
+            .chunks(6)
+            .take(8)
+            .map(|chunk| SixCardOmaha::try_from(Cards::from(chunk.to_vec())).unwrap())
+            .collect();
+
+        assert_eq!(
+            PKError::NotEnoughCards,
+            OmahaEquity::new(hands, Cards::default(), Cards::default()).unwrap_err()
+        );
+    }
+
+    /// One more player than a `PlayerFlag` has bits for. It never gets as far as noticing that
+    /// they're all holding the same cards.
+    #[test]
+    fn new__too_many_hands() {
+        let hands = hands(&["A♠ K♠ Q♦ J♦"; Win::MAX_PLAYERS + 1]);
+
+        assert_eq!(
+            PKError::TooManyHands,
+            OmahaEquity::new(hands, Cards::default(), Cards::default()).unwrap_err()
+        );
+    }
+
+    #[test]
+    fn new__turn() {
+        let equity = OmahaEquity::new(
+            hands(&["A♠ K♠ Q♦ J♦", "9♥ 9♣ 8♥ 7♣"]),
+            Cards::from_str("9♠ 6♦ 2♥ K♦").unwrap(),
+            Cards::default(),
+        )
+        .unwrap();
+
+        // Kings vs a set of nines. Player one needs a diamond that doesn't pair the board, or
+        // a ten for the king high straight. A third king only fills up player two.
+        assert!(equity.is_exhaustive());
+        assert_eq!(40, equity.case_evals.len());
+        assert_eq!(40, equity.results.case_count);
+        assert_eq!((10, 0), equity.results.wins_and_ties(0));
+        assert_eq!((30, 0), equity.results.wins_and_ties(1));
+        assert_eq!(
+            &Cards::from_str("A♦ T♠ T♥ T♦ T♣ 8♦ 7♦ 5♦ 4♦ 3♦").unwrap(),
+            equity.outs.get(1).unwrap()
+        );
+        assert_eq!(30, equity.outs.len_for_player(2));
+    }
+
+    #[test]
+    fn new__turn__dead_cards() {
+        let equity = OmahaEquity::new(
+            hands(&["A♠ K♠ Q♦ J♦", "9♥ 9♣ 8♥ 7♣"]),
+            Cards::from_str("9♠ 6♦ 2♥ K♦").unwrap(),
+            Cards::from_str("T♠ T♥").unwrap(),
+        )
+        .unwrap();
+
+        assert_eq!(38, equity.case_evals.len());
+        assert_eq!((8, 0), equity.results.wins_and_ties(0));
+        assert!(!equity.outs.get(1).unwrap().contains(&Card::TEN_SPADES));
+    }
+
+    /// The board plays for nobody in Omaha, but a split is still a split.
+    #[test]
+    fn new__turn__ties() {
+        let equity = OmahaEquity::new(
+            hands(&["A♠ K♠ 4♦ 3♦", "A♥ K♥ 4♣ 3♣", "8♠ 8♥ 7♦ 7♣"]),
+            Cards::from_str("Q♦ J♣ T♥ 2♠").unwrap(),
+            Cards::default(),
+        )
+        .unwrap();
+
+        // Broadway for the first two on every river. Player three can't get there.
+        assert_eq!((0, 36), equity.results.wins_and_ties(0));
+        assert_eq!((0, 36), equity.results.wins_and_ties(1));
+        assert_eq!((0, 0), equity.results.wins_and_ties(2));
+    }
+
+    #[test]
+    fn new__flop() {
+        let equity = OmahaEquity::new(
+            hands(&["A♠ K♠ Q♦ J♦", "9♥ 9♣ 8♥ 7♣"]),
+            Cards::from_str("9♠ 6♦ 2♥").unwrap(),
+            Cards::default(),
+        )
+        .unwrap();
+
+        let (first, _) = equity.results.wins_and_ties(0);
+        let (second, _) = equity.results.wins_and_ties(1);
+
+        assert!(equity.is_exhaustive());
+        assert_eq!(Sampling::count(41, 2), equity.case_evals.len());
+        assert!(second > first);
+        assert_eq!(0, equity.outs.len_longest());
+    }
+
+    #[test]
+    fn new__preflop__sampled() {
+        let equity = OmahaEquity::with_samples(
+            hands(&["A♠ A♥ K♠ K♥", "7♣ 6♦ 3♣ 2♦"]),
+            Cards::default(),
+            Cards::default(),
+            1_000,
+        )
+        .unwrap();
+
+        assert!(!equity.is_exhaustive());
+        assert_eq!(1_000, equity.case_evals.len());
+        assert!(equity.results.wins_total(0) > equity.results.wins_total(1));
+    }
+
+    #[test]
+    fn with_sampling__seeded() {
+        let equity = |seed: u64| {
+            OmahaEquity::with_sampling(
+                hands(&["A♠ A♥ K♠ K♥", "7♣ 6♦ 3♣ 2♦"]),
+                Cards::default(),
+                Cards::default(),
+                Sampling::new(500).with_seed(seed),
+            )
+            .unwrap()
+        };
+
+        assert_eq!(equity(9).wins, equity(9).wins);
+        assert_eq!(500, equity(9).case_evals.len());
+    }
+
+    #[test]
+    fn new__errors() {
+        let board = Cards::from_str("9♠ 6♦ 2♥").unwrap();
+
+        assert_eq!(
+            PKError::NotEnoughHands,
+            OmahaEquity::new(hands(&["A♠ K♠ Q♦ J♦"]), board.clone(), Cards::default()).unwrap_err()
+        );
+        assert_eq!(
+            PKError::DuplicateCard,
+            OmahaEquity::new(hands(&["A♠ K♠ Q♦ J♦", "9♠ 9♣ 8♥ 7♣"]), board.clone(), Cards::default()).unwrap_err()
+        );
+        assert_eq!(
+            PKError::DuplicateCard,
+            OmahaEquity::new(
+                hands(&["A♠ K♠ Q♦ J♦", "9♥ 9♣ 8♥ 7♣"]),
+                board,
+                Cards::from_str("A♠").unwrap()
+            )
+            .unwrap_err()
+        );
+        assert_eq!(
+            PKError::InvalidCardCount,
+            OmahaEquity::new(
+                hands(&["A♠ K♠ Q♦ J♦", "9♥ 9♣ 8♥ 7♣"]),
+                Cards::from_str("9♠ 6♦").unwrap(),
+                Cards::default()
+            )
+            .unwrap_err()
+        );
+        assert_eq!(
+            PKError::BlankCard,
+            OmahaEquity::new(
+                vec![OmahaHigh::from_str("A♠ K♠ Q♦ J♦").unwrap(), OmahaHigh::default()],
+                Cards::default(),
+                Cards::default()
+            )
+            .unwrap_err()
+        );
+    }
+
+    #[test]
+    fn display() {
+        let equity = OmahaEquity::new(
+            hands(&["A♠ K♠ Q♦ J♦", "9♥ 9♣ 8♥ 7♣"]),
+            Cards::from_str("9♠ 6♦ 2♥ K♦").unwrap(),
+            Cards::default(),
+        )
+        .unwrap();
+
+        let s = equity.to_string();
+
+        assert!(s.contains("Player #1 [A♠ K♠ Q♦ J♦] 25.0%"));
+        assert!(s.contains("OUTS: "));
+    }
+}
@@ -0,0 +1,432 @@
+use crate::PKError;
+use crate::analysis::sampling::Sampling;
+use crate::arrays::five::Five;
+use crate::cards::Cards;
+use crate::games::omaha::equity::OmahaEquity;
+use crate::games::omaha::{OmahaHand, OmahaHiLoEval, OmahaHigh};
+use crate::play::board::Board;
+use crate::util::wincounter::PlayerFlag;
+use crate::util::wincounter::win::Win;
+use crate::util::wincounter::wins::Wins;
+use rayon::prelude::*;
+use std::cmp::Ordering;
+use std::fmt::{Display, Formatter};
+
+/// How much of the pot a player takes home in Omaha/8, broken down by how they got it, and
+/// averaged out over every runout. A share of `1.0` is the whole pot.
+///
+/// * `scoop` - the player took the whole thing, either by winning both halves outright, or
+///   winning the high when nobody qualified for a low.
+/// * `high_only` - the player won the high, or split it when there was no low, and got none of
+///   the low.
+/// * `low_only` - the player won the low outright and got none of the high.
+/// * `quartered` - the player had to split one of the halves with someone else. The classic
+///   example is two players holding the same low, each getting a quarter of the pot.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct HiLoShares {
+    pub scoop: f64,
+    pub high_only: f64,
+    pub low_only: f64,
+    pub quartered: f64,
+}
+
+impl HiLoShares {
+    /// The player's total share of the pot, which is their equity.
+    #[must_use]
+    pub fn equity(&self) -> f64 {
+        self.scoop + self.high_only + self.low_only + self.quartered
+    }
+
+    /// Returns the `PlayerFlag` for the winners of the high, and the `PlayerFlag` for the winners
+    /// of the low, which is zero if nobody qualifies. Each player comes in as their high and
+    /// their low, if they have one, in seat order. It doesn't care what game it is, so Omaha/8
+    /// and Stud/8 both use it.
+    ///
+    /// ```
+    /// use pkcore::games::omaha::hi_lo::HiLoShares;
+    /// use pkcore::util::wincounter::win::Win;
+    ///
+    /// let players = vec![(9, None), (7, Some(3)), (9, Some(3))];
+    ///
+    /// assert_eq!((Win::FIRST | Win::THIRD, Win::SECOND | Win::THIRD), HiLoShares::flags(players));
+    /// ```
+    #[must_use]
+    pub fn flags<H: Ord, L: Ord, I: IntoIterator<Item = (H, Option<L>)>>(players: I) -> (PlayerFlag, PlayerFlag) {
+        let mut best_high: Option<H> = None;
+        let mut best_low: Option<L> = None;
+        let mut high = PlayerFlag::default();
+        let mut low = PlayerFlag::default();
+
+        for (i, (h, l)) in players.into_iter().enumerate() {
+            match best_high.as_ref().map(|best| h.cmp(best)) {
+                None | Some(Ordering::Greater) => {
+                    best_high = Some(h);
+                    high = Win::from_index(i);
+                }
+                Some(Ordering::Equal) => high = Win::or(high, Win::from_index(i)),
+                Some(Ordering::Less) => {}
+            }
+            if let Some(l) = l {
+                match best_low.as_ref().map(|best| l.cmp(best)) {
+                    None | Some(Ordering::Greater) => {
+                        best_low = Some(l);
+                        low = Win::from_index(i);
+                    }
+                    Some(Ordering::Equal) => low = Win::or(low, Win::from_index(i)),
+                    Some(Ordering::Less) => {}
+                }
+            }
+        }
+        (high, low)
+    }
+
+    /// Adds the share that the player at `index` gets for a single runout.
+    #[allow(clippy::cast_precision_loss)]
+    pub(crate) fn add(&mut self, index: usize, high: PlayerFlag, low: PlayerFlag) {
+        let player = Win::from_index(index);
+        let high_winners = high.count_ones();
+        let low_winners = low.count_ones();
+        let won_high = high & player == player;
+        let won_low = low_winners > 0 && low & player == player;
+
+        let high_pot = if low_winners == 0 { 1.0 } else { 0.5 };
+        let high_share = if won_high {
+            high_pot / f64::from(high_winners)
+        } else {
+            0.0
+        };
+        let low_share = if won_low { 0.5 / f64::from(low_winners) } else { 0.0 };
+        let share = high_share + low_share;
+
+        let sole_high = won_high && high_winners == 1;
+        let sole_low = won_low && low_winners == 1;
+        if sole_high && (low_winners == 0 || sole_low) {
+            self.scoop += share;
+        } else if (won_high && high_winners > 1 && low_winners > 0) || (won_low && low_winners > 1) {
+            self.quartered += share;
+        } else if won_high {
+            self.high_only += share;
+        } else if won_low {
+            self.low_only += share;
+        }
+    }
+
+    pub(crate) fn divide(&mut self, count: f64) {
+        self.scoop /= count;
+        self.high_only /= count;
+        self.low_only /= count;
+        self.quartered /= count;
+    }
+}
+
+impl Display for HiLoShares {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{:.1}% (scoop {:.1}% / high {:.1}% / low {:.1}% / quartered {:.1}%)",
+            self.equity() * 100.0,
+            self.scoop * 100.0,
+            self.high_only * 100.0,
+            self.low_only * 100.0,
+            self.quartered * 100.0
+        )
+    }
+}
+
+/// Split pot equity for Omaha Hi-Lo. It uses the same runouts as `OmahaEquity`, exhaustive where
+/// that's possible and sampled where it's not, but instead of a single `Wins` for the pot, it
+/// keeps one for the high and one for the low. When nobody makes a low for a runout the low
+/// `PlayerFlag` is zero and the high takes the whole pot.
+///
+/// ```
+/// use pkcore::cards::Cards;
+/// use pkcore::games::omaha::OmahaHigh;
+/// use pkcore::games::omaha::hi_lo::OmahaHiLoEquity;
+/// use std::str::FromStr;
+///
+/// let hands = vec![
+///     OmahaHigh::from_str("A♠ 2♠ K♦ K♣").unwrap(),
+///     OmahaHigh::from_str("Q♥ Q♣ J♥ T♣").unwrap(),
+/// ];
+/// let board = Cards::from_str("3♥ 5♦ Q♠ 8♣").unwrap();
+///
+/// let equity = OmahaHiLoEquity::new(hands, board, Cards::default()).unwrap();
+///
+/// // Player two's set of queens is good for the high, but player one has the nut low locked up.
+/// assert_eq!(40, equity.case_count);
+/// assert!(equity.shares[0].low_only > 0.0);
+/// ```
+#[derive(Clone, Debug, Default)]
+pub struct OmahaHiLoEquity<H = OmahaHigh> {
+    pub hands: Vec<H>,
+    pub board: Cards,
+    pub dead: Cards,
+    pub case_count: usize,
+    pub high_wins: Wins,
+    pub low_wins: Wins,
+    pub shares: Vec<HiLoShares>,
+    exhaustive: bool,
+}
+
+impl<H: OmahaHand + Sync> OmahaHiLoEquity<H> {
+    /// # Errors
+    ///
+    /// See `OmahaEquity::with_samples()`.
+    pub fn new(hands: Vec<H>, board: Cards, dead: Cards) -> Result<Self, PKError> {
+        Self::with_sampling(hands, board, dead, Sampling::default())
+    }
+
+    /// # Errors
+    ///
+    /// See `OmahaEquity::with_samples()`.
+    pub fn with_samples(hands: Vec<H>, board: Cards, dead: Cards, samples: usize) -> Result<Self, PKError> {
+        Self::with_sampling(hands, board, dead, Sampling::new(samples))
+    }
+
+    /// # Errors
+    ///
+    /// See `OmahaEquity::with_samples()`.
+    #[allow(clippy::cast_precision_loss)]
+    pub fn with_sampling(hands: Vec<H>, board: Cards, dead: Cards, sampling: Sampling) -> Result<Self, PKError> {
+        let remaining = OmahaEquity::validate(&hands, &board, &dead)?;
+        let (cases, exhaustive) = sampling.runouts(&remaining, 5 - board.len());
+
+        let flags: Vec<(PlayerFlag, PlayerFlag)> = cases
+            .into_par_iter()
+            .filter_map(|case| Self::case_flags(&hands, &board, &case).ok())
+            .collect();
+
+        let mut high_wins = Wins::default();
+        let mut low_wins = Wins::default();
+        let mut shares = vec![HiLoShares::default(); hands.len()];
+        for (high, low) in &flags {
+            high_wins.add(*high);
+            low_wins.add(*low);
+            for (i, share) in shares.iter_mut().enumerate() {
+                share.add(i, *high, *low);
+            }
+        }
+        if !flags.is_empty() {
+            for share in &mut shares {
+                share.divide(flags.len() as f64);
+            }
+        }
+
+        Ok(Self {
+            hands,
+            board,
+            dead,
+            case_count: flags.len(),
+            high_wins,
+            low_wins,
+            shares,
+            exhaustive,
+        })
+    }
+
+    /// Returns the `OmahaHiLoEval` for every player for a specific runout.
+    ///
+    /// # Errors
+    ///
+    /// Throws `PKError::InvalidCardCount` if the board and the case don't add up to five cards.
+    pub fn case_evals(hands: &[H], board: &Cards, case: &Cards) -> Result<Vec<OmahaHiLoEval>, PKError> {
+        let mut cards = board.clone();
+        cards.insert_all(case);
+        if cards.len() != 5 {
+            return Err(PKError::InvalidCardCount);
+        }
+        let board = Board::from(Five::try_from(cards)?);
+
+        Ok(hands.iter().map(|hand| hand.best_hi_lo(&board)).collect())
+    }
+
+    /// Returns the `PlayerFlag` for the winners of the high, and the `PlayerFlag` for the winners
+    /// of the low, which is zero if nobody qualifies.
+    ///
+    /// # Errors
+    ///
+    /// Throws `PKError::InvalidCardCount` if the board and the case don't add up to five cards.
+    pub fn case_flags(hands: &[H], board: &Cards, case: &Cards) -> Result<(PlayerFlag, PlayerFlag), PKError> {
+        let evals = Self::case_evals(hands, board, case)?;
+
+        Ok(HiLoShares::flags(evals.iter().map(|e| (e.high.eval, e.low))))
+    }
+
+    /// `true` if every possible runout was dealt, `false` if we sampled.
+    #[must_use]
+    pub fn is_exhaustive(&self) -> bool {
+        self.exhaustive
+    }
+}
+
+impl<H: OmahaHand> Display for OmahaHiLoEquity<H> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        writeln!(f, "Board: {}", self.board)?;
+        if !self.dead.is_empty() {
+            writeln!(f, "Dead: {}", self.dead)?;
+        }
+
+        for (i, hand) in self.hands.iter().enumerate() {
+            let shares = self.shares.get(i).copied().unwrap_or_default();
+            writeln!(f, "  Player #{} [{}] {}", i + 1, hand, shares)?;
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+#[allow(non_snake_case)]
+mod games__omaha__hi_lo_tests {
+    use super::*;
+    use std::str::FromStr;
+
+    fn hands(v: &[&str]) -> Vec<OmahaHigh> {
+        v.iter().map(|s| OmahaHigh::from_str(s).unwrap()).collect()
+    }
+
+    fn assert_close(expected: f64, actual: f64) {
+        assert!((expected - actual).abs() < 1e-9, "expected {expected} got {actual}");
+    }
+
+    #[test]
+    fn best_hi_lo() {
+        let hand = OmahaHigh::from_str("A♠ 2♠ K♦ K♣").unwrap();
+        let board = Board::from_str("3♥ 5♦ Q♠ 8♣ K♥").unwrap();
+
+        let actual = hand.best_hi_lo(&board);
+
+        assert_eq!("K♥ K♦ K♣ Q♠ 8♣", actual.high.eval.hand.to_string());
+        assert_eq!("8-5-3-2-A", actual.low.unwrap().to_string());
+        assert!(actual.low.unwrap().hand.to_string().starts_with("A♠ 2♠"));
+    }
+
+    #[test]
+    fn best_low__needs_two_hole_cards() {
+        // Four low cards on the board, but only one in hand. No low.
+        let hand = OmahaHigh::from_str("A♠ K♠ K♦ K♣").unwrap();
+        let board = Board::from_str("2♥ 3♦ 4♠ 5♣ K♥").unwrap();
+
+        assert!(hand.best_low(&board).is_none());
+    }
+
+    #[test]
+    fn best_low__counterfeit() {
+        // A♠ 2♠ on a board with an ace and a deuce still makes a low, just a worse one.
+        let hand = OmahaHigh::from_str("A♠ 2♠ 3♦ K♣").unwrap();
+        let board = Board::from_str("A♥ 2♦ 7♠ 8♣ K♥").unwrap();
+
+        assert_eq!("8-7-3-2-A", hand.best_low(&board).unwrap().to_string());
+    }
+
+    #[test]
+    fn best_low__no_low_board() {
+        let hand = OmahaHigh::from_str("A♠ 2♠ 3♦ 4♣").unwrap();
+        let board = Board::from_str("9♥ T♦ 7♠ 8♣ K♥").unwrap();
+
+        assert!(hand.best_low(&board).is_none());
+    }
+
+    #[test]
+    fn case_flags() {
+        let hands = hands(&["A♠ 2♠ K♦ K♣", "A♥ 2♥ Q♦ Q♣", "J♠ J♥ T♦ 9♣"]);
+        let board = Cards::from_str("3♥ 5♦ Q♠ 8♣").unwrap();
+
+        let (high, low) = OmahaHiLoEquity::case_flags(&hands, &board, &Cards::from_str("K♥").unwrap()).unwrap();
+
+        assert_eq!(Win::FIRST, high);
+        assert_eq!(Win::FIRST | Win::SECOND, low);
+
+        let (high, low) = OmahaHiLoEquity::case_flags(&hands, &board, &Cards::from_str("Q♥").unwrap()).unwrap();
+
+        assert_eq!(Win::SECOND, high);
+        assert_eq!(Win::FIRST | Win::SECOND, low);
+
+        let (high, low) = OmahaHiLoEquity::case_flags(&hands, &board, &Cards::from_str("T♥").unwrap()).unwrap();
+
+        assert_eq!(Win::THIRD, high);
+        assert_eq!(Win::FIRST | Win::SECOND, low);
+    }
+
+    #[test]
+    fn hi_lo_shares__add() {
+        let mut scoop = HiLoShares::default();
+        scoop.add(0, Win::FIRST, Win::FIRST);
+        scoop.add(0, Win::FIRST, 0);
+        assert_close(2.0, scoop.scoop);
+
+        let mut split = HiLoShares::default();
+        split.add(0, Win::FIRST, Win::SECOND);
+        split.add(1, Win::FIRST, Win::SECOND);
+        assert_close(0.5, split.high_only);
+
+        let mut low = HiLoShares::default();
+        low.add(1, Win::FIRST, Win::SECOND);
+        assert_close(0.5, low.low_only);
+
+        let mut quartered = HiLoShares::default();
+        quartered.add(0, Win::THIRD, Win::FIRST | Win::SECOND);
+        assert_close(0.25, quartered.quartered);
+        assert_close(0.25, quartered.equity());
+
+        // Win the high, get quartered on the low.
+        let mut three_quarters = HiLoShares::default();
+        three_quarters.add(0, Win::FIRST, Win::FIRST | Win::SECOND);
+        assert_close(0.75, three_quarters.quartered);
+
+        // Chop the high with no low is just a chop.
+        let mut chop = HiLoShares::default();
+        chop.add(0, Win::FIRST | Win::SECOND, 0);
+        assert_close(0.5, chop.high_only);
+    }
+
+    /// Both players have A-2 for the nut low. Player one's kings are ahead for the high.
+    #[test]
+    fn new__turn__quartered() {
+        let equity = OmahaHiLoEquity::new(
+            hands(&["A♠ 2♠ K♦ K♣", "A♥ 2♥ Q♦ J♣"]),
+            Cards::from_str("3♥ 5♦ 9♠ 8♣").unwrap(),
+            Cards::default(),
+        )
+        .unwrap();
+
+        assert!(equity.is_exhaustive());
+        assert_eq!(40, equity.case_count);
+        assert_eq!(40, equity.high_wins.len());
+        assert_eq!(40, equity.low_wins.len());
+
+        let total: f64 = equity.shares.iter().map(HiLoShares::equity).sum();
+        assert_close(1.0, total);
+        assert!(equity.shares[0].quartered > 0.0);
+        assert!(equity.shares[1].quartered > 0.0);
+        assert!(equity.shares[0].equity() > equity.shares[1].equity());
+    }
+
+    #[test]
+    fn new__flop__shares_add_up() {
+        let equity = OmahaHiLoEquity::new(
+            hands(&["A♠ 2♠ K♦ K♣", "Q♥ Q♣ J♥ T♣", "7♥ 6♥ 4♣ 3♣"]),
+            Cards::from_str("3♥ 5♦ Q♠").unwrap(),
+            Cards::default(),
+        )
+        .unwrap();
+
+        let total: f64 = equity.shares.iter().map(HiLoShares::equity).sum();
+
+        assert_eq!(Sampling::count(37, 2), equity.case_count);
+        assert_close(1.0, total);
+    }
+
+    #[test]
+    fn display() {
+        let equity = OmahaHiLoEquity::new(
+            hands(&["A♠ 2♠ K♦ K♣", "A♥ 2♥ Q♦ J♣"]),
+            Cards::from_str("3♥ 5♦ 9♠ 8♣").unwrap(),
+            Cards::default(),
+        )
+        .unwrap();
+
+        assert!(equity.to_string().contains("Player #1 [A♠ K♦ K♣ 2♠]"));
+        assert!(equity.to_string().contains("quartered"));
+    }
+}
@@ -1 +1,2 @@
 pub mod california;
+pub mod equity;
@@ -0,0 +1,430 @@
+use crate::PKError;
+use crate::analysis::sampling::Sampling;
+use crate::arrays::HandRanker;
+use crate::arrays::seven::Seven;
+use crate::cards::Cards;
+use crate::games::razz::california::{CaliforniaHandRankValue, NO_RAZZ_HAND_RANK_VALUE};
+use crate::games::stud::{StudHand, StudStreet};
+use crate::util::wincounter::PlayerFlag;
+use crate::util::wincounter::results::Results;
+use crate::util::wincounter::win::Win;
+use crate::util::wincounter::wins::Wins;
+use rayon::prelude::*;
+use std::fmt::{Display, Formatter};
+
+/// Who's favored in a Razz hand before it's over.
+///
+/// Each player is a `StudHand`: what they're showing, plus their down cards if we know them.
+/// Add in the upcards that have been folded, which are dead, and the street, and we deal out
+/// every way the hands could finish. Unknown down cards are dealt from the same stub as the cards
+/// still to come, since as far as we're concerned they're just as random.
+///
+/// On sixth street heads up that's only a couple thousand completions, so we do them all. Any
+/// earlier and the numbers explode, so we take a random sample like `OmahaEquity` does.
+///
+/// Hands are compared by `CaliforniaHandRankValue`, where lower is better. We don't worry about
+/// the common card. If eight players make it to seventh street, they aren't in a Razz game we
+/// want to be calculating odds for.
+///
+/// ```
+/// use pkcore::cards::Cards;
+/// use pkcore::games::razz::equity::RazzEquity;
+/// use pkcore::games::stud::{StudHand, StudStreet};
+/// use std::str::FromStr;
+///
+/// let hands = vec![
+///     StudHand::new(Cards::from_str("A♠ 2♠").unwrap(), Cards::from_str("3♥ 4♦ 5♣ K♠").unwrap()),
+///     StudHand::new(Cards::from_str("A♥ 2♥").unwrap(), Cards::from_str("3♦ 4♣ 6♠ Q♠").unwrap()),
+/// ];
+///
+/// let equity = RazzEquity::new(hands, Cards::default(), StudStreet::Sixth).unwrap();
+///
+/// // Player one has the wheel. The best player two can do is catch a five and chop.
+/// assert!(equity.is_exhaustive());
+/// assert_eq!(1_560, equity.case_count);
+/// assert_eq!((1_443, 117), equity.results.wins_and_ties(0));
+/// ```
+#[derive(Clone, Debug, Default)]
+pub struct RazzEquity {
+    pub hands: Vec<StudHand>,
+    pub dead: Cards,
+    pub street: Option<StudStreet>,
+    pub case_count: usize,
+    pub wins: Wins,
+    pub results: Results,
+    exhaustive: bool,
+}
+
+impl RazzEquity {
+    /// # Errors
+    ///
+    /// See `RazzEquity::with_samples()`.
+    pub fn new(hands: Vec<StudHand>, dead: Cards, street: StudStreet) -> Result<Self, PKError> {
+        RazzEquity::with_sampling(hands, dead, street, Sampling::default())
+    }
+
+    /// Same as `RazzEquity::new()` but lets you decide how many completions to sample if there
+    /// are too many to deal out.
+    ///
+    /// # Errors
+    ///
+    /// * `PKError::NotEnoughHands` if there are fewer than two hands.
+    /// * `PKError::NotEnoughCards` if there aren't enough cards left to finish everyone's hand.
+    /// * `PKError::InvalidCardCount` if a hand doesn't match the street.
+    /// * `PKError::DuplicateCard` if the same card shows up in more than one place.
+    pub fn with_samples(
+        hands: Vec<StudHand>,
+        dead: Cards,
+        street: StudStreet,
+        samples: usize,
+    ) -> Result<Self, PKError> {
+        RazzEquity::with_sampling(hands, dead, street, Sampling::new(samples))
+    }
+
+    /// Same as `RazzEquity::with_samples()` but with a `Sampling`, so that you can seed it.
+    ///
+    /// # Errors
+    ///
+    /// See `RazzEquity::with_samples()`.
+    pub fn with_sampling(
+        hands: Vec<StudHand>,
+        dead: Cards,
+        street: StudStreet,
+        sampling: Sampling,
+    ) -> Result<Self, PKError> {
+        let remaining = RazzEquity::validate(&hands, &dead, street)?;
+        let (cases, exhaustive) = RazzEquity::completions(&hands, &remaining, sampling)?;
+
+        let flags: Vec<PlayerFlag> = cases
+            .into_par_iter()
+            .filter_map(|case| RazzEquity::case_flag(&hands, &case).ok())
+            .collect();
+
+        let mut wins = Wins::default();
+        for flag in &flags {
+            wins.add(*flag);
+        }
+        let results = Results::from_wins(&wins, hands.len());
+
+        Ok(RazzEquity {
+            hands,
+            dead,
+            street: Some(street),
+            case_count: flags.len(),
+            wins,
+            results,
+            exhaustive,
+        })
+    }
+
+    /// Returns the `PlayerFlag` for who has the best low once each player's `case` cards have
+    /// been added to their hand.
+    ///
+    /// # Errors
+    ///
+    /// `PKError::NotEnoughCards` or `PKError::TooManyCards` if a hand doesn't come out to seven.
+    pub fn case_flag(hands: &[StudHand], case: &[Cards]) -> Result<PlayerFlag, PKError> {
+        let mut values = Vec::with_capacity(hands.len());
+        for (hand, cards) in hands.iter().zip(case) {
+            let mut seven = hand.known();
+            seven.insert_all(cards);
+            values.push(RazzEquity::hand_rank_value(&Seven::try_from(seven)?));
+        }
+
+        let best = values
+            .iter()
+            .filter(|v| **v != NO_RAZZ_HAND_RANK_VALUE)
+            .min()
+            .copied()
+            .unwrap_or(NO_RAZZ_HAND_RANK_VALUE);
+
+        let mut flag = PlayerFlag::default();
+        for (i, value) in values.iter().enumerate() {
+            if *value == best {
+                flag = Win::or(flag, Win::from_index(i));
+            }
+        }
+        Ok(flag)
+    }
+
+    /// The best five card low out of seven. Lower is better.
+    #[must_use]
+    pub fn hand_rank_value(seven: &Seven) -> CaliforniaHandRankValue {
+        seven.razz_hand_rank_value_and_hand().0
+    }
+
+    /// `true` if every possible completion was dealt, `false` if we sampled.
+    #[must_use]
+    pub fn is_exhaustive(&self) -> bool {
+        self.exhaustive
+    }
+
+    /// The number of different ways to finish the hands, capped at `usize::MAX`.
+    #[must_use]
+    pub fn completion_count(remaining: usize, needs: &[usize]) -> usize {
+        let mut count: usize = 1;
+        let mut left = remaining;
+        for need in needs {
+            count = count.saturating_mul(Sampling::count(left, *need));
+            left = left.saturating_sub(*need);
+        }
+        count
+    }
+
+    /// Returns the cards each player still needs to get to seven, for every possible completion
+    /// of the hands if there are no more than `Sampling::EXHAUSTIVE_LIMIT` of them, or for
+    /// `sampling.samples` random ones if there are. The flag is `true` if the completions are the
+    /// real deal.
+    ///
+    /// # Errors
+    ///
+    /// `PKError::NotEnoughCards` if the deck runs out while sampling, which `validate()` should
+    /// have already caught.
+    pub(crate) fn completions(
+        hands: &[StudHand],
+        remaining: &Cards,
+        sampling: Sampling,
+    ) -> Result<(Vec<Vec<Cards>>, bool), PKError> {
+        let needs: Vec<usize> = hands.iter().map(|hand| 7 - hand.known().len()).collect();
+
+        if Sampling::is_exhaustive(RazzEquity::completion_count(remaining.len(), &needs)) {
+            let mut cases = Vec::new();
+            RazzEquity::enumerate(remaining, &needs, &mut Vec::new(), &mut cases);
+            Ok((cases, true))
+        } else {
+            let mut rng = sampling.rng();
+            let mut cases = Vec::with_capacity(sampling.samples);
+            for _ in 0..sampling.samples {
+                let mut deck = Sampling::shuffle(remaining, &mut rng);
+                let mut case = Vec::with_capacity(needs.len());
+                for need in &needs {
+                    case.push(deck.draw(*need)?);
+                }
+                cases.push(case);
+            }
+            Ok((cases, false))
+        }
+    }
+
+    fn enumerate(remaining: &Cards, needs: &[usize], case: &mut Vec<Cards>, cases: &mut Vec<Vec<Cards>>) {
+        match needs.split_first() {
+            None => cases.push(case.clone()),
+            Some((need, rest)) => {
+                for combination in remaining.combinations(*need) {
+                    let drawn = Cards::from(combination);
+                    let left = remaining.minus(&drawn);
+                    case.push(drawn);
+                    RazzEquity::enumerate(&left, rest, case, cases);
+                    case.pop();
+                }
+            }
+        }
+    }
+
+    /// Makes sure that everything lines up, and returns the `Cards` left in the deck if it does.
+    pub(crate) fn validate(hands: &[StudHand], dead: &Cards, street: StudStreet) -> Result<Cards, PKError> {
+        if hands.len() < 2 {
+            return Err(PKError::NotEnoughHands);
+        }
+
+        let mut used = dead.clone();
+        let mut expected = dead.len();
+        for hand in hands {
+            hand.validate(street)?;
+            used.insert_all(&hand.known());
+            expected += hand.down.len() + hand.up.len();
+        }
+        if used.len() != expected {
+            return Err(PKError::DuplicateCard);
+        }
+
+        let remaining = Cards::deck_minus(&used);
+        let needed: usize = hands.iter().map(|hand| 7 - hand.known().len()).sum();
+        if remaining.len() < needed {
+            return Err(PKError::NotEnoughCards);
+        }
+        Ok(remaining)
+    }
+}
+
+impl Display for RazzEquity {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        if let Some(street) = self.street {
+            writeln!(f, "{street}")?;
+        }
+        if !self.dead.is_empty() {
+            writeln!(f, "Dead: {}", self.dead)?;
+        }
+
+        for (i, hand) in self.hands.iter().enumerate() {
+            writeln!(f, "  Player #{} {} {}", i + 1, hand, self.results.player_to_string(i))?;
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+#[allow(non_snake_case)]
+mod games__razz__equity_tests {
+    use super::*;
+    use std::str::FromStr;
+
+    fn hand(down: &str, up: &str) -> StudHand {
+        StudHand::new(Cards::from_str(down).unwrap_or_default(), Cards::from_str(up).unwrap())
+    }
+
+    fn wheel_vs_six() -> Vec<StudHand> {
+        vec![hand("A♠ 2♠", "3♥ 4♦ 5♣ K♠"), hand("A♥ 2♥", "3♦ 4♣ 6♠ Q♠")]
+    }
+
+    #[test]
+    fn new__seventh() {
+        let hands = vec![hand("A♠ 2♠ K♥", "3♥ 4♦ 5♣ K♠"), hand("A♥ 2♥ Q♥", "3♦ 4♣ 6♠ Q♠")];
+
+        let equity = RazzEquity::new(hands, Cards::default(), StudStreet::Seventh).unwrap();
+
+        assert!(equity.is_exhaustive());
+        assert_eq!(1, equity.case_count);
+        assert_eq!((1, 0), equity.results.wins_and_ties(0));
+        assert_eq!((0, 0), equity.results.wins_and_ties(1));
+    }
+
+    /// 40 cards left, so 40 x 39 ways to finish. Three fives let player two tie.
+    #[test]
+    fn new__sixth() {
+        let equity = RazzEquity::new(wheel_vs_six(), Cards::default(), StudStreet::Sixth).unwrap();
+
+        assert_eq!(1_560, equity.case_count);
+        assert_eq!((1_443, 117), equity.results.wins_and_ties(0));
+        assert_eq!((0, 117), equity.results.wins_and_ties(1));
+    }
+
+    /// Two of the fives are dead, which leaves 38 x 37 completions with only 37 ties.
+    #[test]
+    fn new__sixth__dead_cards() {
+        let dead = Cards::from_str("5♠ 5♥").unwrap();
+
+        let equity = RazzEquity::new(wheel_vs_six(), dead, StudStreet::Sixth).unwrap();
+
+        assert_eq!(1_406, equity.case_count);
+        assert_eq!((1_369, 37), equity.results.wins_and_ties(0));
+    }
+
+    /// Three way on seventh street, where players one and two chop with a 7-6-4-3-2 and
+    /// player three is stuck with a pair.
+    #[test]
+    fn new__seventh__three_way_chop() {
+        let hands = vec![
+            hand("7♠ 2♠ K♥", "3♥ 4♦ 6♣ K♠"),
+            hand("7♥ 2♥ Q♥", "3♦ 4♣ 6♠ Q♠"),
+            hand("A♥ A♦ 9♥", "A♣ 5♥ 5♦ 8♠"),
+        ];
+
+        let equity = RazzEquity::new(hands, Cards::default(), StudStreet::Seventh).unwrap();
+
+        assert_eq!(1, equity.case_count);
+        assert_eq!((0, 1), equity.results.wins_and_ties(0));
+        assert_eq!((0, 1), equity.results.wins_and_ties(1));
+        assert_eq!(0, equity.results.wins_total(2));
+    }
+
+    /// Fourth street with player two's down cards unknown needs 3 + 5 cards from 44. That's
+    /// well over the limit.
+    #[test]
+    fn new__fourth__sampled() {
+        let hands = vec![hand("A♠ 2♠", "3♥ 4♦"), hand("", "K♠ Q♥")];
+
+        let equity = RazzEquity::with_samples(hands, Cards::default(), StudStreet::Fourth, 1_000).unwrap();
+
+        assert!(!equity.is_exhaustive());
+        assert_eq!(1_000, equity.case_count);
+        assert!(equity.results.wins_total(0) > equity.results.wins_total(1));
+    }
+
+    #[test]
+    fn new__errors() {
+        assert_eq!(
+            PKError::NotEnoughHands,
+            RazzEquity::new(vec![hand("A♠ 2♠", "3♥")], Cards::default(), StudStreet::Third).unwrap_err()
+        );
+        assert_eq!(
+            PKError::InvalidCardCount,
+            RazzEquity::new(
+                vec![hand("A♠ 2♠", "3♥ 4♥"), hand("A♥ 2♥", "3♦")],
+                Cards::default(),
+                StudStreet::Third
+            )
+            .unwrap_err()
+        );
+        assert_eq!(
+            PKError::InvalidCardCount,
+            RazzEquity::new(
+                vec![hand("A♠ 2♠ 5♠", "3♥"), hand("A♥ 2♥", "3♦")],
+                Cards::default(),
+                StudStreet::Third
+            )
+            .unwrap_err()
+        );
+        assert_eq!(
+            PKError::DuplicateCard,
+            RazzEquity::new(
+                vec![hand("A♠ 2♠", "3♥"), hand("A♥ 2♥", "3♦")],
+                Cards::from_str("3♥").unwrap(),
+                StudStreet::Third
+            )
+            .unwrap_err()
+        );
+    }
+
+    /// Eight players on third street need 32 more cards, and there are only 28 left.
+    #[test]
+    fn new__not_enough_cards() {
+        let hands = ["A", "K", "Q", "J", "T", "9", "8", "7"]
+            .iter()
+            .map(|rank| hand(&format!("{rank}♠ {rank}♥"), &format!("{rank}♦")))
+            .collect();
+
+        assert_eq!(
+            PKError::NotEnoughCards,
+            RazzEquity::new(hands, Cards::default(), StudStreet::Third).unwrap_err()
+        );
+    }
+
+    #[test]
+    fn case_flag() {
+        let hands = wheel_vs_six();
+
+        let tie = vec![Cards::from_str("9♠").unwrap(), Cards::from_str("5♠").unwrap()];
+        let win = vec![Cards::from_str("9♠").unwrap(), Cards::from_str("8♠").unwrap()];
+
+        assert_eq!(Win::FIRST | Win::SECOND, RazzEquity::case_flag(&hands, &tie).unwrap());
+        assert_eq!(Win::FIRST, RazzEquity::case_flag(&hands, &win).unwrap());
+    }
+
+    #[test]
+    fn completion_count() {
+        assert_eq!(1_560, RazzEquity::completion_count(40, &[1, 1]));
+        assert_eq!(1, RazzEquity::completion_count(38, &[0, 0]));
+        assert_eq!(13_244 * 749_398, RazzEquity::completion_count(44, &[3, 5]));
+    }
+
+    #[test]
+    fn hand_rank_value() {
+        let wheel = Seven::from_str("A♠ 2♠ 3♥ 4♦ 5♣ K♠ K♥").unwrap();
+        let six = Seven::from_str("A♥ 2♥ 3♦ 4♣ 6♠ Q♠ Q♥").unwrap();
+
+        assert_eq!(1, RazzEquity::hand_rank_value(&wheel));
+        assert!(RazzEquity::hand_rank_value(&six) > RazzEquity::hand_rank_value(&wheel));
+    }
+
+    #[test]
+    fn display() {
+        let equity = RazzEquity::new(wheel_vs_six(), Cards::default(), StudStreet::Sixth).unwrap();
+
+        let s = equity.to_string();
+
+        assert!(s.starts_with("Sixth Street\n"));
+        assert!(s.contains("Player #1 [A♠ 2♠] 3♥ 4♦ 5♣ K♠"));
+    }
+}
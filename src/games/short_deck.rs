@@ -0,0 +1,403 @@
+use crate::analysis::case_eval::CaseEval;
+use crate::analysis::case_evals::CaseEvals;
+use crate::analysis::class::HandRankClass;
+use crate::analysis::eval::Eval;
+use crate::analysis::hand_rank::{HandRank, HandRankValue, NO_HAND_RANK_VALUE};
+use crate::analysis::name::HandRankName;
+use crate::analysis::store::db::hup::HUPResult;
+use crate::arrays::HandRanker;
+use crate::arrays::five::Five;
+use crate::arrays::matchups::sorted_heads_up::SortedHeadsUp;
+use crate::arrays::seven::Seven;
+use crate::arrays::two::Two;
+use crate::card::Card;
+use crate::cards::Cards;
+use crate::play::hole_cards::HoleCards;
+use crate::rank::Rank;
+use crate::{PKError, Pile};
+use rayon::prelude::*;
+
+/// Short Deck, or Six Plus Hold'em, is hold'em played with the deuces through fives stripped
+/// out of the deck. That leaves 36 cards, and with fewer cards two things change:
+///
+/// * Flushes are harder to make than full houses, so they rank above them.
+/// * The ace plays low with the six, so A-6-7-8-9 is the bottom straight, taking the place of the wheel.
+///
+/// Rather than build a new set of lookup tables, we take the Cactus Kev `HandRankValue` and move
+/// it around. Full houses (167-322) slide down below the flushes, the flushes (323-1599) slide up
+/// to take their place, and A-6-7-8-9 gets the value of a nine high straight, which can't
+/// otherwise exist without a five in the deck. Everything else stays where it is.
+///
+/// Some rooms also rank trips over straights. We don't. Most of the big games have settled on
+/// straights beating trips, and that's what we play.
+///
+/// The `HandRankName` and `HandRankClass` are still the standard ones, so a `HandRank` from here
+/// will happily tell you it's a `Flush` even though its value is smaller than a full house.
+pub struct ShortDeck;
+
+impl ShortDeck {
+    pub const DECK_SIZE: usize = 36;
+
+    /// The ranks left in the deck, from the top down.
+    pub const RANKS: [Rank; 9] = [
+        Rank::ACE,
+        Rank::KING,
+        Rank::QUEEN,
+        Rank::JACK,
+        Rank::TEN,
+        Rank::NINE,
+        Rank::EIGHT,
+        Rank::SEVEN,
+        Rank::SIX,
+    ];
+
+    /// A-9-8-7-6, as `Five::or_rank_bits()` sees it.
+    pub const A6789_OR_BITS: u32 = 0b1_0000_1111_0000;
+
+    const NINE_HIGH_STRAIGHT: HandRankValue = 1605;
+    const NINE_HIGH_STRAIGHT_FLUSH: HandRankValue = 6;
+    const FULL_HOUSES: std::ops::RangeInclusive<HandRankValue> = 167..=322;
+    const FLUSHES: std::ops::RangeInclusive<HandRankValue> = 323..=1599;
+    const FULL_HOUSE_COUNT: HandRankValue = 156;
+    const FLUSH_COUNT: HandRankValue = 1277;
+
+    /// The 36 cards, in the same order as `Cards::deck()`.
+    ///
+    /// ```
+    /// use pkcore::games::short_deck::ShortDeck;
+    ///
+    /// let deck = ShortDeck::deck();
+    ///
+    /// assert_eq!(36, deck.len());
+    /// assert_eq!(
+    ///     "A♠ K♠ Q♠ J♠ T♠ 9♠ 8♠ 7♠ 6♠ A♥ K♥ Q♥ J♥ T♥ 9♥ 8♥ 7♥ 6♥ A♦ K♦ Q♦ J♦ T♦ 9♦ 8♦ 7♦ 6♦ A♣ K♣ Q♣ J♣ T♣ 9♣ 8♣ 7♣ 6♣",
+    ///     deck.to_string()
+    /// );
+    /// ```
+    #[must_use]
+    pub fn deck() -> Cards {
+        Cards::deck()
+            .iter()
+            .filter(|card| ShortDeck::is_short_deck_card(card))
+            .copied()
+            .collect()
+    }
+
+    #[must_use]
+    pub fn deck_minus(cards: &Cards) -> Cards {
+        ShortDeck::deck().minus(cards)
+    }
+
+    #[must_use]
+    pub fn is_short_deck_card(card: &Card) -> bool {
+        ShortDeck::RANKS.contains(&card.get_rank())
+    }
+
+    /// Every `Card` in the collection needs to be in the short deck.
+    ///
+    /// # Errors
+    ///
+    /// Throws `PKError::InvalidCard` if any of them are deuces through fives, or blank.
+    pub fn validate(cards: &Cards) -> Result<(), PKError> {
+        if cards.iter().all(ShortDeck::is_short_deck_card) {
+            Ok(())
+        } else {
+            Err(PKError::InvalidCard)
+        }
+    }
+
+    /// Moves a standard Cactus Kev `HandRankValue` for the `Five` into its short deck slot.
+    #[must_use]
+    pub fn hand_rank_value(five: &Five) -> HandRankValue {
+        let (_, value) = ShortDeck::standard_and_value(five);
+        value
+    }
+
+    /// ```
+    /// use pkcore::analysis::name::HandRankName;
+    /// use pkcore::arrays::five::Five;
+    /// use pkcore::games::short_deck::ShortDeck;
+    /// use std::str::FromStr;
+    ///
+    /// let flush = ShortDeck::hand_rank(&Five::from_str("K♠ J♠ 9♠ 8♠ 6♠").unwrap());
+    /// let boat = ShortDeck::hand_rank(&Five::from_str("A♠ A♥ A♦ K♣ K♦").unwrap());
+    ///
+    /// assert_eq!(HandRankName::Flush, flush.name);
+    /// assert!(flush > boat);
+    /// ```
+    #[must_use]
+    pub fn hand_rank(five: &Five) -> HandRank {
+        let (standard, value) = ShortDeck::standard_and_value(five);
+        if standard == NO_HAND_RANK_VALUE {
+            return HandRank::default();
+        }
+
+        HandRank {
+            value,
+            name: HandRankName::from(standard),
+            class: HandRankClass::from(standard),
+        }
+    }
+
+    /// Returns the standard value, which is what we name the hand by, along with the short deck
+    /// one, which is what we rank it by. A-6-7-8-9 is named as a nine high straight.
+    fn standard_and_value(five: &Five) -> (HandRankValue, HandRankValue) {
+        let standard = five.hand_rank_value();
+
+        if standard == NO_HAND_RANK_VALUE {
+            (NO_HAND_RANK_VALUE, NO_HAND_RANK_VALUE)
+        } else if five.or_rank_bits() == ShortDeck::A6789_OR_BITS {
+            let straight = if five.is_flush() {
+                ShortDeck::NINE_HIGH_STRAIGHT_FLUSH
+            } else {
+                ShortDeck::NINE_HIGH_STRAIGHT
+            };
+            (straight, straight)
+        } else if ShortDeck::FULL_HOUSES.contains(&standard) {
+            (standard, standard + ShortDeck::FLUSH_COUNT)
+        } else if ShortDeck::FLUSHES.contains(&standard) {
+            (standard, standard - ShortDeck::FULL_HOUSE_COUNT)
+        } else {
+            (standard, standard)
+        }
+    }
+
+    /// Every way the board can finish for the hands, drawn only from what's left of the 36 cards.
+    /// Pass in an empty board for odds at the deal.
+    ///
+    /// # Errors
+    ///
+    /// * `PKError::InvalidCard` if any of the cards aren't in the short deck.
+    /// * `PKError::TooManyCards` if the board has more than five cards.
+    /// * `PKError::DuplicateCard` if a card shows up twice.
+    pub fn case_evals(hands: &HoleCards, board: &Cards) -> Result<CaseEvals, PKError> {
+        if board.len() > 5 {
+            return Err(PKError::TooManyCards);
+        }
+
+        let mut dealt = hands.cards();
+        if dealt.len() != hands.len() * 2 {
+            return Err(PKError::DuplicateCard);
+        }
+        for card in board.iter() {
+            if dealt.contains(card) {
+                return Err(PKError::DuplicateCard);
+            }
+        }
+        dealt.insert_all(board);
+        ShortDeck::validate(&dealt)?;
+
+        let remaining = ShortDeck::deck_minus(&dealt);
+        let combos: Vec<Vec<Card>> = remaining.combinations(5 - board.len()).collect();
+
+        let case_evals: Vec<CaseEval> = combos
+            .par_iter()
+            .map(|combo| {
+                let case = Cards::from(combo.clone());
+                let mut full_board = board.clone();
+                full_board.insert_all(&case);
+
+                let mut case_eval = CaseEval::new(case);
+                for hand in hands.iter() {
+                    let mut cards = hand.cards();
+                    cards.insert_all(&full_board);
+                    let seven = Seven::try_from(cards).unwrap_or_default();
+                    case_eval.push(seven.short_deck_eval());
+                }
+                case_eval
+            })
+            .collect();
+
+        Ok(CaseEvals::from(case_evals))
+    }
+
+    /// Heads up odds at the deal, in the same `HUPResult` form as the hold'em matchups, so
+    /// that `A♠ K♠` v `Q♥ Q♦` reads the same way for both decks.
+    ///
+    /// With four cards out there are C(32, 5) = 201,376 boards to run through.
+    ///
+    /// # Errors
+    ///
+    /// Same as `ShortDeck::case_evals()`.
+    pub fn heads_up(first: Two, second: Two) -> Result<HUPResult, PKError> {
+        let shu = SortedHeadsUp::new(first, second);
+        let hands = HoleCards::from(vec![shu.higher(), shu.lower()]);
+        let wins = ShortDeck::case_evals(&hands, &Cards::default())?.wins();
+        Ok(HUPResult::from_sorted_heads_up(&shu, &wins))
+    }
+}
+
+/// Like `HandRanker`, but for the short deck. Gives you the best `Eval` ranked by
+/// `ShortDeck::hand_rank()`.
+pub trait ShortDeckRanker {
+    fn short_deck_eval(&self) -> Eval;
+
+    fn short_deck_hand_rank(&self) -> HandRank {
+        self.short_deck_eval().hand_rank
+    }
+}
+
+impl ShortDeckRanker for Five {
+    fn short_deck_eval(&self) -> Eval {
+        Eval::new(ShortDeck::hand_rank(self), self.sort().clean())
+    }
+}
+
+impl ShortDeckRanker for Seven {
+    fn short_deck_eval(&self) -> Eval {
+        let mut best_hrv: HandRankValue = NO_HAND_RANK_VALUE;
+        let mut best_hand = Five::default();
+
+        for perm in Seven::FIVE_CARD_PERMUTATIONS {
+            let hand = self.five_from_permutation(perm);
+            let hrv = ShortDeck::hand_rank_value(&hand);
+            if (best_hrv == 0) || hrv != 0 && hrv < best_hrv {
+                best_hrv = hrv;
+                best_hand = hand;
+            }
+        }
+
+        best_hand.short_deck_eval()
+    }
+}
+
+#[cfg(test)]
+#[allow(non_snake_case)]
+mod games__short_deck_tests {
+    use super::*;
+    use crate::util::wincounter::win::Win;
+    use rstest::rstest;
+    use std::collections::HashSet;
+    use std::str::FromStr;
+
+    #[test]
+    fn deck() {
+        let deck = ShortDeck::deck();
+
+        assert_eq!(ShortDeck::DECK_SIZE, deck.len());
+        assert!(ShortDeck::validate(&deck).is_ok());
+        assert!(!deck.contains(&Card::from_str("5♠").unwrap()));
+    }
+
+    #[test]
+    fn validate() {
+        assert_eq!(
+            PKError::InvalidCard,
+            ShortDeck::validate(&Cards::from_str("A♠ 5♠").unwrap()).unwrap_err()
+        );
+    }
+
+    /// There are 7,462 distinct hands with a full deck. Take away the deuces through fives and
+    /// you're down to 120 high card hands, 120 flushes, 6 straights, 6 straight flushes, 504 pairs,
+    /// 252 two pair, 252 trips, 72 full houses, and 72 quads.
+    #[test]
+    fn hand_rank__distinct() {
+        let values: HashSet<HandRankValue> = ShortDeck::deck()
+            .combinations(5)
+            .map(|v| ShortDeck::hand_rank_value(&Five::try_from(v).unwrap()))
+            .collect();
+
+        assert_eq!(1404, values.len());
+        assert!(!values.contains(&NO_HAND_RANK_VALUE));
+    }
+
+    #[rstest]
+    #[case("A♠ K♠ Q♠ J♠ T♠", "K♠ Q♠ J♠ T♠ 9♠")]
+    #[case("9♠ 8♠ 7♠ 6♠ A♠", "A♠ A♥ A♦ A♣ K♠")]
+    #[case("K♠ J♠ 9♠ 8♠ 6♠", "A♠ A♥ A♦ K♣ K♦")]
+    #[case("8♠ 7♠ 6♠ Q♠ T♠", "A♠ A♥ A♦ K♣ K♦")]
+    #[case("6♠ 6♥ 6♦ 7♣ 7♦", "A♠ K♠ Q♠ J♠ 9♦")]
+    #[case("T♠ 9♥ 8♦ 7♣ 6♦", "A♠ 9♥ 8♦ 7♣ 6♦")]
+    #[case("A♠ 9♥ 8♦ 7♣ 6♦", "A♠ A♥ A♦ K♣ Q♦")]
+    #[case("A♠ A♥ A♦ K♣ Q♦", "A♠ A♥ K♦ K♣ Q♦")]
+    fn hand_rank__ordering(#[case] higher: &'static str, #[case] lower: &'static str) {
+        let higher = ShortDeck::hand_rank(&Five::from_str(higher).unwrap());
+        let lower = ShortDeck::hand_rank(&Five::from_str(lower).unwrap());
+
+        assert!(higher > lower, "{higher} should beat {lower}");
+    }
+
+    #[test]
+    fn hand_rank__a6789() {
+        let straight = ShortDeck::hand_rank(&Five::from_str("A♠ 9♥ 8♦ 7♣ 6♦").unwrap());
+        let straight_flush = ShortDeck::hand_rank(&Five::from_str("A♠ 9♠ 8♠ 7♠ 6♠").unwrap());
+
+        assert_eq!(1605, straight.value);
+        assert_eq!(HandRankName::Straight, straight.name);
+        assert_eq!(6, straight_flush.value);
+        assert_eq!(HandRankName::StraightFlush, straight_flush.name);
+    }
+
+    #[test]
+    fn hand_rank__blank() {
+        assert_eq!(HandRank::default(), ShortDeck::hand_rank(&Five::default()));
+    }
+
+    #[test]
+    fn short_deck_eval__seven() {
+        let seven = Seven::from_str("A♠ K♦ 9♥ 8♠ 7♣ 6♦ K♠").unwrap();
+
+        let eval = seven.short_deck_eval();
+
+        assert_eq!(HandRankName::Straight, eval.hand_rank.name);
+        assert_eq!(1605, eval.hand_rank.value);
+    }
+
+    /// Seven cards can't hold both a flush and a full house, but across the table they sure can.
+    #[test]
+    fn short_deck_eval__flush_over_full_house() {
+        let flush = Seven::from_str("K♠ J♠ Q♥ Q♦ 9♠ 7♠ 6♠").unwrap();
+        let boat = Seven::from_str("Q♣ 9♥ Q♥ Q♦ 9♠ 7♠ 6♠").unwrap();
+
+        assert!(boat.eval() > flush.eval());
+        assert!(flush.short_deck_eval() > boat.short_deck_eval());
+        assert_eq!(HandRankName::Flush, flush.short_deck_eval().hand_rank.name);
+        assert_eq!(HandRankName::FullHouse, boat.short_deck_eval().hand_rank.name);
+    }
+
+    #[test]
+    fn case_evals__turn() {
+        let hands = HoleCards::from_str("A♠ K♠ Q♥ Q♦").unwrap();
+        let board = Cards::from_str("Q♠ J♠ 6♥ 7♣").unwrap();
+
+        let case_evals = ShortDeck::case_evals(&hands, &board).unwrap();
+        let (ak, _) = case_evals.wins().wins_for(Win::FIRST);
+
+        // 36 - 4 - 4 = 28 rivers. The five spades get there, even the ones that fill up the queens,
+        // and so do the other three tens.
+        assert_eq!(28, case_evals.len());
+        assert_eq!(8, ak);
+    }
+
+    #[test]
+    fn case_evals__errors() {
+        let hands = HoleCards::from_str("A♠ K♠ Q♥ Q♦").unwrap();
+
+        assert_eq!(
+            PKError::InvalidCard,
+            ShortDeck::case_evals(&hands, &Cards::from_str("2♠ 3♠ 4♠").unwrap()).unwrap_err()
+        );
+        assert_eq!(
+            PKError::DuplicateCard,
+            ShortDeck::case_evals(&hands, &Cards::from_str("A♠ 7♠ 8♠").unwrap()).unwrap_err()
+        );
+        assert_eq!(
+            PKError::TooManyCards,
+            ShortDeck::case_evals(&hands, &Cards::from_str("T♠ 9♠ 8♠ 7♠ 6♥ 6♦").unwrap()).unwrap_err()
+        );
+    }
+
+    /// Takes most of a minute in a debug build. Run it with `--release`.
+    #[test]
+    #[ignore]
+    fn heads_up() {
+        let hup = ShortDeck::heads_up(Two::from_str("Q♥ Q♦").unwrap(), Two::from_str("A♠ K♠").unwrap()).unwrap();
+
+        // Ace king suited is a favorite over queens in short deck. It isn't with a full deck.
+        assert_eq!(201_376, hup.odds.wins + hup.odds.losses + hup.odds.draws);
+        assert_eq!(108_298, hup.odds.wins);
+        assert_eq!(91_071, hup.odds.losses);
+        assert_eq!(2_007, hup.odds.draws);
+        assert_eq!(Two::from_str("A♠ K♠").unwrap().bard(), hup.higher);
+    }
+}
@@ -1 +1,943 @@
+use crate::analysis::eval::Eval;
+use crate::analysis::seven_card_evaluator::SevenCardEvaluator;
+use crate::arrays::seven::Seven;
+use crate::arrays::sliced::BoxedCards;
+use crate::card::Card;
+use crate::cards::Cards;
+use crate::cards_cell::CardsCell;
+use crate::casino::game::ForcedBets;
+use crate::casino::player::Player;
+use crate::casino::table::Table;
+use crate::casino::table::event::TableAction;
+use crate::casino::table::seat::Seat;
+use crate::casino::table::seats::Seats;
+use crate::games::GameType;
+use crate::games::stud::hi_lo::StudHiLoEval;
+use crate::rank::Rank;
+use crate::{PKError, Pile};
+use std::cell::Cell;
+use std::fmt::{Display, Formatter};
 
+pub mod hi_lo;
+
+/// The five betting rounds of Seven Card Stud, named after how many cards each player is
+/// holding when the betting starts.
+#[derive(Clone, Copy, Debug, Eq, Hash, Ord, PartialEq, PartialOrd)]
+pub enum StudStreet {
+    Third,
+    Fourth,
+    Fifth,
+    Sixth,
+    Seventh,
+}
+
+impl StudStreet {
+    /// How many cards each player gets on the street. Third street is two down and one up.
+    #[must_use]
+    pub fn cards_dealt(&self) -> usize {
+        match self {
+            StudStreet::Third => 3,
+            _ => 1,
+        }
+    }
+
+    /// How many cards each player is holding once the street is dealt.
+    #[must_use]
+    pub fn cards_held(&self) -> usize {
+        match self {
+            StudStreet::Third => 3,
+            StudStreet::Fourth => 4,
+            StudStreet::Fifth => 5,
+            StudStreet::Sixth => 6,
+            StudStreet::Seventh => 7,
+        }
+    }
+
+    /// How many of the cards held are face down.
+    #[must_use]
+    pub fn down_count(&self) -> usize {
+        match self {
+            StudStreet::Seventh => 3,
+            _ => 2,
+        }
+    }
+
+    /// How many of the cards held are face up.
+    #[must_use]
+    pub fn up_count(&self) -> usize {
+        self.cards_held() - self.down_count()
+    }
+
+    /// Fifth street on is played with the big bet.
+    #[must_use]
+    pub fn is_big_bet(&self) -> bool {
+        *self >= StudStreet::Fifth
+    }
+
+    /// Returns `true` if the card in the slot is dealt face up. The first two cards and the last
+    /// one are down, the four in between are up.
+    #[must_use]
+    pub fn is_up(slot: usize) -> bool {
+        (2..=5).contains(&slot)
+    }
+
+    #[must_use]
+    pub fn next(&self) -> Option<StudStreet> {
+        match self {
+            StudStreet::Third => Some(StudStreet::Fourth),
+            StudStreet::Fourth => Some(StudStreet::Fifth),
+            StudStreet::Fifth => Some(StudStreet::Sixth),
+            StudStreet::Sixth => Some(StudStreet::Seventh),
+            StudStreet::Seventh => None,
+        }
+    }
+}
+
+impl Display for StudStreet {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            StudStreet::Third => write!(f, "Third Street"),
+            StudStreet::Fourth => write!(f, "Fourth Street"),
+            StudStreet::Fifth => write!(f, "Fifth Street"),
+            StudStreet::Sixth => write!(f, "Sixth Street"),
+            StudStreet::Seventh => write!(f, "Seventh Street"),
+        }
+    }
+}
+
+/// What we know about a player's stud hand part way through: the cards they're showing, and
+/// their down cards if we've seen them. Down cards that we haven't seen are left out, so an
+/// opponent on fourth street with nothing known but `7♠ 4♥` showing is just those two upcards.
+///
+/// ```
+/// use pkcore::cards::Cards;
+/// use pkcore::games::stud::StudHand;
+/// use std::str::FromStr;
+///
+/// let hand = StudHand::new(Cards::from_str("A♠ 2♠").unwrap(), Cards::from_str("3♥").unwrap());
+///
+/// assert_eq!("[A♠ 2♠] 3♥", hand.to_string());
+/// assert_eq!("[__ __] 7♠ 4♥", StudHand::showing(Cards::from_str("7♠ 4♥").unwrap()).to_string());
+/// ```
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct StudHand {
+    pub down: Cards,
+    pub up: Cards,
+}
+
+impl StudHand {
+    #[must_use]
+    pub fn new(down: Cards, up: Cards) -> Self {
+        StudHand { down, up }
+    }
+
+    /// A hand where all we can see are the upcards.
+    #[must_use]
+    pub fn showing(up: Cards) -> Self {
+        StudHand {
+            down: Cards::default(),
+            up,
+        }
+    }
+
+    /// Every card in the hand that we know about.
+    #[must_use]
+    pub fn known(&self) -> Cards {
+        let mut known = self.down.clone();
+        known.insert_all(&self.up);
+        known
+    }
+
+    /// Makes sure that the hand lines up with the street, and that it isn't holding more down
+    /// cards than it could have.
+    ///
+    /// # Errors
+    ///
+    /// `PKError::InvalidCardCount` if it doesn't.
+    pub fn validate(&self, street: StudStreet) -> Result<(), PKError> {
+        if self.up.len() != street.up_count() || self.down.len() > street.down_count() {
+            return Err(PKError::InvalidCardCount);
+        }
+        Ok(())
+    }
+}
+
+impl Display for StudHand {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        if self.down.is_empty() {
+            write!(f, "[__ __] {}", self.up)
+        } else {
+            write!(f, "[{}] {}", self.down, self.up)
+        }
+    }
+}
+
+/// Stud is a fixed limit game. Everybody antes, the low card is forced to bring it in for less
+/// than a full bet, and then every bet and raise is exactly the small bet on third and fourth
+/// street, and exactly the big bet from fifth street on.
+#[derive(Clone, Copy, Debug, Default, Eq, Hash, PartialEq)]
+pub struct StudStakes {
+    pub ante: usize,
+    pub bring_in: usize,
+    pub small_bet: usize,
+    pub big_bet: usize,
+}
+
+impl StudStakes {
+    #[must_use]
+    pub fn new(ante: usize, bring_in: usize, small_bet: usize, big_bet: usize) -> Self {
+        StudStakes {
+            ante,
+            bring_in,
+            small_bet,
+            big_bet,
+        }
+    }
+
+    #[must_use]
+    pub fn bet_size(&self, street: StudStreet) -> usize {
+        if street.is_big_bet() {
+            self.big_bet
+        } else {
+            self.small_bet
+        }
+    }
+}
+
+impl Display for StudStakes {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{}/{}, Ante: {}, Bring In: {}",
+            self.small_bet, self.big_bet, self.ante, self.bring_in
+        )
+    }
+}
+
+/// Seven Card Stud on top of our `Table`.
+///
+/// The `Table` was built for hold'em, so it knows about buttons and blinds and flops. Most of
+/// that doesn't matter here. What stud needs instead:
+///
+/// * Third street is two cards down and one up, fourth through sixth are up, and seventh is down.
+/// * There are no blinds. The lowest upcard on third street is forced to bring it in, with ties
+///   broken by suit: clubs, then diamonds, then hearts, then spades.
+/// * From fourth street on, whoever is showing the best board acts first.
+/// * Eight players need 56 cards. If there aren't enough cards left to give everyone their
+///   seventh street card, a single common card is dealt face up for all of them to share. We
+///   keep it on the table's `board`, since that's what it is.
+///
+/// The dealing starts with the seat to the left of the button, same as hold'em, and when two
+/// boards are tied the first of them in that order acts first. Like the rest of the `Table`, for
+/// now we don't burn any cards.
+///
+/// ```
+/// use pkcore::games::stud::{SevenCardStud, StudStakes, StudStreet};
+/// use pkcore::prelude::*;
+///
+/// let players = vec![
+///     Player::new_with_chips("Chip".to_string(), 1_000),
+///     Player::new_with_chips("Stu".to_string(), 1_000),
+///     Player::new_with_chips("Ungar".to_string(), 1_000),
+/// ];
+/// let stud = SevenCardStud::new(players, StudStakes::new(5, 10, 20, 40)).unwrap();
+///
+/// stud.act_antes().unwrap();
+/// assert_eq!(StudStreet::Third, stud.deal_street().unwrap());
+/// stud.act_bring_in().unwrap();
+///
+/// assert_eq!(15, stud.table.pot.count());
+/// assert_eq!(10, stud.table.seats.current_bet());
+/// ```
+#[derive(Clone, Debug)]
+pub struct SevenCardStud {
+    pub table: Table,
+    pub stakes: StudStakes,
+    street: Cell<Option<StudStreet>>,
+}
+
+impl SevenCardStud {
+    pub const CARDS_PER_PLAYER: usize = 7;
+    /// Fixed limit betting is capped at a bet and three raises. On third street the bring in
+    /// doesn't count, but completing it does.
+    pub const BET_CAP: usize = 4;
+
+    /// # Errors
+    ///
+    /// * `PKError::NotEnoughHands` if there are fewer than two players.
+    /// * `PKError::TooManyHands` if there are more than eight.
+    pub fn new(players: Vec<Player>, stakes: StudStakes) -> Result<Self, PKError> {
+        if players.len() < 2 {
+            return Err(PKError::NotEnoughHands);
+        }
+        if players.len() > GameType::SevenCardStud.max_players() as usize {
+            return Err(PKError::TooManyHands);
+        }
+
+        let seats = players
+            .into_iter()
+            .map(|player| Seat::new_with_cards(player, BoxedCards::blanks(SevenCardStud::CARDS_PER_PLAYER)))
+            .collect();
+        let forced = ForcedBets::new_with_ante(stakes.bring_in, stakes.small_bet, stakes.ante);
+
+        Ok(SevenCardStud {
+            table: Table::from_seats(GameType::SevenCardStud, Seats::new(seats), forced),
+            stakes,
+            street: Cell::new(None),
+        })
+    }
+
+    /// Same as `SevenCardStud::new()` but with the deck stacked ahead of time, like
+    /// `Table::nlh_primed()`.
+    ///
+    /// # Errors
+    ///
+    /// See `SevenCardStud::new()`.
+    pub fn primed(players: Vec<Player>, stakes: StudStakes, dealt: &CardsCell) -> Result<Self, PKError> {
+        let stud = SevenCardStud::new(players, stakes)?;
+        stud.table.deck.0.swap(&dealt.0);
+        Ok(stud)
+    }
+
+    // region actions
+
+    /// Everybody in the hand puts in their ante, which goes straight into the pot. Returns the
+    /// size of the pot.
+    ///
+    /// # Errors
+    ///
+    /// `PKError::InsufficientChips` if a player can't cover the ante.
+    pub fn act_antes(&self) -> Result<usize, PKError> {
+        if self.stakes.ante > 0 {
+            for seat_number in self.seats_in_hand() {
+                if let Some(seat) = self.table.get_seat(seat_number) {
+                    let ante = seat.player.chips.bet(self.stakes.ante)?;
+                    self.table.pot.add_to(ante);
+                }
+                self.table
+                    .event_log
+                    .log(TableAction::BetAnteForced(seat_number, self.stakes.ante));
+            }
+        }
+        Ok(self.table.pot.count())
+    }
+
+    /// Forces the lowest upcard on third street to bring it in. Returns the seat that did.
+    ///
+    /// Unlike a big blind, the bring in doesn't get an option. If everybody just calls it, the
+    /// betting on third street is over, so it goes down as a bet and not a blind.
+    ///
+    /// # Errors
+    ///
+    /// * `PKError::InvalidTableAction` if third street hasn't been dealt.
+    /// * `PKError::InsufficientChips` if the player can't cover the bring in.
+    pub fn act_bring_in(&self) -> Result<u8, PKError> {
+        if self.street() != Some(StudStreet::Third) {
+            return Err(PKError::InvalidTableAction);
+        }
+        let seat_number = self.bring_in_seat().ok_or(PKError::InvalidTableAction)?;
+        self.table.seats.act_bet(seat_number, self.stakes.bring_in)?;
+        self.table
+            .event_log
+            .log(TableAction::ForcedBetBringIn(seat_number, self.stakes.bring_in));
+        Ok(seat_number)
+    }
+
+    /// Makes a fixed limit bet of the size for the street.
+    ///
+    /// # Errors
+    ///
+    /// * `PKError::InvalidTableAction` if there's already a bet to face, or nothing's been dealt.
+    /// * `PKError::InsufficientChips` if the player doesn't have enough chips to make the bet.
+    pub fn act_bet(&self, seat_number: u8) -> Result<usize, PKError> {
+        let street = self.street().ok_or(PKError::InvalidTableAction)?;
+        if self.table.seats.current_bet() > 0 {
+            return Err(PKError::InvalidTableAction);
+        }
+        self.table.act_bet(seat_number, self.stakes.bet_size(street))
+    }
+
+    /// On third street, the players after the bring in can either call the bring in or
+    /// _complete_ it to a full small bet.
+    ///
+    /// # Errors
+    ///
+    /// * `PKError::InvalidTableAction` if it isn't third street, or the bet has already been
+    ///   completed.
+    /// * `PKError::InsufficientChips` if the player doesn't have enough chips.
+    pub fn act_complete(&self, seat_number: u8) -> Result<usize, PKError> {
+        if self.street() != Some(StudStreet::Third) || self.table.seats.current_bet() >= self.stakes.small_bet {
+            return Err(PKError::InvalidTableAction);
+        }
+        self.table.act_raise(seat_number, self.stakes.small_bet)
+    }
+
+    /// Raises by exactly the size of the bet for the street.
+    ///
+    /// # Errors
+    ///
+    /// * `PKError::InvalidTableAction` if there is no bet to raise, or the betting is capped.
+    /// * `PKError::InsufficientChips` if the player doesn't have enough chips.
+    pub fn act_raise(&self, seat_number: u8) -> Result<usize, PKError> {
+        let street = self.street().ok_or(PKError::InvalidTableAction)?;
+        let current_bet = self.table.seats.current_bet();
+        let bet_size = self.stakes.bet_size(street);
+        if current_bet == 0
+            || (street == StudStreet::Third && current_bet < self.stakes.small_bet)
+            || current_bet + bet_size > bet_size * SevenCardStud::BET_CAP
+        {
+            return Err(PKError::InvalidTableAction);
+        }
+        self.table.act_raise(seat_number, current_bet + bet_size)
+    }
+
+    // endregion
+
+    /// The size of a bet or raise on the current street.
+    #[must_use]
+    pub fn bet_size(&self) -> usize {
+        self.stakes.bet_size(self.street().unwrap_or(StudStreet::Third))
+    }
+
+    /// The seat with the lowest upcard on third street. Aces are high, and ties go by suit.
+    #[must_use]
+    pub fn bring_in_seat(&self) -> Option<u8> {
+        self.seats_in_hand()
+            .into_iter()
+            .filter_map(|seat_number| {
+                self.upcards(seat_number)
+                    .get_index(0)
+                    .map(|card| (seat_number, card.get_rank(), card.get_suit() as u8))
+            })
+            .min_by_key(|(_, rank, suit)| (*rank, *suit))
+            .map(|(seat_number, _, _)| seat_number)
+    }
+
+    /// The common card, if there weren't enough cards to go around on seventh street.
+    #[must_use]
+    pub fn common_card(&self) -> Option<Card> {
+        self.table.board.cards().get_index(0).copied()
+    }
+
+    /// Deals the next street to every player still in the hand. On seventh street, if the
+    /// deck can't cover everyone, a common card is dealt instead.
+    ///
+    /// Before the cards go out, the bets from the last street are brought into the pot, and
+    /// everybody still in the hand is back to yet to act. If nobody has acted, there's nothing
+    /// to bring in, and we just deal.
+    ///
+    /// # Errors
+    ///
+    /// * `PKError::AlreadyDealt` if seventh street has already been dealt.
+    /// * `PKError::ActionIsntFinished` if there are bets out that haven't been called.
+    /// * `PKError::NotEnoughCards` if the deck runs out, which shouldn't happen.
+    pub fn deal_street(&self) -> Result<StudStreet, PKError> {
+        let street = match self.street() {
+            None => StudStreet::Third,
+            Some(street) => street.next().ok_or(PKError::AlreadyDealt)?,
+        };
+        if self.street().is_some() {
+            if self.table.seats.is_betting_complete() {
+                self.table.bring_it_in()?;
+            } else if self.table.seats.current_bet() > 0 {
+                return Err(PKError::ActionIsntFinished);
+            }
+        }
+        let in_hand = self.seats_in_hand();
+
+        if street == StudStreet::Seventh && self.table.deck.len() < in_hand.len() {
+            let common = self.table.deck.draw_one()?;
+            self.table.board.insert(common);
+            self.table.event_log.log(TableAction::DealtCommonCard(common.bard()));
+        } else {
+            for _ in 0..street.cards_dealt() {
+                for seat_number in &in_hand {
+                    self.table.deal_card_to_seat(*seat_number)?;
+                }
+            }
+        }
+
+        self.street.set(Some(street));
+        Ok(street)
+    }
+
+    /// The seat that opens the betting on the current street. On third street that's the player
+    /// to the left of the bring in. After that it's whoever is showing the best board.
+    #[must_use]
+    pub fn first_to_act(&self) -> Option<u8> {
+        match self.street()? {
+            StudStreet::Third => {
+                let bring_in = self.bring_in_seat()?;
+                let in_hand = self.seats_in_hand();
+                let position = in_hand.iter().position(|s| *s == bring_in)?;
+                in_hand.get((position + 1) % in_hand.len()).copied()
+            }
+            _ => self.best_visible_board(),
+        }
+    }
+
+    /// The seat showing the best poker hand with their upcards. With no more than four of them
+    /// straights and flushes don't count, so it comes down to quads, trips, two pair, pair and
+    /// high cards.
+    #[must_use]
+    pub fn best_visible_board(&self) -> Option<u8> {
+        let mut best: Option<(u8, (u8, Vec<Rank>))> = None;
+        for seat_number in self.seats_in_hand() {
+            let value = SevenCardStud::visible_value(&self.upcards(seat_number));
+            match &best {
+                Some((_, best_value)) if value <= *best_value => {}
+                _ => best = Some((seat_number, value)),
+            }
+        }
+        best.map(|(seat_number, _)| seat_number)
+    }
+
+    /// All of a player's cards, including the common card if there is one.
+    #[must_use]
+    pub fn hand(&self, seat_number: u8) -> Cards {
+        let mut cards = match self.table.get_seat(seat_number) {
+            Some(seat) => seat.cards.cards(),
+            None => Cards::default(),
+        };
+        if let Some(common) = self.common_card() {
+            cards.insert(common);
+        }
+        cards
+    }
+
+    /// Returns the `Eval` of the player's best five out of seven, once they have seven.
+    ///
+    /// # Errors
+    ///
+    /// `PKError::NotEnoughCards` if the hand hasn't made it to seventh street.
+    pub fn eval(&self, seat_number: u8) -> Result<Eval, PKError> {
+        let seven = Seven::try_from(self.hand(seat_number))?;
+        Ok(SevenCardEvaluator::eval(&seven))
+    }
+
+    /// Returns the `StudHiLoEval` of the player's best high and best qualifying low, for when
+    /// we're playing Stud/8.
+    ///
+    /// # Errors
+    ///
+    /// `PKError::NotEnoughCards` if the hand hasn't made it to seventh street.
+    pub fn eval_hi_lo(&self, seat_number: u8) -> Result<StudHiLoEval, PKError> {
+        Ok(StudHiLoEval::from(Seven::try_from(self.hand(seat_number))?))
+    }
+
+    /// Splits `pot` between the seats still in the hand at a Stud/8 showdown, returning each
+    /// seat along with how many chips it takes. See `StudHiLoEval::split_pot()`.
+    ///
+    /// # Errors
+    ///
+    /// `PKError::NotEnoughCards` if the hand hasn't made it to seventh street.
+    pub fn split_pot_hi_lo(&self, pot: usize) -> Result<Vec<(u8, usize)>, PKError> {
+        let seats = self.seats_in_hand();
+        let mut evals = Vec::with_capacity(seats.len());
+        for seat_number in &seats {
+            evals.push(self.eval_hi_lo(*seat_number)?);
+        }
+        Ok(seats.into_iter().zip(StudHiLoEval::split_pot(pot, &evals)).collect())
+    }
+
+    /// The seats in the hand, in dealing order.
+    #[must_use]
+    pub fn seats_in_hand(&self) -> Vec<u8> {
+        let start = self.table.button.static_up_x(1).value;
+        self.table
+            .seats
+            .indices_from(start)
+            .filter_map(|i| u8::try_from(i).ok())
+            .filter(|seat_number| self.table.seats.is_seat_in_hand(*seat_number))
+            .collect()
+    }
+
+    /// The last street dealt, if any.
+    #[must_use]
+    pub fn street(&self) -> Option<StudStreet> {
+        self.street.get()
+    }
+
+    /// The cards the player has face up, in the order they were dealt.
+    #[must_use]
+    pub fn upcards(&self, seat_number: u8) -> Cards {
+        match self.table.get_seat(seat_number) {
+            Some(seat) => Cards::from(
+                seat.cards
+                    .as_slice()
+                    .iter()
+                    .enumerate()
+                    .filter(|(slot, card)| StudStreet::is_up(*slot) && **card != Card::BLANK)
+                    .map(|(_, card)| *card)
+                    .collect::<Vec<Card>>(),
+            ),
+            None => Cards::default(),
+        }
+    }
+
+    /// The seats with the best hand at showdown. More than one means a split pot.
+    ///
+    /// # Errors
+    ///
+    /// `PKError::NotEnoughCards` if the hand hasn't made it to seventh street.
+    pub fn winners(&self) -> Result<Vec<u8>, PKError> {
+        let mut evals = Vec::new();
+        for seat_number in self.seats_in_hand() {
+            evals.push((seat_number, self.eval(seat_number)?));
+        }
+        let best = evals.iter().map(|(_, eval)| *eval).max().unwrap_or_default();
+        Ok(evals
+            .into_iter()
+            .filter(|(_, eval)| *eval == best)
+            .map(|(seat_number, _)| seat_number)
+            .collect())
+    }
+
+    /// Ranks upcards as (quads = 4, trips = 3, two pair = 2, pair = 1, nothing = 0), followed
+    /// by the ranks in order of how many there are and then how high they are.
+    fn visible_value(upcards: &Cards) -> (u8, Vec<Rank>) {
+        let mut counts: Vec<(usize, Rank)> = Vec::new();
+        for card in upcards.iter() {
+            let rank = card.get_rank();
+            match counts.iter_mut().find(|(_, r)| *r == rank) {
+                Some((count, _)) => *count += 1,
+                None => counts.push((1, rank)),
+            }
+        }
+        counts.sort_by(|a, b| b.cmp(a));
+
+        let category = match counts.iter().map(|(count, _)| *count).collect::<Vec<usize>>()[..] {
+            [4, ..] => 4,
+            [3, ..] => 3,
+            [2, 2, ..] => 2,
+            [2, ..] => 1,
+            _ => 0,
+        };
+        (category, counts.into_iter().map(|(_, rank)| rank).collect())
+    }
+}
+
+#[cfg(test)]
+#[allow(non_snake_case)]
+mod games__stud_tests {
+    use super::*;
+    use std::str::FromStr;
+
+    /// Dealing starts to the left of the button, so the order is seats 1, 2, 0.
+    ///
+    /// | Seat | Down  | Third | Fourth | Fifth | Sixth | Seventh |
+    /// |------|-------|-------|--------|-------|-------|---------|
+    /// | 1    | A♠ 7♣ | K♠    | Q♠     | K♥    | K♦    | 5♠      |
+    /// | 2    | A♥ 2♥ | 4♦    | 4♥     | 9♣    | 2♣    | 8♥      |
+    /// | 0    | 7♦ 3♥ | 4♣    | J♣     | J♦    | 3♣    | 9♦      |
+    const DEAL: &str = "A♠ A♥ 7♦ 7♣ 2♥ 3♥ K♠ 4♦ 4♣ Q♠ 4♥ J♣ K♥ 9♣ J♦ K♦ 2♣ 3♣ 5♠ 8♥ 9♦";
+
+    fn players(count: usize) -> Vec<Player> {
+        (0..count)
+            .map(|i| Player::new_with_chips(format!("Player {i}"), 1_000))
+            .collect()
+    }
+
+    fn stud() -> SevenCardStud {
+        let deck = CardsCell::from(Cards::deck_primed(&Cards::from_str(DEAL).unwrap()));
+        SevenCardStud::primed(players(3), StudStakes::new(5, 10, 20, 40), &deck).unwrap()
+    }
+
+    fn deal_to(stud: &SevenCardStud, street: StudStreet) {
+        while stud.street() != Some(street) {
+            stud.deal_street().unwrap();
+        }
+    }
+
+    #[test]
+    fn new__errors() {
+        let stakes = StudStakes::new(5, 10, 20, 40);
+
+        assert_eq!(
+            PKError::NotEnoughHands,
+            SevenCardStud::new(players(1), stakes).unwrap_err()
+        );
+        assert_eq!(
+            PKError::TooManyHands,
+            SevenCardStud::new(players(9), stakes).unwrap_err()
+        );
+    }
+
+    #[test]
+    fn deal_street__third() {
+        let stud = stud();
+
+        assert_eq!(StudStreet::Third, stud.deal_street().unwrap());
+
+        assert_eq!("A♠ 7♣ K♠", stud.hand(1).to_string());
+        assert_eq!("K♠", stud.upcards(1).to_string());
+        assert_eq!("4♦", stud.upcards(2).to_string());
+        assert_eq!("4♣", stud.upcards(0).to_string());
+        assert_eq!(43, stud.table.deck.len());
+    }
+
+    #[test]
+    fn deal_street__seventh_is_down() {
+        let stud = stud();
+
+        deal_to(&stud, StudStreet::Seventh);
+
+        assert_eq!("A♠ 7♣ K♠ Q♠ K♥ K♦ 5♠", stud.hand(1).to_string());
+        assert_eq!("K♠ Q♠ K♥ K♦", stud.upcards(1).to_string());
+        assert!(stud.common_card().is_none());
+        assert_eq!(PKError::AlreadyDealt, stud.deal_street().unwrap_err());
+    }
+
+    #[test]
+    fn deal_street__skips_folded() {
+        let stud = stud();
+        stud.deal_street().unwrap();
+
+        stud.table.act_fold(2).unwrap();
+        stud.deal_street().unwrap();
+
+        assert_eq!(vec![1, 0], stud.seats_in_hand());
+        assert_eq!("Q♠", stud.upcards(1).get_index(1).unwrap().to_string());
+        assert_eq!("4♥", stud.upcards(0).get_index(1).unwrap().to_string());
+    }
+
+    /// Eight players see sixth street with 48 cards out, which only leaves four in the deck.
+    #[test]
+    fn deal_street__common_card() {
+        let stud = SevenCardStud::new(players(8), StudStakes::new(5, 10, 20, 40)).unwrap();
+
+        deal_to(&stud, StudStreet::Seventh);
+
+        assert_eq!(Some(Card::FIVE_CLUBS), stud.common_card());
+        assert_eq!(3, stud.table.deck.len());
+        for seat_number in 0..8 {
+            assert_eq!(7, stud.hand(seat_number).len());
+            assert!(stud.hand(seat_number).contains(&Card::FIVE_CLUBS));
+            assert!(stud.eval(seat_number).is_ok());
+        }
+    }
+
+    #[test]
+    fn deal_street__seven_players__no_common_card() {
+        let stud = SevenCardStud::new(players(7), StudStakes::new(5, 10, 20, 40)).unwrap();
+
+        deal_to(&stud, StudStreet::Seventh);
+
+        assert!(stud.common_card().is_none());
+        assert_eq!(3, stud.table.deck.len());
+    }
+
+    #[test]
+    fn act_antes() {
+        let stud = stud();
+
+        assert_eq!(15, stud.act_antes().unwrap());
+        assert_eq!(995, stud.table.get_seat(0).unwrap().player.chips.count());
+        assert_eq!(1, stud.table.event_count(&TableAction::BetAnteForced(0, 5)));
+    }
+
+    #[test]
+    fn act_bring_in() {
+        let stud = stud();
+        assert_eq!(PKError::InvalidTableAction, stud.act_bring_in().unwrap_err());
+
+        stud.deal_street().unwrap();
+
+        assert_eq!(0, stud.act_bring_in().unwrap());
+        assert_eq!(10, stud.table.seats.current_bet());
+        assert_eq!(1, stud.table.event_count(&TableAction::ForcedBetBringIn(0, 10)));
+    }
+
+    /// Both fours are the lowest, but clubs is the lowest suit.
+    #[test]
+    fn bring_in_seat() {
+        let stud = stud();
+        assert!(stud.bring_in_seat().is_none());
+
+        stud.deal_street().unwrap();
+
+        assert_eq!(Some(0), stud.bring_in_seat());
+    }
+
+    #[test]
+    fn bring_in_seat__aces_are_high() {
+        let deck = CardsCell::from(Cards::deck_primed(&Cards::from_str("2♠ 3♠ 2♥ 3♥ A♦ K♦").unwrap()));
+        let stud = SevenCardStud::primed(players(2), StudStakes::new(5, 10, 20, 40), &deck).unwrap();
+
+        stud.deal_street().unwrap();
+
+        assert_eq!(Some(0), stud.bring_in_seat());
+    }
+
+    #[test]
+    fn act_complete_and_raise() {
+        let stud = stud();
+        stud.act_antes().unwrap();
+        stud.deal_street().unwrap();
+        stud.act_bring_in().unwrap();
+
+        assert_eq!(PKError::InvalidTableAction, stud.act_raise(1).unwrap_err());
+        stud.act_complete(1).unwrap();
+        assert_eq!(20, stud.table.seats.current_bet());
+        assert_eq!(PKError::InvalidTableAction, stud.act_complete(2).unwrap_err());
+
+        stud.act_raise(2).unwrap();
+
+        assert_eq!(40, stud.table.seats.current_bet());
+        assert_eq!(955, stud.table.get_seat(2).unwrap().player.chips.count());
+    }
+
+    #[test]
+    fn act_bet() {
+        let stud = stud();
+        assert_eq!(PKError::InvalidTableAction, stud.act_bet(1).unwrap_err());
+
+        deal_to(&stud, StudStreet::Fifth);
+        stud.act_bet(1).unwrap();
+
+        assert_eq!(40, stud.table.seats.current_bet());
+        assert_eq!(PKError::InvalidTableAction, stud.act_bet(2).unwrap_err());
+    }
+
+    #[test]
+    fn act_raise__capped() {
+        let stud = stud();
+        deal_to(&stud, StudStreet::Fourth);
+
+        stud.act_bet(2).unwrap();
+        stud.act_raise(0).unwrap();
+        stud.act_raise(1).unwrap();
+        stud.act_raise(2).unwrap();
+
+        assert_eq!(80, stud.table.seats.current_bet());
+        assert_eq!(PKError::InvalidTableAction, stud.act_raise(0).unwrap_err());
+    }
+
+    #[test]
+    fn deal_street__brings_in_bets() {
+        let stud = stud();
+        stud.act_antes().unwrap();
+        stud.deal_street().unwrap();
+        let bring_in = stud.act_bring_in().unwrap();
+        for seat_number in stud.seats_in_hand().into_iter().filter(|s| *s != bring_in) {
+            stud.table.act_call(seat_number).unwrap();
+        }
+        let mut pot = 45;
+
+        while stud.street() != Some(StudStreet::Seventh) {
+            stud.deal_street().unwrap();
+            assert_eq!(pot, stud.table.pot.count());
+            assert_eq!(0, stud.table.seats.current_bet());
+
+            let first = stud.first_to_act().unwrap();
+            stud.act_bet(first).unwrap();
+            for seat_number in stud.seats_in_hand().into_iter().filter(|s| *s != first) {
+                stud.table.act_call(seat_number).unwrap();
+            }
+            pot += 3 * stud.bet_size();
+        }
+        stud.table.bring_it_in().unwrap();
+
+        assert_eq!(465, pot);
+        assert_eq!(pot, stud.table.pot.count());
+    }
+
+    #[test]
+    fn deal_street__action_isnt_finished() {
+        let stud = stud();
+        deal_to(&stud, StudStreet::Fourth);
+
+        stud.act_bet(2).unwrap();
+
+        assert_eq!(PKError::ActionIsntFinished, stud.deal_street().unwrap_err());
+        assert_eq!(Some(StudStreet::Fourth), stud.street());
+    }
+
+    #[test]
+    fn bet_size() {
+        let stud = stud();
+
+        stud.deal_street().unwrap();
+        assert_eq!(20, stud.bet_size());
+        stud.deal_street().unwrap();
+        assert_eq!(20, stud.bet_size());
+        stud.deal_street().unwrap();
+        assert_eq!(40, stud.bet_size());
+        stud.deal_street().unwrap();
+        assert_eq!(40, stud.bet_size());
+        stud.deal_street().unwrap();
+        assert_eq!(40, stud.bet_size());
+    }
+
+    #[test]
+    fn first_to_act() {
+        let stud = stud();
+        assert!(stud.first_to_act().is_none());
+
+        // Seat 0 brings it in, and the action wraps around to seat 1.
+        stud.deal_street().unwrap();
+        assert_eq!(Some(1), stud.first_to_act());
+
+        // K♠ Q♠ vs 4♦ 4♥ vs 4♣ J♣
+        stud.deal_street().unwrap();
+        assert_eq!(Some(2), stud.first_to_act());
+
+        // K♠ Q♠ K♥ vs 4♦ 4♥ 9♣ vs 4♣ J♣ J♦
+        stud.deal_street().unwrap();
+        assert_eq!(Some(1), stud.first_to_act());
+    }
+
+    #[test]
+    fn winners() {
+        let stud = stud();
+        assert_eq!(PKError::NotEnoughCards, stud.winners().unwrap_err());
+
+        deal_to(&stud, StudStreet::Seventh);
+
+        assert_eq!(vec![1], stud.winners().unwrap());
+    }
+
+    /// Nobody makes a low with the `DEAL`, so seat 1's kings full take the whole pot.
+    #[test]
+    fn split_pot_hi_lo() {
+        let stud = stud();
+        assert_eq!(PKError::NotEnoughCards, stud.split_pot_hi_lo(100).unwrap_err());
+
+        deal_to(&stud, StudStreet::Seventh);
+
+        assert!(stud.eval_hi_lo(2).unwrap().low.is_none());
+        assert_eq!(vec![(1, 100), (2, 0), (0, 0)], stud.split_pot_hi_lo(100).unwrap());
+    }
+
+    #[test]
+    fn visible_value() {
+        let value = |s: &str| SevenCardStud::visible_value(&Cards::from_str(s).unwrap());
+
+        assert!(value("A♠") > value("K♠"));
+        assert!(value("2♠ 2♥") > value("A♠ K♠"));
+        assert!(value("3♠ 3♥ 2♦") > value("2♠ 2♥ A♦"));
+        assert!(value("3♠ 3♥ 2♦ 2♣") > value("A♠ A♥ K♦ Q♣"));
+        assert!(value("2♠ 2♥ 2♦") > value("A♠ A♥ K♦ K♣"));
+        assert!(value("2♠ 2♥ 2♦ 2♣") > value("A♠ A♥ A♦ K♣"));
+        assert_eq!(value("A♠ K♠"), value("A♥ K♦"));
+    }
+
+    #[test]
+    fn stud_street__next() {
+        assert_eq!(Some(StudStreet::Fourth), StudStreet::Third.next());
+        assert_eq!(None, StudStreet::Seventh.next());
+        assert!(!StudStreet::Fourth.is_big_bet());
+        assert!(StudStreet::Fifth.is_big_bet());
+    }
+
+    #[test]
+    fn stud_street__counts() {
+        assert_eq!((2, 1), (StudStreet::Third.down_count(), StudStreet::Third.up_count()));
+        assert_eq!((2, 2), (StudStreet::Fourth.down_count(), StudStreet::Fourth.up_count()));
+        assert_eq!((2, 4), (StudStreet::Sixth.down_count(), StudStreet::Sixth.up_count()));
+        assert_eq!(
+            (3, 4),
+            (StudStreet::Seventh.down_count(), StudStreet::Seventh.up_count())
+        );
+    }
+}
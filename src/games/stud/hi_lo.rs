@@ -0,0 +1,477 @@
+use crate::PKError;
+use crate::analysis::eval::Eval;
+use crate::analysis::omaha::EightOrBetterLow;
+use crate::analysis::sampling::Sampling;
+use crate::analysis::seven_card_evaluator::SevenCardEvaluator;
+use crate::arrays::HandRanker;
+use crate::arrays::seven::Seven;
+use crate::card::Card;
+use crate::cards::Cards;
+use crate::games::omaha::hi_lo::HiLoShares;
+use crate::games::razz::equity::RazzEquity;
+use crate::games::stud::{StudHand, StudStreet};
+use crate::rank::Rank;
+use crate::util::wincounter::PlayerFlag;
+use crate::util::wincounter::win::Win;
+use crate::util::wincounter::wins::Wins;
+use rayon::prelude::*;
+use std::fmt::{Display, Formatter};
+
+/// What a Seven Card Stud Hi-Lo hand, aka Stud/8, the "E" in H.O.R.S.E., is worth at showdown.
+///
+/// The high and the low are picked independently, so the same card can play in both halves. A
+/// hand like `A♠ 2♠ 3♠ 4♠ 5♥ K♦ K♣` is a wheel for the low and a five high straight for the high.
+///
+/// ```
+/// use pkcore::arrays::seven::Seven;
+/// use pkcore::games::stud::hi_lo::StudHiLoEval;
+/// use std::str::FromStr;
+///
+/// let eval = StudHiLoEval::from(Seven::from_str("A♠ 2♠ 3♠ 4♠ 5♥ K♦ K♣").unwrap());
+///
+/// assert_eq!("5-4-3-2-A", eval.low.unwrap().to_string());
+/// assert_eq!("A♠", eval.high_card.to_string());
+/// ```
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub struct StudHiLoEval {
+    pub high: Eval,
+    pub low: Option<EightOrBetterLow>,
+    /// The player's highest card, by rank and then by suit, which is what decides who gets the
+    /// odd chip when a half doesn't split evenly.
+    pub high_card: Card,
+}
+
+impl StudHiLoEval {
+    /// Returns the `PlayerFlag` for the winners of the high, and the `PlayerFlag` for the winners
+    /// of the low, which is zero if nobody qualifies. See `HiLoShares::flags()`.
+    #[must_use]
+    pub fn flags(evals: &[StudHiLoEval]) -> (PlayerFlag, PlayerFlag) {
+        HiLoShares::flags(evals.iter().map(|e| (e.high, e.low)))
+    }
+
+    /// Splits a pot of `pot` chips between the players at showdown, returning how many chips each
+    /// one takes, in the same order as the `evals`.
+    ///
+    /// Half goes to the best high and half goes to the best qualifying low. When the pot doesn't
+    /// split evenly the odd chip goes to the high. If nobody has a low, the high takes it all.
+    /// When more than one player splits a half, any odd chips left over go by the stud rule: to
+    /// the player with the highest card, by rank and then by suit, spades, hearts, diamonds, and
+    /// clubs. My first pass handed them out in dealing order, which is the flop game rule, not
+    /// the stud one.
+    ///
+    /// ```
+    /// use pkcore::arrays::seven::Seven;
+    /// use pkcore::games::stud::hi_lo::StudHiLoEval;
+    /// use std::str::FromStr;
+    ///
+    /// let evals = vec![
+    ///     StudHiLoEval::from(Seven::from_str("K♠ K♥ K♦ Q♠ Q♥ J♣ 9♦").unwrap()),
+    ///     StudHiLoEval::from(Seven::from_str("A♠ 2♠ 3♥ 4♦ 6♣ T♠ J♠").unwrap()),
+    /// ];
+    ///
+    /// assert_eq!(vec![51, 50], StudHiLoEval::split_pot(101, &evals));
+    /// ```
+    #[must_use]
+    pub fn split_pot(pot: usize, evals: &[StudHiLoEval]) -> Vec<usize> {
+        let (high, low) = StudHiLoEval::flags(evals);
+        let mut chips = vec![0; evals.len()];
+
+        if low == PlayerFlag::default() {
+            StudHiLoEval::award(pot, high, evals, &mut chips);
+        } else {
+            let low_half = pot / 2;
+            StudHiLoEval::award(pot - low_half, high, evals, &mut chips);
+            StudHiLoEval::award(low_half, low, evals, &mut chips);
+        }
+        chips
+    }
+
+    /// `true` if one player won both halves, or the high when there was no low.
+    #[must_use]
+    pub fn is_scoop(evals: &[StudHiLoEval]) -> bool {
+        let (high, low) = StudHiLoEval::flags(evals);
+        StudHiLoEval::scooper(high, low).is_some()
+    }
+
+    /// The index of the player who took the whole pot, if anyone did.
+    fn scooper(high: PlayerFlag, low: PlayerFlag) -> Option<usize> {
+        if high.is_power_of_two() && (low == PlayerFlag::default() || low == high) {
+            Some(high.trailing_zeros() as usize)
+        } else {
+            None
+        }
+    }
+
+    fn award(amount: usize, flag: PlayerFlag, evals: &[StudHiLoEval], chips: &mut [usize]) {
+        let mut winners: Vec<usize> = (0..chips.len())
+            .filter(|i| flag & Win::from_index(*i) != PlayerFlag::default())
+            .collect();
+        winners.sort_by_key(|i| std::cmp::Reverse(StudHiLoEval::card_order(evals[*i].high_card)));
+        if winners.is_empty() {
+            return;
+        }
+
+        let share = amount / winners.len();
+        let odd = amount % winners.len();
+        for (n, i) in winners.into_iter().enumerate() {
+            chips[i] += share + usize::from(n < odd);
+        }
+    }
+}
+
+impl StudHiLoEval {
+    /// Aces high, and ties go by suit. This is `SevenCardStud::bring_in_seat()` flipped around.
+    fn card_order(card: Card) -> (Rank, u8) {
+        (card.get_rank(), card.get_suit() as u8)
+    }
+}
+
+impl From<Seven> for StudHiLoEval {
+    fn from(seven: Seven) -> Self {
+        StudHiLoEval {
+            high: SevenCardEvaluator::eval(&seven),
+            low: EightOrBetterLow::best(
+                Seven::FIVE_CARD_PERMUTATIONS
+                    .iter()
+                    .map(|perm| seven.five_from_permutation(*perm)),
+            ),
+            high_card: seven
+                .to_arr()
+                .into_iter()
+                .max_by_key(|card| StudHiLoEval::card_order(*card))
+                .unwrap_or_default(),
+        }
+    }
+}
+
+impl Display for StudHiLoEval {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self.low {
+            Some(low) => write!(f, "{} / {low}", self.high),
+            None => write!(f, "{} / No Low", self.high),
+        }
+    }
+}
+
+/// Split pot equity for Stud/8. The completions are the same as `RazzEquity`, exhaustive where
+/// that's possible and sampled where it's not, and the shares are the same as
+/// `OmahaHiLoEquity`, so you can see how often a player scoops versus how often they have to
+/// settle for half.
+///
+/// ```
+/// use pkcore::cards::Cards;
+/// use pkcore::games::stud::{StudHand, StudStreet};
+/// use pkcore::games::stud::hi_lo::StudHiLoEquity;
+/// use std::str::FromStr;
+///
+/// let hands = vec![
+///     StudHand::new(Cards::from_str("A♠ 2♠").unwrap(), Cards::from_str("3♠ 4♠ 5♥ K♦").unwrap()),
+///     StudHand::new(Cards::from_str("K♣ K♥").unwrap(), Cards::from_str("K♠ Q♦ Q♥ J♣").unwrap()),
+/// ];
+///
+/// let equity = StudHiLoEquity::new(hands, Cards::default(), StudStreet::Sixth).unwrap();
+///
+/// // Player one has the wheel locked up, and scoops if they catch the 5♠ for a straight flush.
+/// assert_eq!(1_560, equity.case_count);
+/// assert_eq!(39, equity.scoops[0]);
+/// ```
+#[derive(Clone, Debug, Default)]
+pub struct StudHiLoEquity {
+    pub hands: Vec<StudHand>,
+    pub dead: Cards,
+    pub street: Option<StudStreet>,
+    pub case_count: usize,
+    pub high_wins: Wins,
+    pub low_wins: Wins,
+    pub scoops: Vec<usize>,
+    pub shares: Vec<HiLoShares>,
+    exhaustive: bool,
+}
+
+impl StudHiLoEquity {
+    /// # Errors
+    ///
+    /// See `RazzEquity::with_samples()`.
+    pub fn new(hands: Vec<StudHand>, dead: Cards, street: StudStreet) -> Result<Self, PKError> {
+        StudHiLoEquity::with_sampling(hands, dead, street, Sampling::default())
+    }
+
+    /// # Errors
+    ///
+    /// See `RazzEquity::with_samples()`.
+    pub fn with_samples(
+        hands: Vec<StudHand>,
+        dead: Cards,
+        street: StudStreet,
+        samples: usize,
+    ) -> Result<Self, PKError> {
+        StudHiLoEquity::with_sampling(hands, dead, street, Sampling::new(samples))
+    }
+
+    /// # Errors
+    ///
+    /// See `RazzEquity::with_samples()`.
+    #[allow(clippy::cast_precision_loss)]
+    pub fn with_sampling(
+        hands: Vec<StudHand>,
+        dead: Cards,
+        street: StudStreet,
+        sampling: Sampling,
+    ) -> Result<Self, PKError> {
+        let remaining = RazzEquity::validate(&hands, &dead, street)?;
+        let (cases, exhaustive) = RazzEquity::completions(&hands, &remaining, sampling)?;
+
+        let flags: Vec<(PlayerFlag, PlayerFlag)> = cases
+            .into_par_iter()
+            .filter_map(|case| StudHiLoEquity::case_evals(&hands, &case).ok())
+            .map(|evals| StudHiLoEval::flags(&evals))
+            .collect();
+
+        let mut high_wins = Wins::default();
+        let mut low_wins = Wins::default();
+        let mut scoops = vec![0; hands.len()];
+        let mut shares = vec![HiLoShares::default(); hands.len()];
+        for (high, low) in &flags {
+            high_wins.add(*high);
+            low_wins.add(*low);
+            if let Some(count) = StudHiLoEval::scooper(*high, *low).and_then(|i| scoops.get_mut(i)) {
+                *count += 1;
+            }
+            for (i, share) in shares.iter_mut().enumerate() {
+                share.add(i, *high, *low);
+            }
+        }
+        if !flags.is_empty() {
+            for share in &mut shares {
+                share.divide(flags.len() as f64);
+            }
+        }
+
+        Ok(StudHiLoEquity {
+            hands,
+            dead,
+            street: Some(street),
+            case_count: flags.len(),
+            high_wins,
+            low_wins,
+            scoops,
+            shares,
+            exhaustive,
+        })
+    }
+
+    /// Returns the `StudHiLoEval` for every player once each player's `case` cards have been
+    /// added to their hand.
+    ///
+    /// # Errors
+    ///
+    /// `PKError::NotEnoughCards` or `PKError::TooManyCards` if a hand doesn't come out to seven.
+    pub fn case_evals(hands: &[StudHand], case: &[Cards]) -> Result<Vec<StudHiLoEval>, PKError> {
+        let mut evals = Vec::with_capacity(hands.len());
+        for (hand, cards) in hands.iter().zip(case) {
+            let mut seven = hand.known();
+            seven.insert_all(cards);
+            evals.push(StudHiLoEval::from(Seven::try_from(seven)?));
+        }
+        Ok(evals)
+    }
+
+    /// `true` if every possible completion was dealt, `false` if we sampled.
+    #[must_use]
+    pub fn is_exhaustive(&self) -> bool {
+        self.exhaustive
+    }
+}
+
+impl Display for StudHiLoEquity {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        if let Some(street) = self.street {
+            writeln!(f, "{street}")?;
+        }
+        if !self.dead.is_empty() {
+            writeln!(f, "Dead: {}", self.dead)?;
+        }
+
+        for (i, hand) in self.hands.iter().enumerate() {
+            let shares = self.shares.get(i).copied().unwrap_or_default();
+            writeln!(f, "  Player #{} {} {}", i + 1, hand, shares)?;
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+#[allow(non_snake_case)]
+mod games__stud__hi_lo_tests {
+    use super::*;
+    use rstest::rstest;
+    use std::str::FromStr;
+
+    fn hand(down: &str, up: &str) -> StudHand {
+        StudHand::new(Cards::from_str(down).unwrap_or_default(), Cards::from_str(up).unwrap())
+    }
+
+    fn eval(s: &str) -> StudHiLoEval {
+        StudHiLoEval::from(Seven::from_str(s).unwrap())
+    }
+
+    fn assert_close(expected: f64, actual: f64) {
+        assert!((expected - actual).abs() < 1e-9, "expected {expected} got {actual}");
+    }
+
+    fn wheel_vs_boat() -> Vec<StudHand> {
+        vec![hand("A♠ 2♠", "3♠ 4♠ 5♥ K♦"), hand("K♣ K♥", "K♠ Q♦ Q♥ J♣")]
+    }
+
+    /// Pairs don't count for the low, so `A♠ A♥ 2♦ 2♣ 3♠ 4♥ 8♦` is still 8-4-3-2-A.
+    #[rstest]
+    #[case("8♠ 7♥ 6♦ 5♣ 4♠ 3♥ 2♦", Some("6-5-4-3-2"))]
+    #[case("A♠ A♥ 2♦ 2♣ 3♠ 4♥ 8♦", Some("8-4-3-2-A"))]
+    #[case("A♠ 2♥ 3♦ 7♣ 8♠ K♥ K♦", Some("8-7-3-2-A"))]
+    #[case("A♠ 2♥ 3♦ 9♣ 9♠ K♥ K♦", None)]
+    fn from__seven__low(#[case] seven: &str, #[case] expected: Option<&str>) {
+        assert_eq!(expected.map(String::from), eval(seven).low.map(|low| low.to_string()));
+    }
+
+    #[rstest]
+    #[case("A♠ 2♠ 3♠ 4♠ 5♥ K♦ K♣", "A♠")]
+    #[case("K♠ K♥ K♦ Q♠ Q♥ J♣ 9♦", "K♠")]
+    #[case("2♣ 3♣ 4♣ 5♣ 7♦ 8♣ 8♥", "8♥")]
+    fn from__seven__high_card(#[case] seven: &str, #[case] expected: &str) {
+        assert_eq!(expected, eval(seven).high_card.to_string());
+    }
+
+    #[test]
+    fn from__seven() {
+        let eval = eval("A♠ 2♠ 3♠ 4♠ 5♥ K♦ K♣");
+
+        assert_eq!("5-4-3-2-A", eval.low.unwrap().to_string());
+        assert_eq!(eval.high.hand_rank.value, 1609);
+    }
+
+    #[test]
+    fn display() {
+        assert!(eval("A♠ 2♠ 3♠ 4♠ 5♥ K♦ K♣").to_string().ends_with(" / 5-4-3-2-A"));
+        assert!(eval("K♠ K♥ K♦ Q♠ Q♥ J♣ 9♦").to_string().ends_with(" / No Low"));
+    }
+
+    #[test]
+    fn flags() {
+        let evals = vec![
+            eval("K♠ K♥ K♦ Q♠ Q♥ J♣ 9♦"),
+            eval("A♠ 2♠ 3♥ 4♦ 6♣ T♠ J♠"),
+            eval("A♥ 2♥ 3♦ 4♣ 6♠ T♥ J♥"),
+        ];
+
+        assert_eq!((Win::FIRST, Win::SECOND | Win::THIRD), StudHiLoEval::flags(&evals));
+    }
+
+    #[test]
+    fn split_pot__odd_chip_to_high() {
+        let evals = vec![eval("K♠ K♥ K♦ Q♠ Q♥ J♣ 9♦"), eval("A♠ 2♠ 3♥ 4♦ 6♣ T♠ J♠")];
+
+        assert_eq!(vec![51, 50], StudHiLoEval::split_pot(101, &evals));
+        assert!(!StudHiLoEval::is_scoop(&evals));
+    }
+
+    #[test]
+    fn split_pot__no_low() {
+        let evals = vec![eval("K♠ K♥ K♦ Q♠ Q♥ J♣ 9♦"), eval("A♠ 2♠ 3♥ 9♦ 9♣ T♠ J♠")];
+
+        assert_eq!(vec![101, 0], StudHiLoEval::split_pot(101, &evals));
+        assert!(StudHiLoEval::is_scoop(&evals));
+    }
+
+    #[test]
+    fn split_pot__scoop() {
+        let evals = vec![eval("A♠ 2♠ 3♠ 4♠ 5♥ K♦ K♣"), eval("A♥ 2♥ 3♦ 4♣ 7♠ T♥ J♥")];
+
+        assert_eq!(vec![100, 0], StudHiLoEval::split_pot(100, &evals));
+        assert!(StudHiLoEval::is_scoop(&evals));
+    }
+
+    /// 103 chips is 52 for the high and 51 for the low, which the two lows chop 26 / 25.
+    #[test]
+    fn split_pot__quartered() {
+        let evals = vec![
+            eval("K♠ K♥ K♦ Q♠ Q♥ J♣ 9♦"),
+            eval("A♠ 2♠ 3♥ 4♦ 6♣ T♠ J♠"),
+            eval("A♥ 2♥ 3♦ 4♣ 6♠ T♥ J♥"),
+        ];
+
+        assert_eq!(vec![52, 26, 25], StudHiLoEval::split_pot(103, &evals));
+    }
+
+    /// Same lows, other way around. The odd chip follows the J♠, not the dealing order.
+    #[test]
+    fn split_pot__odd_chip_by_high_card() {
+        let evals = vec![
+            eval("K♠ K♥ K♦ Q♠ Q♥ J♣ 9♦"),
+            eval("A♥ 2♥ 3♦ 4♣ 6♠ T♥ J♥"),
+            eval("A♠ 2♠ 3♥ 4♦ 6♣ T♠ J♠"),
+        ];
+
+        assert_eq!(vec![52, 25, 26], StudHiLoEval::split_pot(103, &evals));
+    }
+
+    /// Two six high straights that are also the same low, and a third player who missed. 101
+    /// chips is 51 for the high and 50 for the low. The high chops 26 / 25 and the K♦ beats the
+    /// Q♠ for the odd chip.
+    #[test]
+    fn split_pot__odd_chip_by_rank_before_suit() {
+        let evals = vec![
+            eval("2♠ 3♠ 4♥ 5♦ 6♣ Q♠ J♠"),
+            eval("2♥ 3♦ 4♣ 5♠ 6♠ K♦ J♥"),
+            eval("T♠ T♥ 9♦ 9♣ 8♠ 8♥ 7♦"),
+        ];
+
+        assert_eq!(vec![50, 51, 0], StudHiLoEval::split_pot(101, &evals));
+    }
+
+    #[test]
+    fn new__seventh() {
+        let hands = vec![hand("A♠ 2♠ 9♥", "3♠ 4♠ 5♥ K♦"), hand("K♣ K♥ 8♣", "K♠ Q♦ Q♥ J♣")];
+
+        let equity = StudHiLoEquity::new(hands, Cards::default(), StudStreet::Seventh).unwrap();
+
+        assert!(equity.is_exhaustive());
+        assert_eq!(1, equity.case_count);
+        assert_eq!(vec![0, 0], equity.scoops);
+        assert_close(0.5, equity.shares[0].low_only);
+        assert_close(0.5, equity.shares[1].high_only);
+    }
+
+    /// 40 x 39 completions. Player one has the low locked, and the only card that gets them
+    /// the high is the 5♠ for a straight flush.
+    #[test]
+    fn new__sixth() {
+        let equity = StudHiLoEquity::new(wheel_vs_boat(), Cards::default(), StudStreet::Sixth).unwrap();
+
+        assert!(equity.is_exhaustive());
+        assert_eq!(1_560, equity.case_count);
+        assert_eq!(vec![39, 0], equity.scoops);
+        assert_close(39.0 / 1_560.0, equity.shares[0].scoop);
+        assert_close(1_521.0 * 0.5 / 1_560.0, equity.shares[0].low_only);
+        assert_close(1_521.0 * 0.5 / 1_560.0, equity.shares[1].high_only);
+        assert_close(1.0, equity.shares[0].equity() + equity.shares[1].equity());
+    }
+
+    #[test]
+    fn new__errors() {
+        assert_eq!(
+            PKError::NotEnoughHands,
+            StudHiLoEquity::new(vec![hand("A♠ 2♠", "3♥")], Cards::default(), StudStreet::Third).unwrap_err()
+        );
+    }
+
+    #[test]
+    fn display__equity() {
+        let equity = StudHiLoEquity::new(wheel_vs_boat(), Cards::default(), StudStreet::Sixth).unwrap();
+
+        let s = equity.to_string();
+
+        assert!(s.starts_with("Sixth Street\n"));
+        assert!(s.contains("Player #1 [A♠ 2♠] 3♠ 4♠ 5♥ K♦ "));
+    }
+}
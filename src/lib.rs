@@ -433,6 +433,7 @@ pub enum PKError {
     InvalidCardNumber,
     InvalidCardCount,
     InvalidComboIndex,
+    InvalidGameType,
     InvalidHand,
     InvalidCardIndex,
     InvalidLength,
@@ -480,6 +481,7 @@ impl Display for PKError {
             PKError::InvalidCardCount => "Invalid Card Count Error",
             PKError::InvalidCardIndex => "Invalid Card Index Error",
             PKError::InvalidComboIndex => "Invalid Combo Index Error",
+            PKError::InvalidGameType => "Invalid Game Type Error",
             PKError::InvalidHand => "Invalid Hand Error",
             PKError::InvalidLength => "Invalid Length Error",
             PKError::InvalidPermutationIndex => "Invalid Permutation Index Error",
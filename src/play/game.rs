@@ -1,7 +1,7 @@
 use crate::analysis::case_eval::CaseEval;
 use crate::analysis::case_evals::CaseEvals;
 use crate::analysis::eval::Eval;
-use crate::analysis::outs::Outs;
+use crate::analysis::outs::{Outs, PlayerOuts};
 use crate::arrays::HandRanker;
 use crate::arrays::four::Four;
 use crate::arrays::seven::Seven;
@@ -248,6 +248,21 @@ impl Game {
         (case_evals, wins, results, outs)
     }
 
+    /// Every player's `PlayerOuts` on the river, from the `CaseEvals` at the turn, so that we can
+    /// say `9 outs, 7 clean` instead of just dumping the cards.
+    ///
+    /// # Errors
+    ///
+    /// Throws `PKError::Fubar` if a player's hand at the turn can't be evaluated.
+    pub fn turn_player_outs(&self, case_evals: &CaseEvals) -> Result<Vec<PlayerOuts>, PKError> {
+        let current = (0..self.hands.len())
+            .map(|i| self.turn_eval_for_player(i))
+            .collect::<Result<Vec<Eval>, PKError>>()?;
+        Ok((0..self.hands.len())
+            .map(|i| PlayerOuts::from_case_evals(i, &current, case_evals))
+            .collect())
+    }
+
     /// # Errors
     ///
     /// Throws `PKError::NotEnoughCards` if there are not enough cards on the `Board`.
@@ -1000,7 +1015,8 @@ mod play__game_tests {
         let hands = HoleCards::from(vec![Two::HAND_AS_KH, Two::HAND_8D_6C]);
         let board = Board::from_str("A♣ 8♥ 7♥ 9♠ 5♠").unwrap();
         let game = Game::new(hands, board);
-        let (_, _, _results, outs) = game.turn_calculations();
+        let (case_evals, _, _results, outs) = game.turn_calculations();
+        let player_outs = game.turn_player_outs(&case_evals).unwrap();
 
         let player1_outs = outs.get(1).unwrap();
         let player2_outs = outs.get(2).unwrap();
@@ -1011,5 +1027,7 @@ mod play__game_tests {
         assert_eq!(31, player1_outs.len());
         assert_eq!(13, player2_outs.len());
         assert_eq!(1, outs.longest_player());
+        assert_eq!(13, player_outs[1].len());
+        assert_eq!(player2_outs.sort(), player_outs[1].cards().sort());
     }
 }
@@ -1,8 +1,12 @@
 use crate::analysis::case_evals::CaseEvals;
 use crate::analysis::eval::Eval;
+use crate::analysis::outs::PlayerOuts;
+use crate::analysis::runouts::RunoutMatrix;
 use crate::arrays::HandRanker;
 use crate::arrays::five::Five;
 use crate::arrays::three::Three;
+use crate::card::Card;
+use crate::play::board::Board;
 use crate::play::game::Game;
 use crate::play::hole_cards::HoleCards;
 use crate::prelude::Table;
@@ -248,6 +252,49 @@ impl FlopEval {
             Ok(eval) => Ok(format!("{} ({})", eval.hand, eval.hand_rank)),
         }
     }
+
+    /// True if nobody has a better hand than the player on the flop.
+    #[must_use]
+    pub fn is_leading(&self, index: usize) -> bool {
+        match self.eval_for_player(index) {
+            Ok(eval) => (0..self.hands.len())
+                .filter_map(|i| self.eval_for_player(i).ok())
+                .all(|other| other.hand_rank <= eval.hand_rank),
+            Err(_) => false,
+        }
+    }
+
+    /// The turn cards that put the player in front, and the runner runners.
+    ///
+    /// # Errors
+    ///
+    /// Returns `PKError::Fubar` if invalid index is passed in.
+    pub fn player_outs(&self, index: usize) -> Result<PlayerOuts, PKError> {
+        let game = Game::new(self.hands.clone(), Board::new(self.board, Card::BLANK, Card::BLANK));
+        PlayerOuts::at_flop(&game, index)
+    }
+
+    /// The outs for every player who's behind on the flop, one line each. This walks every turn
+    /// and river for each of them, so it isn't something you want happening every time you
+    /// print a `FlopEval`. Call it when you want it.
+    #[must_use]
+    pub fn outs_to_string(&self) -> String {
+        (0..self.hands.len())
+            .filter(|i| !self.is_leading(*i))
+            .filter_map(|i| {
+                self.player_outs(i)
+                    .ok()
+                    .map(|outs| format!("  Player #{} OUTS: {outs} - {}", i + 1, outs.cards()))
+            })
+            .collect::<Vec<String>>()
+            .join("\n")
+    }
+
+    /// Every turn card, and every turn and river pair, from the `CaseEvals` we already have.
+    #[must_use]
+    pub fn runouts(&self) -> RunoutMatrix {
+        RunoutMatrix::from_case_evals(self.board, &self.hands, &self.case_evals)
+    }
 }
 
 /// Originally part of our calc example program. When my examples have functionality
@@ -360,6 +407,25 @@ mod play__stages__flop_eval_tests {
         assert_eq!(PKError::NotDealt, sut.unwrap_err());
     }
 
+    #[test]
+    fn runouts() {
+        let sut = FlopEval::try_from(TestData::the_hand()).unwrap();
+        let game = TestData::the_hand();
+
+        assert_eq!(RunoutMatrix::new(game.board.flop, &game.hands).unwrap(), sut.runouts());
+    }
+
+    #[test]
+    fn outs_to_string() {
+        let sut = FlopEval::try_from(TestData::the_hand()).unwrap();
+
+        let outs = sut.outs_to_string();
+
+        assert!(outs.starts_with("  Player #2 OUTS: "));
+        assert!(!outs.contains("Player #1"));
+        assert!(!sut.to_string().contains("OUTS"));
+    }
+
     #[test]
     fn iterations_heads_up() {
         let game = TestData::the_hand();
@@ -2,7 +2,7 @@ use crate::PKError;
 use crate::analysis::case_eval::CaseEval;
 use crate::analysis::case_evals::CaseEvals;
 use crate::analysis::eval::Eval;
-use crate::analysis::outs::Outs;
+use crate::analysis::outs::{Outs, PlayerOuts};
 use crate::arrays::HandRanker;
 use crate::arrays::seven::Seven;
 use crate::arrays::six::Six;
@@ -22,6 +22,8 @@ pub struct TurnEval {
     pub wins: Wins,
     pub results: Results,
     pub outs: Outs,
+    /// Each player's outs, clean and tainted, in the same order as the hands.
+    pub player_outs: Vec<PlayerOuts>,
 }
 
 impl TurnEval {
@@ -182,7 +184,10 @@ impl Display for TurnEval {
 
             if player_id != winning_player {
                 if let Some(cards) = self.outs.get(player_id) {
-                    writeln!(f, "    OUTS: {cards}")?;
+                    match self.player_outs.get(i) {
+                        Some(player_outs) => writeln!(f, "    OUTS: {player_outs} - {cards}")?,
+                        None => writeln!(f, "    OUTS: {cards}")?,
+                    }
                 }
             }
         }
@@ -203,6 +208,7 @@ impl TryFrom<&Game> for TurnEval {
         let wins = case_evals.wins();
         let results = Results::from_wins(&wins, game.hands.len());
         let outs = Outs::from(&case_evals);
+        let player_outs = game.turn_player_outs(&case_evals)?;
 
         Ok(TurnEval {
             game: game.clone(),
@@ -210,6 +216,7 @@ impl TryFrom<&Game> for TurnEval {
             wins,
             results,
             outs,
+            player_outs,
         })
     }
 }
@@ -44,6 +44,20 @@ impl Util {
         }
     }
 
+    /// A count as an `f64`, for dividing by. The casting lint has a point in general, but no
+    /// count of hands or boards we deal is ever getting close to 2^52, so I'd rather allow it
+    /// once here than in every file that needs an average.
+    ///
+    /// ```
+    /// use pkcore::util::Util;
+    /// assert_eq!(1_326.0, Util::as_f64(1_326));
+    /// ```
+    #[must_use]
+    #[allow(clippy::cast_precision_loss)]
+    pub fn as_f64(n: usize) -> f64 {
+        n as f64
+    }
+
     ///
     ///
     /// # Errors
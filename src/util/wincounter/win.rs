@@ -21,6 +21,10 @@ impl Win {
     pub const FIFTEENTH: PlayerFlag = 0b100_0000_0000_0000;
     pub const SIXTEENTH: PlayerFlag = 0b1000_0000_0000_0000;
 
+    /// One bit per player in a `PlayerFlag`, so this is as many players as anything that counts
+    /// wins with it can handle.
+    pub const MAX_PLAYERS: usize = PlayerFlag::BITS as usize;
+
     /// `CaseEval` win count Test #2: TAKE TWO detour.
     ///
     /// Our heroic system has been sidelined. Our heroes need a way to translate a zero based
@@ -1,6 +1,12 @@
+use pkcore::Pile;
+use pkcore::analysis::seven_card_evaluator::SevenCardEvaluator;
 use pkcore::analysis::store::db::hup::HUPResult;
+use pkcore::arrays::HandRanker;
+use pkcore::arrays::seven::Seven;
+use pkcore::cards::Cards;
 use pkcore::util::data::TestData;
 use pkcore::util::wincounter::win::Win;
+use rayon::prelude::*;
 
 #[allow(non_snake_case)]
 mod heavy_tests {
@@ -95,4 +101,36 @@ mod heavy_tests {
 
         assert_eq!(actual, TestData::the_hand_as_hup_result());
     }
+
+    /// Every single one of the 133,784,560 seven card hands, scored by both the 21 permutation
+    /// scan and the `SevenCardEvaluator`. Both the `HandRankValue` and the `Five` need to match.
+    ///
+    /// `cargo test --release --test heavy_tests -- --ignored seven_card_evaluator`
+    #[test]
+    #[ignore]
+    fn seven_card_evaluator__exhaustive() {
+        let deck = Cards::deck().to_vec();
+
+        let checked: usize = (0..deck.len())
+            .into_par_iter()
+            .map(|i| {
+                let rest = Cards::from(deck[i + 1..].to_vec());
+                let mut count = 0;
+                for six in rest.combinations(6) {
+                    let mut v = vec![deck[i]];
+                    v.extend(six);
+                    let seven = Seven::try_from(v).unwrap();
+                    assert_eq!(
+                        seven.hand_rank_value_and_hand(),
+                        SevenCardEvaluator::hand_rank_value_and_hand(&seven),
+                        "{seven}"
+                    );
+                    count += 1;
+                }
+                count
+            })
+            .sum();
+
+        assert_eq!(133_784_560, checked);
+    }
 }